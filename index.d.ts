@@ -10,11 +10,15 @@ export interface ScanOptions {
 }
 
 export interface ScanResultItem {
+  id: string;
   filePath: string;
   lineStart: number;
   lineEnd: number;
+  byteStart: number;
+  byteEnd: number;
   codeSnippet: string;
   kind: string;
+  severity: string;
   name: string;
   reason: string;
   confidence: number;
@@ -32,7 +36,24 @@ export interface ScanResult {
  */
 export function run(args: string[]): Promise<number>;
 
+/**
+ * JSON Schema of the scan output, for LLM tool definitions
+ */
+export function schema(): string;
+
 /**
  * Programmatic scan API for Node.js consumers
  */
-export function scan(options: ScanOptions): Promise<ScanResult>;
+export function scan(options: ScanOptions, token?: CancellationToken | undefined | null): Promise<ScanResult>;
+
+/**
+ * Cancels a running `scan` it was passed to
+ */
+export class CancellationToken {
+  constructor();
+  /**
+   * Stop the scan; it rejects with "Scan cancelled"
+   */
+  cancel(): void;
+  get isCancelled(): boolean;
+}
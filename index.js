@@ -234,7 +234,9 @@ if (!nativeBinding) {
   throw new Error(`Failed to load native binding`);
 }
 
-const { run, scan } = nativeBinding;
+const { run, scan, schema, CancellationToken } = nativeBinding;
 
 module.exports.run = run;
 module.exports.scan = scan;
+module.exports.schema = schema;
+module.exports.CancellationToken = CancellationToken;
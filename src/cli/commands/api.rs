@@ -0,0 +1,73 @@
+//! API command - List the public API surface of a package
+//!
+//! The inverse of a dead code scan: every export reachable from the
+//! package's entry points, for reviewing what consumers can depend on.
+
+use crate::cli::{ApiArgs, ApiFormat};
+use crate::scanner::{load_root_config, package_entries, Scanner};
+use crate::types::{ApiSymbol, ClrConfig};
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::collections::HashSet;
+use std::path::PathBuf;
+
+pub async fn run(root: PathBuf, args: ApiArgs, config_file: Option<PathBuf>) -> Result<i32> {
+    let config = load_root_config(&root, config_file.as_deref(), ClrConfig::default())?;
+    let graph = Scanner::new(&root).with_config(&config).build_graph()?;
+
+    let mut entries = Vec::new();
+    if args.entry.is_empty() {
+        for entry in package_entries(&root) {
+            match graph.resolve_entry(&root.join(&entry)) {
+                Some(file) => entries.push(file),
+                // Usually a build output, which scans skip
+                None => eprintln!(
+                    "{} package.json entry {} is not a scanned file",
+                    "⚠".yellow(),
+                    entry
+                ),
+            }
+        }
+        if entries.is_empty() {
+            bail!("No package.json entry resolves to a scanned file; pass --entry");
+        }
+    } else {
+        for entry in &args.entry {
+            match graph.resolve_entry(&root.join(entry)) {
+                Some(file) => entries.push(file),
+                None => bail!("Entry {} is not a scanned file", entry.display()),
+            }
+        }
+    }
+    let mut seen = HashSet::new();
+    entries.retain(|entry| seen.insert(entry.clone()));
+
+    let api = graph.public_api(&root, &entries);
+    match args.format {
+        ApiFormat::Pretty => print_pretty(&api),
+        ApiFormat::Json => println!("{}", serde_json::to_string_pretty(&api)?),
+    }
+    Ok(0)
+}
+
+fn print_pretty(api: &[ApiSymbol]) {
+    let mut entry = None;
+    for symbol in api {
+        if entry != Some(&symbol.entry) {
+            if entry.is_some() {
+                println!();
+            }
+            println!("{}", symbol.entry.bold().underline());
+            entry = Some(&symbol.entry);
+        }
+        println!(
+            "  {} {} {}:{}",
+            symbol.kind.to_string().dimmed(),
+            symbol.name.cyan(),
+            symbol.relative_path,
+            symbol.line
+        );
+    }
+    println!();
+    println!("{} {} exported symbols", "✓".green(), api.len());
+}
@@ -0,0 +1,193 @@
+//! Diff command - Compare two scan outputs
+//!
+//! Findings are matched by their stable `id`, so moving code around a file
+//! does not count as a change.
+
+use crate::cli::{DiffArgs, DiffFormat};
+use crate::types::{DeadCodeItem, ScanOutput};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::Serialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub async fn run(root: PathBuf, args: DiffArgs) -> Result<i32> {
+    let old = load_output(&root.join(&args.old))?;
+    let new = load_output(&root.join(&args.new))?;
+    for (path, output) in [(&args.old, &old), (&args.new, &new)] {
+        if output.dead_code.len() < output.total_findings as usize {
+            eprintln!(
+                "{} {} lists {} of {} findings; the diff only covers those listed",
+                "⚠".yellow(),
+                path.display(),
+                output.dead_code.len(),
+                output.total_findings
+            );
+        }
+    }
+
+    let diff = ScanDiff::between(&old, &new);
+    match args.format {
+        DiffFormat::Pretty => print_pretty(&diff),
+        DiffFormat::Json => println!("{}", serde_json::to_string_pretty(&diff)?),
+    }
+
+    // New findings fail the run, so CI can flag regressions
+    Ok(if diff.added.is_empty() { 0 } else { 1 })
+}
+
+pub(super) fn load_output(path: &Path) -> Result<ScanOutput> {
+    let json =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    serde_json::from_str(&json)
+        .with_context(|| format!("{} is not a clrd JSON scan output", path.display()))
+}
+
+/// Findings added, removed and kept between two scans
+#[derive(Debug, Serialize)]
+struct ScanDiff {
+    added: Vec<DeadCodeItem>,
+    removed: Vec<DeadCodeItem>,
+    unchanged: Vec<DeadCodeItem>,
+    /// Findings in the new scan minus findings in the old one
+    net_change: i64,
+    /// Net change per kind, for kinds whose count changed
+    kind_changes: BTreeMap<String, i64>,
+}
+
+impl ScanDiff {
+    fn between(old: &ScanOutput, new: &ScanOutput) -> Self {
+        let old_ids: HashSet<&str> = old.dead_code.iter().map(|item| item.id.as_str()).collect();
+        let new_ids: HashSet<&str> = new.dead_code.iter().map(|item| item.id.as_str()).collect();
+
+        let added: Vec<DeadCodeItem> = new
+            .dead_code
+            .iter()
+            .filter(|item| !old_ids.contains(item.id.as_str()))
+            .cloned()
+            .collect();
+        let removed: Vec<DeadCodeItem> = old
+            .dead_code
+            .iter()
+            .filter(|item| !new_ids.contains(item.id.as_str()))
+            .cloned()
+            .collect();
+        let unchanged: Vec<DeadCodeItem> = new
+            .dead_code
+            .iter()
+            .filter(|item| old_ids.contains(item.id.as_str()))
+            .cloned()
+            .collect();
+
+        let mut kind_changes: BTreeMap<String, i64> = BTreeMap::new();
+        for item in &added {
+            *kind_changes.entry(item.kind.to_string()).or_default() += 1;
+        }
+        for item in &removed {
+            *kind_changes.entry(item.kind.to_string()).or_default() -= 1;
+        }
+        kind_changes.retain(|_, change| *change != 0);
+
+        Self {
+            net_change: added.len() as i64 - removed.len() as i64,
+            added,
+            removed,
+            unchanged,
+            kind_changes,
+        }
+    }
+}
+
+fn print_pretty(diff: &ScanDiff) {
+    let net = format!("{:+}", diff.net_change);
+    let net = match diff.net_change {
+        change if change > 0 => net.red(),
+        change if change < 0 => net.green(),
+        _ => net.normal(),
+    };
+    println!("{} {}", "Net change:".bold(), net);
+    println!(
+        "  {} added, {} removed, {} unchanged",
+        diff.added.len(),
+        diff.removed.len(),
+        diff.unchanged.len()
+    );
+
+    for (heading, sign, items) in [
+        ("Added", "+".red(), &diff.added),
+        ("Removed", "-".green(), &diff.removed),
+    ] {
+        if items.is_empty() {
+            continue;
+        }
+        println!();
+        println!("{}", heading.bold().underline());
+        for item in items {
+            println!(
+                "  {} {} {} {} ({:.0}%)",
+                sign,
+                item.kind.to_string().dimmed(),
+                item.name.cyan(),
+                item.location(),
+                item.confidence * 100.0
+            );
+        }
+    }
+
+    if !diff.kind_changes.is_empty() {
+        println!();
+        println!("{}", "By kind".bold().underline());
+        for (kind, change) in &diff.kind_changes {
+            println!("  {:<22} {:+}", kind, change);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::Scanner;
+    use crate::types::DeadCodeKind;
+
+    fn item(path: &str, name: &str, kind: DeadCodeKind) -> DeadCodeItem {
+        DeadCodeItem::fixture(path, name, kind)
+    }
+
+    #[tokio::test]
+    async fn test_diff_matches_findings_by_id() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut old = Scanner::new(dir.path()).scan().await.unwrap();
+        let mut new = old.clone();
+        old.dead_code = vec![
+            item("src/a.ts", "shared", DeadCodeKind::UnusedExport),
+            item("src/a.ts", "fixed", DeadCodeKind::UnusedImport),
+        ];
+        let mut moved = item("src/a.ts", "shared", DeadCodeKind::UnusedExport);
+        moved.span.start = 40;
+        new.dead_code = vec![
+            moved,
+            item("src/b.ts", "fresh", DeadCodeKind::UnusedExport),
+            item("src/b.ts", "other", DeadCodeKind::UnusedExport),
+        ];
+
+        // Round-trip through JSON as `clrd diff` reads files
+        let old: ScanOutput = serde_json::from_str(&serde_json::to_string(&old).unwrap()).unwrap();
+        let diff = ScanDiff::between(&old, &new);
+
+        let names = |items: &[DeadCodeItem]| -> Vec<String> {
+            items.iter().map(|item| item.name.clone()).collect()
+        };
+        assert_eq!(names(&diff.added), vec!["fresh", "other"]);
+        assert_eq!(names(&diff.removed), vec!["fixed"]);
+        assert_eq!(names(&diff.unchanged), vec!["shared"]);
+        assert_eq!(diff.net_change, 1);
+        assert_eq!(
+            diff.kind_changes,
+            BTreeMap::from([
+                ("unused_export".to_string(), 2),
+                ("unused_import".to_string(), -1),
+            ])
+        );
+    }
+}
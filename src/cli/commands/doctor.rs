@@ -0,0 +1,181 @@
+//! Doctor command - Diagnose configuration and environment
+//!
+//! Read-only: reports what a scan of the root would see, so silent
+//! misconfigurations (wrong root, no files matched, bad globs) show up.
+
+use crate::scanner::{
+    default_ignore_patterns, load_root_config, FileWalker, CONFIG_FILE, TSCONFIG_FILE,
+};
+use crate::types::ClrConfig;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::{Path, PathBuf};
+use std::process::Command;
+
+/// What a scan of one root would see
+#[derive(Debug)]
+struct Diagnosis {
+    root: PathBuf,
+    root_exists: bool,
+    config_files: Vec<PathBuf>,
+    /// Why the root `clrd.toml` (or `--config` file) could not be read
+    config_error: Option<String>,
+    extensions: Vec<String>,
+    ignore_patterns: Vec<String>,
+    invalid_ignore_patterns: Vec<(String, String)>,
+    file_count: usize,
+    has_tsconfig: bool,
+    has_package_json: bool,
+    /// `git --version`, when git is installed
+    git_version: Option<String>,
+}
+
+pub async fn run(root: PathBuf, config_file: Option<PathBuf>) -> Result<i32> {
+    let diagnosis = diagnose(&root, config_file.as_deref())?;
+    print_diagnosis(&diagnosis);
+    Ok(0)
+}
+
+fn diagnose(root: &Path, config_file: Option<&Path>) -> Result<Diagnosis> {
+    let root = root.canonicalize().unwrap_or_else(|_| root.to_path_buf());
+    let root_exists = root.is_dir();
+
+    let (config, config_error) = match load_root_config(&root, config_file, ClrConfig::default()) {
+        Ok(config) => (config, None),
+        Err(error) => (ClrConfig::default(), Some(format!("{:#}", error))),
+    };
+
+    let ignore_patterns = default_ignore_patterns();
+    let walker = FileWalker::new(&root)
+        .with_extensions(&config.extensions)
+        .with_ignore_patterns(&ignore_patterns)
+        .with_test_patterns(&config.test_file_globs)
+        .include_tests(config.include_tests);
+    let (file_count, mut config_files) = if root_exists {
+        (walker.collect_files()?.len(), walker.collect_config_files())
+    } else {
+        (0, Vec::new())
+    };
+    config_files.sort();
+    // `--config` takes the place of the root `clrd.toml`
+    if let Some(path) = config_file {
+        config_files.retain(|file| *file != root.join(CONFIG_FILE));
+        config_files.insert(0, path.to_path_buf());
+    }
+
+    Ok(Diagnosis {
+        root_exists,
+        config_files,
+        config_error,
+        extensions: config.extensions,
+        invalid_ignore_patterns: walker.invalid_ignore_patterns().to_vec(),
+        ignore_patterns,
+        file_count,
+        has_tsconfig: root.join(TSCONFIG_FILE).is_file(),
+        has_package_json: root.join("package.json").is_file(),
+        git_version: git_version(),
+        root,
+    })
+}
+
+fn git_version() -> Option<String> {
+    let output = Command::new("git").arg("--version").output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+fn print_diagnosis(diagnosis: &Diagnosis) {
+    let ok = |text: String| println!("  {} {}", "✓".green(), text);
+    let warn = |text: String| println!("  {} {}", "⚠".yellow(), text);
+
+    println!("{}", "clrd doctor".bold());
+    println!();
+
+    if diagnosis.root_exists {
+        ok(format!("Root: {}", diagnosis.root.display()));
+    } else {
+        warn(format!(
+            "Root {} is not a directory",
+            diagnosis.root.display()
+        ));
+    }
+
+    match &diagnosis.config_error {
+        Some(error) => warn(error.clone()),
+        None if diagnosis.config_files.is_empty() => {
+            warn(format!("No {} found; using defaults", CONFIG_FILE))
+        }
+        None => ok(format!("Config files: {}", diagnosis.config_files.len())),
+    }
+    for file in &diagnosis.config_files {
+        let shown = file.strip_prefix(&diagnosis.root).unwrap_or(file);
+        println!("      {}", shown.display().to_string().dimmed());
+    }
+
+    ok(format!("Extensions: {}", diagnosis.extensions.join(", ")));
+    ok(format!(
+        "Ignore patterns: {}",
+        diagnosis.ignore_patterns.join(", ")
+    ));
+    for (pattern, error) in &diagnosis.invalid_ignore_patterns {
+        warn(format!("Invalid ignore pattern `{}`: {}", pattern, error));
+    }
+
+    if diagnosis.file_count == 0 {
+        warn("No files would be scanned; check the root and extensions".to_string());
+    } else {
+        ok(format!("Files to scan: {}", diagnosis.file_count));
+    }
+
+    for (name, found) in [
+        (TSCONFIG_FILE, diagnosis.has_tsconfig),
+        ("package.json", diagnosis.has_package_json),
+    ] {
+        if found {
+            ok(format!("{} found", name));
+        } else {
+            warn(format!("No {} at the root", name));
+        }
+    }
+
+    match &diagnosis.git_version {
+        Some(version) => ok(version.clone()),
+        None => warn("git not found; fix --commit needs it".to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    #[test]
+    fn test_empty_dir_has_no_files_or_config() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let diagnosis = diagnose(dir.path(), None).unwrap();
+        assert!(diagnosis.root_exists);
+        assert_eq!(diagnosis.file_count, 0);
+        assert!(diagnosis.config_files.is_empty());
+        assert!(diagnosis.config_error.is_none());
+        assert!(!diagnosis.has_tsconfig);
+        assert!(!diagnosis.has_package_json);
+    }
+
+    #[test]
+    fn test_counts_files_and_config() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join(CONFIG_FILE), "extensions = [\"ts\"]\n").unwrap();
+        fs::write(dir.path().join("package.json"), "{}").unwrap();
+        fs::write(dir.path().join("a.ts"), "export const a = 1;\n").unwrap();
+        fs::write(dir.path().join("b.js"), "export const b = 1;\n").unwrap();
+
+        let diagnosis = diagnose(dir.path(), None).unwrap();
+        assert_eq!(diagnosis.file_count, 1);
+        assert_eq!(diagnosis.config_files.len(), 1);
+        assert_eq!(diagnosis.extensions, vec!["ts".to_string()]);
+        assert!(diagnosis.has_package_json);
+    }
+}
@@ -0,0 +1,195 @@
+//! Explain command - Detailed report for a single finding
+
+use crate::cli::ExplainArgs;
+use crate::scanner::{load_root_config, ReferenceGraph, Scanner, EXPORT_BASE_CONFIDENCE};
+use crate::types::{ClrConfig, DeadCodeItem, DeadCodeKind};
+use anyhow::{bail, Result};
+use colored::Colorize;
+use std::fmt::Write as _;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub async fn run(root: PathBuf, args: ExplainArgs, config_file: Option<PathBuf>) -> Result<i32> {
+    let mut config = load_root_config(&root, config_file.as_deref(), ClrConfig::default())?;
+    config.include_tests |= args.include_tests;
+    let scanner = Scanner::new(&root).with_config(&config);
+    let graph = scanner.build_graph()?;
+    let dead_code = graph.find_dead_code(&root, 0.0)?;
+
+    let (file, name) = match args.target.rsplit_once(':') {
+        Some((file, name)) => (file, Some(name)),
+        None => (args.target.as_str(), None),
+    };
+
+    let matches: Vec<&DeadCodeItem> = dead_code
+        .iter()
+        .filter(|item| item.relative_path == file || item.file_path.ends_with(file))
+        .filter(|item| name.map_or(true, |name| item.name == name))
+        .filter(|item| {
+            args.kind
+                .as_ref()
+                .map_or(true, |kind| item.kind.to_string() == *kind)
+        })
+        .collect();
+
+    match matches.as_slice() {
+        [] => {
+            println!(
+                "{}",
+                format!("No finding matches '{}'", args.target).yellow()
+            );
+            Ok(1)
+        }
+        [item] => {
+            print!("{}", render_explanation(item, &graph, &root)?);
+            Ok(0)
+        }
+        _ => {
+            println!(
+                "{}",
+                format!("'{}' matches {} findings:", args.target, matches.len()).yellow()
+            );
+            for item in &matches {
+                println!(
+                    "  {} {}:{} ({})",
+                    "→".dimmed(),
+                    item.relative_path,
+                    item.name,
+                    item.kind
+                );
+            }
+            println!("Use {} to disambiguate", "--kind".cyan());
+            bail!("ambiguous target '{}'", args.target)
+        }
+    }
+}
+
+/// Render the expanded report for one finding
+fn render_explanation(item: &DeadCodeItem, graph: &ReferenceGraph, root: &Path) -> Result<String> {
+    let mut out = String::new();
+
+    writeln!(out, "{}", format!("{} ({})", item.name, item.kind).bold())?;
+    writeln!(out, "  {} {}", "File:".bold(), item.relative_path.cyan())?;
+    writeln!(
+        out,
+        "  {} {}-{}",
+        "Lines:".bold(),
+        item.span.start,
+        item.span.end
+    )?;
+    writeln!(out, "  {} {}", "Reason:".bold(), item.reason)?;
+    writeln!(out)?;
+
+    // Confidence breakdown
+    writeln!(
+        out,
+        "{} {:.0}%",
+        "Confidence:".bold(),
+        item.confidence * 100.0
+    )?;
+    if item.kind == DeadCodeKind::UnusedExport {
+        writeln!(
+            out,
+            "  base                           {:+.2}",
+            EXPORT_BASE_CONFIDENCE
+        )?;
+        for (factor, delta) in graph.export_confidence_factors(&item.file_path, &item.name) {
+            writeln!(out, "  {:<30} {:+.2}", factor, delta)?;
+        }
+    }
+    writeln!(out)?;
+
+    // Context fields
+    if let Some(context) = &item.context {
+        writeln!(out, "{}", "Context:".bold())?;
+        writeln!(out, "  possibly_dynamic: {}", context.possibly_dynamic)?;
+        writeln!(out, "  in_test_file:     {}", context.in_test_file)?;
+        writeln!(out, "  public_api:       {}", context.public_api)?;
+        writeln!(out, "  has_todo:         {}", context.has_todo)?;
+        if let Some(doc) = &context.doc_comment {
+            writeln!(out, "  doc_comment:      {}", doc)?;
+        }
+        writeln!(out)?;
+    }
+
+    // References elsewhere in the project
+    writeln!(out, "{}", "References:".bold())?;
+    let importers = graph.importers_of(&item.file_path);
+    let same_name = graph.files_importing_name(&item.name, &item.file_path);
+    if importers.is_empty() && same_name.is_empty() {
+        writeln!(out, "  (none)")?;
+    }
+    for path in &importers {
+        writeln!(out, "  imports this file:  {}", relative(path, root))?;
+    }
+    for path in &same_name {
+        writeln!(out, "  imports '{}': {}", item.name, relative(path, root))?;
+    }
+    writeln!(out)?;
+
+    // Full, untruncated code
+    writeln!(out, "{}", "Code:".bold())?;
+    for line in full_snippet(item)?.lines() {
+        writeln!(out, "  {}", line)?;
+    }
+
+    Ok(out)
+}
+
+/// The full source lines covered by a finding
+fn full_snippet(item: &DeadCodeItem) -> Result<String> {
+    if item.kind == DeadCodeKind::ZombieFile {
+        return Ok(item.code_snippet.clone());
+    }
+
+    let content = fs::read_to_string(&item.file_path)?;
+    let lines: Vec<&str> = content.lines().collect();
+    let start = (item.span.start as usize)
+        .saturating_sub(1)
+        .min(lines.len());
+    let end = (item.span.end as usize).min(lines.len()).max(start);
+
+    Ok(lines[start..end].join("\n"))
+}
+
+fn relative(path: &Path, root: &Path) -> String {
+    pathdiff::diff_paths(path, root)
+        .unwrap_or_else(|| path.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn test_explain_unused_export() {
+        let dir = tempdir().unwrap();
+        fs::write(
+            dir.path().join("utils.ts"),
+            "export function used() {}\nexport function unusedHelper() {\n  return 1;\n}\n",
+        )
+        .unwrap();
+        fs::write(
+            dir.path().join("index.ts"),
+            "import { used } from './utils';\nused();\n",
+        )
+        .unwrap();
+
+        let graph = Scanner::new(dir.path()).build_graph().unwrap();
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let item = dead_code
+            .iter()
+            .find(|item| item.name == "unusedHelper")
+            .unwrap();
+
+        let report = render_explanation(item, &graph, dir.path()).unwrap();
+
+        assert!(report.contains(&item.reason));
+        assert!(report.contains("possibly_dynamic: false"));
+        assert!(report.contains("imports this file:  index.ts"));
+        assert!(report.contains("  return 1;"));
+    }
+}
@@ -1,17 +1,21 @@
 //! Fix command - Remove or comment out dead code
 
+use super::undo;
 use crate::cli::FixArgs;
-use crate::scanner::Scanner;
-use crate::types::{DeadCodeItem, DeadCodeKind};
-use anyhow::{bail, Result};
+use crate::scanner::{content_hash, load_root_config, read_source, AstAnalyzer, Scanner};
+use crate::types::{
+    ClrConfig, DeadCodeItem, DeadCodeKind, LlmJudgmentResponse, RemovalAction, RemovalEstimate,
+};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
-use std::collections::HashMap;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use std::collections::{BTreeMap, HashMap};
 use std::fs;
 use std::io::{self, Write};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use std::process::Command;
 
-pub async fn run(root: PathBuf, args: FixArgs) -> Result<i32> {
+pub async fn run(root: PathBuf, args: FixArgs, config_file: Option<PathBuf>) -> Result<i32> {
     // Check git status if forcing
     if args.force && !is_git_clean(&root)? {
         bail!("Git working directory is not clean. Commit or stash changes before using --force");
@@ -19,7 +23,9 @@ pub async fn run(root: PathBuf, args: FixArgs) -> Result<i32> {
 
     // Run scan first
     println!("{}", "🔍 Scanning for dead code...".bold());
-    let scanner = Scanner::new(&root).with_confidence_threshold(args.confidence);
+    let mut config = load_root_config(&root, config_file.as_deref(), ClrConfig::default())?;
+    config.confidence_threshold = args.confidence;
+    let scanner = Scanner::new(&root).with_config(&config);
     let scan_output = scanner.scan().await?;
 
     if scan_output.dead_code.is_empty() {
@@ -27,15 +33,57 @@ pub async fn run(root: PathBuf, args: FixArgs) -> Result<i32> {
         return Ok(0);
     }
 
-    // Filter by files if specified
-    let items: Vec<&DeadCodeItem> = if let Some(ref files) = args.files {
-        scan_output
-            .dead_code
-            .iter()
-            .filter(|item| files.iter().any(|f| item.file_path.ends_with(f)))
+    // Filter by files if specified; circular imports and redundant exports are still in use
+    let removable = scan_output
+        .dead_code
+        .iter()
+        .filter(|item| item.kind.is_fixable());
+    let candidates: Vec<&DeadCodeItem> = if let Some(ref files) = args.files {
+        let globs = compile_file_globs(files);
+        removable
+            .filter(|item| is_targeted(item, files, &globs))
             .collect()
     } else {
-        scan_output.dead_code.iter().collect()
+        removable.collect()
+    };
+
+    // Pick the action for each item, deferring to a judgment when given
+    let default_action = if args.soft {
+        RemovalAction::CommentOut
+    } else {
+        RemovalAction::Delete
+    };
+    let items: Vec<(&DeadCodeItem, RemovalAction)> = match &args.from_judgment {
+        Some(path) => {
+            let judgment: LlmJudgmentResponse = serde_json::from_str(&fs::read_to_string(path)?)
+                .with_context(|| format!("Invalid judgment file {}", path.display()))?;
+            for rejected in &judgment.rejected {
+                println!(
+                    "  {} Skipping {} ({}): {}",
+                    "↷".dimmed(),
+                    rejected.name.bold(),
+                    rejected.file_path.dimmed(),
+                    rejected.reason
+                );
+            }
+            // There is no trash to move code into, so those are left alone
+            let (trash, confirmed): (Vec<_>, Vec<_>) = select_confirmed(&candidates, &judgment)
+                .into_iter()
+                .partition(|(_, action)| matches!(action, RemovalAction::MoveToTrash));
+            for (item, _) in trash {
+                println!(
+                    "  {} Skipping {} ({}): move_to_trash is not supported, use delete or comment_out",
+                    "↷".dimmed(),
+                    item.name.bold(),
+                    item.relative_path.dimmed()
+                );
+            }
+            confirmed
+        }
+        None => candidates
+            .into_iter()
+            .map(|item| (item, default_action))
+            .collect(),
     };
 
     if items.is_empty() {
@@ -52,7 +100,7 @@ pub async fn run(root: PathBuf, args: FixArgs) -> Result<i32> {
     println!();
 
     // Show preview
-    for (i, item) in items.iter().enumerate().take(10) {
+    for (i, (item, _)) in items.iter().enumerate().take(10) {
         println!(
             "  {}. {} {} ({})",
             i + 1,
@@ -65,9 +113,46 @@ pub async fn run(root: PathBuf, args: FixArgs) -> Result<i32> {
     if items.len() > 10 {
         println!("  ... and {} more", items.len() - 10);
     }
+    let removals = items
+        .iter()
+        .map(|(item, _)| *item)
+        .filter(|item| item.kind.is_removable());
+    let estimate = RemovalEstimate::of(removals, |path| {
+        fs::read_to_string(path).map_or(0, |content| content.lines().count() as u32)
+    });
+    println!();
+    println!(
+        "  {} ~{} lines across {} files",
+        if args.soft { "Comments out" } else { "Removes" },
+        estimate.lines.to_string().cyan(),
+        estimate.files
+    );
+
+    // Group items by file for efficient processing
+    let mut by_file: BTreeMap<PathBuf, Vec<(&DeadCodeItem, RemovalAction)>> = BTreeMap::new();
+    for (item, action) in &items {
+        by_file
+            .entry(item.file_path.clone())
+            .or_default()
+            .push((item, *action));
+    }
 
     // Dry run mode
     if args.dry_run {
+        let width = crossterm::terminal::size().map_or(100, |(cols, _)| cols as usize);
+        for (file_path, file_items) in &by_file {
+            let content = fs::read_to_string(file_path)
+                .with_context(|| format!("Failed to read {}", file_path.display()))?;
+            let rewritten = rewrite_imports(file_path, &content, file_items);
+            let edits = plan_edits(content.lines().count(), file_items);
+            println!();
+            println!("{}", file_items[0].0.relative_path.bold());
+            print_diff(
+                &render_diff(&content, &rewritten, &edits, args.context),
+                width,
+            );
+        }
+
         println!();
         println!("{}", "Dry run mode - no changes made".yellow().bold());
         println!("Run without {} to apply changes", "--dry-run".cyan());
@@ -77,41 +162,74 @@ pub async fn run(root: PathBuf, args: FixArgs) -> Result<i32> {
     // Confirmation
     if !args.force {
         println!();
-        print!("Apply these changes? [y/N] ");
-        io::stdout().flush()?;
-
-        let mut input = String::new();
-        io::stdin().read_line(&mut input)?;
-
-        if !input.trim().eq_ignore_ascii_case("y") {
+        if !confirm("Apply these changes?")? {
             println!("Aborted.");
             return Ok(0);
         }
     }
 
-    // Group items by file for efficient processing
-    let mut by_file: HashMap<PathBuf, Vec<&DeadCodeItem>> = HashMap::new();
-    for item in &items {
-        by_file
-            .entry(item.file_path.clone())
-            .or_default()
-            .push(item);
-    }
+    // Journal the original contents so `clrd undo` can restore them
+    let journal = undo::record(&root, by_file.keys())?;
+    println!("  Saved undo journal to {}", journal.display());
 
     // Apply fixes
     let mut fixed = 0;
     let mut errors = 0;
+    let mut fixed_files = Vec::new();
+    let mut deleted_files = Vec::new();
+    let mut fixed_kinds: BTreeMap<String, usize> = BTreeMap::new();
 
     for (file_path, file_items) in by_file {
-        match apply_fixes(&file_path, &file_items, args.soft) {
-            Ok(count) => {
+        // Spans from the scan no longer describe a file edited since
+        if !unchanged_since_scan(&file_path, &scan_output.content_hashes) {
+            if args.force {
+                println!(
+                    "  {} {} changed since the scan; fixing anyway (--force)",
+                    "⚠".yellow(),
+                    file_path.display()
+                );
+            } else {
+                errors += 1;
+                println!(
+                    "  {} Skipping {}: modified since the scan (rerun, or use {})",
+                    "✗".red(),
+                    file_path.display(),
+                    "--force".cyan()
+                );
+                continue;
+            }
+        }
+
+        match apply_fixes(&file_path, &file_items) {
+            Ok((count, emptied)) => {
                 fixed += count;
+                for (item, _) in &file_items {
+                    *fixed_kinds.entry(item.kind.to_string()).or_default() += 1;
+                }
                 println!(
                     "  {} Fixed {} items in {}",
                     "✓".green(),
                     count,
                     file_path.display()
                 );
+
+                // Nothing left but whitespace and comments: the file itself is dead
+                if emptied {
+                    if args.soft {
+                        println!(
+                            "  {} {} is now empty; consider deleting it",
+                            "⚠".yellow(),
+                            file_path.display()
+                        );
+                    } else if args.force
+                        || confirm(&format!("Delete now-empty {}?", file_path.display()))?
+                    {
+                        fs::remove_file(&file_path)?;
+                        println!("  {} Deleted {}", "✓".green(), file_path.display());
+                        deleted_files.push(file_path.clone());
+                    }
+                }
+                fixed_files.push(file_path);
             }
             Err(e) => {
                 errors += 1;
@@ -120,12 +238,30 @@ pub async fn run(root: PathBuf, args: FixArgs) -> Result<i32> {
         }
     }
 
+    if args.prune_dirs {
+        for dir in prune_empty_dirs(&root, &deleted_files)? {
+            println!("  {} Removed empty {}", "✓".green(), dir.display());
+        }
+    }
+
     println!();
     println!(
         "{}",
         format!("Fixed {} items with {} errors", fixed, errors).bold()
     );
 
+    if args.commit {
+        match commit_fixes(&root, &fixed_files, &fixed_kinds)? {
+            CommitOutcome::Committed => println!("  {} Committed the removals", "✓".green()),
+            CommitOutcome::NothingToCommit => println!("  No changes to commit"),
+            CommitOutcome::NotARepository => println!(
+                "  {} Not a git repository, skipping {}",
+                "⚠".yellow(),
+                "--commit".cyan()
+            ),
+        }
+    }
+
     if errors > 0 {
         Ok(1)
     } else {
@@ -134,8 +270,9 @@ pub async fn run(root: PathBuf, args: FixArgs) -> Result<i32> {
 }
 
 fn is_git_clean(root: &PathBuf) -> Result<bool> {
+    // clrd's own scan cache and undo journal don't count
     let output = Command::new("git")
-        .args(["status", "--porcelain"])
+        .args(["status", "--porcelain", "--", ":/", ":(exclude).clrd"])
         .current_dir(root)
         .output();
 
@@ -145,43 +282,346 @@ fn is_git_clean(root: &PathBuf) -> Result<bool> {
     }
 }
 
-fn apply_fixes(file_path: &PathBuf, items: &[&DeadCodeItem], soft: bool) -> Result<usize> {
-    let content = fs::read_to_string(file_path)?;
-    let lines: Vec<&str> = content.lines().collect();
+/// Result of committing applied fixes
+#[derive(Debug, PartialEq, Eq)]
+enum CommitOutcome {
+    Committed,
+    NothingToCommit,
+    NotARepository,
+}
+
+/// Stage `files` and commit only them, with a message counting removals
+/// per kind; anything else already staged stays staged
+fn commit_fixes(
+    root: &Path,
+    files: &[PathBuf],
+    kinds: &BTreeMap<String, usize>,
+) -> Result<CommitOutcome> {
+    let git = |args: &[&str], paths: &[&PathBuf]| {
+        Command::new("git")
+            .args(args)
+            .arg("--")
+            .args(paths)
+            .current_dir(root)
+            .status()
+    };
+    let in_repo = Command::new("git")
+        .args(["rev-parse", "--is-inside-work-tree"])
+        .current_dir(root)
+        .output()
+        .is_ok_and(|output| output.status.success());
+    if !in_repo {
+        return Ok(CommitOutcome::NotARepository);
+    }
+
+    // A deleted file git never knew about has nothing to commit
+    let mut paths = Vec::new();
+    for file in files {
+        let tracked = || {
+            Command::new("git")
+                .args(["ls-files", "--error-unmatch", "--"])
+                .arg(file)
+                .current_dir(root)
+                .output()
+                .is_ok_and(|output| output.status.success())
+        };
+        if file.exists() || tracked() {
+            paths.push(file);
+        }
+    }
+    if paths.is_empty() {
+        return Ok(CommitOutcome::NothingToCommit);
+    }
+
+    if !git(&["add"], &paths)?.success() {
+        bail!("git add failed");
+    }
+
+    // `--quiet` exits 0 when the index matches HEAD for these paths
+    if git(&["diff", "--cached", "--quiet"], &paths)?.success() {
+        return Ok(CommitOutcome::NothingToCommit);
+    }
+
+    let total: usize = kinds.values().sum();
+    let mut message = format!("Remove {} dead code items found by clrd\n\n", total);
+    for (kind, count) in kinds {
+        message.push_str(&format!("- {}: {}\n", kind, count));
+    }
+
+    if !git(&["commit", "--quiet", "-m", &message], &paths)?.success() {
+        bail!("git commit failed");
+    }
+
+    Ok(CommitOutcome::Committed)
+}
+
+/// Pair each finding confirmed by the judgment with its requested action
+fn select_confirmed<'a>(
+    items: &[&'a DeadCodeItem],
+    judgment: &LlmJudgmentResponse,
+) -> Vec<(&'a DeadCodeItem, RemovalAction)> {
+    items
+        .iter()
+        .filter_map(|item| {
+            let confirmed = judgment
+                .confirmed
+                .iter()
+                .find(|confirmed| refers_to(&confirmed.file_path, &confirmed.name, item))?;
+            Some((*item, confirmed.action))
+        })
+        .collect()
+}
 
-    // Sort items by line number in reverse order to avoid offset issues
-    let mut sorted_items = items.to_vec();
-    sorted_items.sort_by(|a, b| b.span.start.cmp(&a.span.start));
+/// Whether a judgment entry names `item`, by relative or absolute path
+pub(super) fn refers_to(file_path: &str, name: &str, item: &DeadCodeItem) -> bool {
+    name == item.name && (file_path == item.relative_path || Path::new(file_path) == item.file_path)
+}
 
-    let mut new_lines: Vec<String> = lines.iter().map(|s| s.to_string()).collect();
+/// Whether `file_path` still has the contents it had when it was scanned;
+/// read the way the scan read it, so invalid UTF-8 hashes the same
+fn unchanged_since_scan(file_path: &Path, content_hashes: &HashMap<PathBuf, u64>) -> bool {
+    let Some(&scanned) = content_hashes.get(file_path) else {
+        return false;
+    };
+    read_source(file_path, None).is_ok_and(|source| content_hash(source.as_str()) == scanned)
+}
 
-    for item in &sorted_items {
-        let start = (item.span.start as usize).saturating_sub(1);
-        let end = (item.span.end as usize).min(new_lines.len());
+/// Compile `--files` arguments as globs over relative paths, skipping invalid ones
+fn compile_file_globs(files: &[PathBuf]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for file in files {
+        let pattern = file.to_string_lossy();
+        match Glob::new(&pattern) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(e) => tracing::warn!("Invalid --files pattern {:?}: {}", pattern, e),
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
+
+/// Whether `item` is in a file matched by a `--files` glob, or whose path
+/// ends with one of the given paths
+fn is_targeted(item: &DeadCodeItem, files: &[PathBuf], globs: &GlobSet) -> bool {
+    globs.is_match(&item.relative_path) || files.iter().any(|f| item.file_path.ends_with(f))
+}
+
+/// Remove the directories of deleted files that are now empty, walking up
+/// until a non-empty directory or `root`; symlinks and paths outside `root`
+/// are left alone
+fn prune_empty_dirs(root: &Path, deleted: &[PathBuf]) -> Result<Vec<PathBuf>> {
+    let mut removed = Vec::new();
 
-        if soft {
-            // Comment out the code
-            for line in new_lines.iter_mut().take(end).skip(start) {
-                *line = format!("// [clrd] {}", line);
+    for file in deleted {
+        let mut dir = file.parent();
+        while let Some(current) = dir {
+            if current == root || !current.starts_with(root) {
+                break;
             }
-        } else {
-            // Remove the lines
-            // Mark for removal
-            for line in new_lines.iter_mut().take(end).skip(start) {
-                *line = "\x00REMOVE\x00".to_string();
+            let is_real_dir = fs::symlink_metadata(current).is_ok_and(|meta| meta.is_dir());
+            if !is_real_dir || fs::read_dir(current)?.next().is_some() {
+                break;
             }
+            fs::remove_dir(current)?;
+            removed.push(current.to_path_buf());
+            dir = current.parent();
         }
     }
 
-    // Remove marked lines
-    let final_lines: Vec<String> = new_lines
-        .into_iter()
-        .filter(|l| l != "\x00REMOVE\x00")
+    Ok(removed)
+}
+
+/// Ask a yes/no question on stdin, defaulting to no
+fn confirm(question: &str) -> Result<bool> {
+    print!("{} [y/N] ", question);
+    io::stdout().flush()?;
+
+    let mut input = String::new();
+    io::stdin().read_line(&mut input)?;
+
+    Ok(input.trim().eq_ignore_ascii_case("y"))
+}
+
+/// Apply the fixes to a file, returning how many were applied and whether
+/// only whitespace and comments remain
+fn apply_fixes(
+    file_path: &PathBuf,
+    items: &[(&DeadCodeItem, RemovalAction)],
+) -> Result<(usize, bool)> {
+    let content = rewrite_imports(file_path, &fs::read_to_string(file_path)?, items);
+    let edits = plan_edits(content.lines().count(), items);
+
+    let final_lines: Vec<String> = content
+        .lines()
+        .zip(&edits)
+        .filter_map(|(line, edit)| match edit {
+            LineEdit::Keep => Some(line.to_string()),
+            LineEdit::CommentOut => Some(format!("// [clrd] {}", line)),
+            LineEdit::Remove => None,
+        })
         .collect();
 
     fs::write(file_path, final_lines.join("\n"))?;
 
-    Ok(sorted_items.len())
+    // Judge the lines as they were: commented-out code is still kept code
+    let emptied = content
+        .lines()
+        .zip(&edits)
+        .filter(|(_, edit)| **edit != LineEdit::Remove)
+        .all(|(line, _)| {
+            let line = line.trim();
+            line.is_empty()
+                || line.starts_with("//")
+                || line.starts_with("/*")
+                || line.starts_with('*')
+        });
+
+    Ok((items.len(), emptied))
+}
+
+/// Turn the file's type-only import findings into `import type`; the
+/// insertions leave every line where it was
+fn rewrite_imports(
+    file_path: &Path,
+    content: &str,
+    items: &[(&DeadCodeItem, RemovalAction)],
+) -> String {
+    let names: Vec<&str> = items
+        .iter()
+        .filter(|(item, _)| item.kind == DeadCodeKind::TypeOnlyImport)
+        .map(|(item, _)| item.name.as_str())
+        .collect();
+    if names.is_empty() {
+        return content.to_string();
+    }
+    AstAnalyzer::make_imports_type_only(file_path, content, &names)
+}
+
+/// What a fix does to one line of a file
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum LineEdit {
+    Keep,
+    Remove,
+    CommentOut,
+}
+
+/// Decide the edit for each of a file's lines; removal wins over commenting out
+fn plan_edits(line_count: usize, items: &[(&DeadCodeItem, RemovalAction)]) -> Vec<LineEdit> {
+    let mut edits = vec![LineEdit::Keep; line_count];
+
+    for (item, action) in items.iter().filter(|(item, _)| item.kind.is_removable()) {
+        let start = (item.span.start as usize).saturating_sub(1);
+        let end = (item.span.end as usize).min(line_count);
+
+        let edit = match action {
+            RemovalAction::Delete => LineEdit::Remove,
+            RemovalAction::CommentOut => LineEdit::CommentOut,
+            // Skipped when the judgment is read; never removed for good
+            RemovalAction::MoveToTrash => continue,
+        };
+        for line in edits.iter_mut().take(end).skip(start) {
+            if *line != LineEdit::Remove {
+                *line = edit;
+            }
+        }
+    }
+
+    edits
+}
+
+/// One line of a dry-run diff; line numbers are 1-indexed in the original file
+#[derive(Debug, PartialEq, Eq)]
+enum DiffLine {
+    /// Start of a hunk at the given line
+    Hunk(usize),
+    Context(usize, String),
+    Removed(usize, String),
+    Added(String),
+}
+
+/// Unified-diff-style view of the planned edits to the `rewritten` lines
+/// with `context` lines around changes
+fn render_diff(
+    original: &str,
+    rewritten: &str,
+    edits: &[LineEdit],
+    context: usize,
+) -> Vec<DiffLine> {
+    let lines: Vec<&str> = original.lines().collect();
+    let new_lines: Vec<&str> = rewritten.lines().collect();
+    let changed: Vec<usize> = (0..lines.len())
+        .filter(|&i| {
+            edits.get(i).is_some_and(|edit| *edit != LineEdit::Keep)
+                || new_lines.get(i) != lines.get(i)
+        })
+        .collect();
+
+    let mut visible = vec![false; lines.len()];
+    for &i in &changed {
+        let end = (i + context).min(lines.len() - 1);
+        for shown in visible
+            .iter_mut()
+            .take(end + 1)
+            .skip(i.saturating_sub(context))
+        {
+            *shown = true;
+        }
+    }
+
+    let mut diff = Vec::new();
+    for (i, line) in lines.iter().enumerate() {
+        if !visible[i] {
+            continue;
+        }
+        if i == 0 || !visible[i - 1] {
+            diff.push(DiffLine::Hunk(i + 1));
+        }
+        let new_line = new_lines.get(i).copied().unwrap_or(line);
+        match edits[i] {
+            LineEdit::Keep if new_line == *line => {
+                diff.push(DiffLine::Context(i + 1, line.to_string()))
+            }
+            LineEdit::Keep => {
+                diff.push(DiffLine::Removed(i + 1, line.to_string()));
+                diff.push(DiffLine::Added(new_line.to_string()));
+            }
+            LineEdit::Remove => diff.push(DiffLine::Removed(i + 1, line.to_string())),
+            LineEdit::CommentOut => {
+                diff.push(DiffLine::Removed(i + 1, line.to_string()));
+                diff.push(DiffLine::Added(format!("// [clrd] {}", new_line)));
+            }
+        }
+    }
+
+    diff
+}
+
+/// Print a diff, cutting lines to fit a terminal `width` columns wide
+fn print_diff(diff: &[DiffLine], width: usize) {
+    // Line number, marker and spacing take 8 columns
+    let fit = |text: &str| -> String {
+        let max = width.saturating_sub(8).max(10);
+        if text.chars().count() > max {
+            let cut: String = text.chars().take(max - 1).collect();
+            format!("{}…", cut)
+        } else {
+            text.to_string()
+        }
+    };
+
+    for line in diff {
+        match line {
+            DiffLine::Hunk(start) => println!("{}", format!("@@ line {} @@", start).cyan()),
+            DiffLine::Context(number, text) => {
+                println!("{} {}", format!("{:>5}  ", number).dimmed(), fit(text))
+            }
+            DiffLine::Removed(number, text) => {
+                println!("{}", format!("{:>5} - {}", number, fit(text)).red())
+            }
+            DiffLine::Added(text) => println!("{}", format!("{:>5} + {}", "", fit(text)).green()),
+        }
+    }
 }
 
 fn kind_to_action(kind: &DeadCodeKind) -> &'static str {
@@ -196,5 +636,362 @@ fn kind_to_action(kind: &DeadCodeKind) -> &'static str {
         UnusedClass => "Remove class",
         UnusedEnum => "Remove enum",
         DeadBranch => "Remove branch",
+        TypeOnlyImport => "Make type-only",
+        CircularImport => "Break cycle",
+        RedundantExport => "Make local",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn git(root: &Path, args: &[&str]) -> String {
+        let output = Command::new("git")
+            .args(args)
+            .current_dir(root)
+            .output()
+            .unwrap();
+        assert!(output.status.success(), "git {:?} failed", args);
+        String::from_utf8_lossy(&output.stdout).to_string()
+    }
+
+    #[test]
+    fn test_commit_fixes_creates_commit() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "--quiet"]);
+        git(root, &["config", "user.name", "clrd"]);
+        git(root, &["config", "user.email", "clrd@example.com"]);
+
+        let file = root.join("utils.ts");
+        fs::write(&file, "export const a = 1;\nexport const b = 2;\n").unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "--quiet", "-m", "initial"]);
+
+        let kinds = BTreeMap::from([
+            ("unused_export".to_string(), 2),
+            ("unused_import".to_string(), 1),
+        ]);
+
+        // Nothing changed yet
+        let outcome = commit_fixes(root, std::slice::from_ref(&file), &kinds).unwrap();
+        assert_eq!(outcome, CommitOutcome::NothingToCommit);
+
+        fs::write(&file, "").unwrap();
+        let outcome = commit_fixes(root, &[file], &kinds).unwrap();
+        assert_eq!(outcome, CommitOutcome::Committed);
+
+        let message = git(root, &["log", "-1", "--format=%B"]);
+        assert!(message.starts_with("Remove 3 dead code items found by clrd"));
+        assert!(message.contains("- unused_export: 2"));
+        assert!(message.contains("- unused_import: 1"));
+    }
+
+    #[test]
+    fn test_git_clean_ignores_clrd_dir() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        git(&root, &["init", "--quiet"]);
+
+        fs::create_dir_all(root.join(".clrd/scans")).unwrap();
+        fs::write(root.join(".clrd/scans/head.json"), "{}").unwrap();
+        assert!(is_git_clean(&root).unwrap());
+
+        fs::write(root.join("utils.ts"), "export const a = 1;\n").unwrap();
+        assert!(!is_git_clean(&root).unwrap());
+    }
+
+    #[test]
+    fn test_commit_fixes_leaves_other_staged_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "--quiet"]);
+        git(root, &["config", "user.name", "clrd"]);
+        git(root, &["config", "user.email", "clrd@example.com"]);
+
+        let (fixed, gone, other) = (
+            root.join("utils.ts"),
+            root.join("empty.ts"),
+            root.join("other.ts"),
+        );
+        fs::write(&fixed, "export const a = 1;\nexport const b = 2;\n").unwrap();
+        fs::write(&gone, "export const c = 3;\n").unwrap();
+        git(root, &["add", "."]);
+        git(root, &["commit", "--quiet", "-m", "initial"]);
+
+        fs::write(&other, "export const d = 4;\n").unwrap();
+        git(root, &["add", "other.ts"]);
+        fs::write(&fixed, "export const a = 1;").unwrap();
+        fs::remove_file(&gone).unwrap();
+
+        let kinds = BTreeMap::from([("unused_export".to_string(), 2)]);
+        let outcome = commit_fixes(root, &[fixed, gone], &kinds).unwrap();
+        assert_eq!(outcome, CommitOutcome::Committed);
+
+        let committed = git(root, &["show", "--name-status", "--format=", "HEAD"]);
+        assert_eq!(committed, "D\tempty.ts\nM\tutils.ts\n");
+        let staged = git(root, &["diff", "--cached", "--name-only"]);
+        assert_eq!(staged, "other.ts\n");
+    }
+
+    #[test]
+    fn test_commit_fixes_outside_repository() {
+        let dir = tempfile::tempdir().unwrap();
+
+        let outcome = commit_fixes(dir.path(), &[], &BTreeMap::new()).unwrap();
+
+        assert_eq!(outcome, CommitOutcome::NotARepository);
+    }
+
+    #[tokio::test]
+    async fn test_fix_from_judgment() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let file = root.join("utils.ts");
+        fs::write(&file, "export const stale = 1;\nexport const wanted = 2;\n").unwrap();
+
+        let judgment = root.join("judgment.json");
+        fs::write(
+            &judgment,
+            r#"{
+                "confirmed": [{"file_path": "utils.ts", "name": "stale", "action": "delete"}],
+                "rejected": [{"file_path": "utils.ts", "name": "wanted", "reason": "public API"}]
+            }"#,
+        )
+        .unwrap();
+
+        let args = FixArgs::from_flags(&["--force", "--from-judgment", judgment.to_str().unwrap()]);
+        assert_eq!(run(root, args, None).await.unwrap(), 0);
+
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "export const wanted = 2;"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fix_skips_move_to_trash() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let file = root.join("utils.ts");
+        let original = "export const stale = 1;\nexport const old = 2;\n";
+        fs::write(&file, original).unwrap();
+
+        let judgment = root.join("judgment.json");
+        fs::write(
+            &judgment,
+            r#"{
+                "confirmed": [
+                    {"file_path": "utils.ts", "name": "stale", "action": "move_to_trash"},
+                    {"file_path": "utils.ts", "name": "old", "action": "move_to_trash"}
+                ],
+                "rejected": []
+            }"#,
+        )
+        .unwrap();
+
+        let args = FixArgs::from_flags(&["--force", "--from-judgment", judgment.to_str().unwrap()]);
+        assert_eq!(run(root, args, None).await.unwrap(), 0);
+
+        assert_eq!(fs::read_to_string(&file).unwrap(), original);
+    }
+
+    #[test]
+    fn test_render_diff() {
+        let original = "a\nb\nc\nd\ne\nf\ng\nh\n";
+        let mut edits = vec![LineEdit::Keep; 8];
+        edits[1] = LineEdit::Remove;
+        edits[6] = LineEdit::CommentOut;
+
+        let diff = render_diff(original, original, &edits, 1);
+
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Hunk(1),
+                DiffLine::Context(1, "a".to_string()),
+                DiffLine::Removed(2, "b".to_string()),
+                DiffLine::Context(3, "c".to_string()),
+                DiffLine::Hunk(6),
+                DiffLine::Context(6, "f".to_string()),
+                DiffLine::Removed(7, "g".to_string()),
+                DiffLine::Added("// [clrd] g".to_string()),
+                DiffLine::Context(8, "h".to_string()),
+            ]
+        );
+
+        // Overlapping hunks merge
+        assert_eq!(
+            render_diff(original, original, &edits, 2)
+                .iter()
+                .filter(|line| matches!(line, DiffLine::Hunk(_)))
+                .count(),
+            1
+        );
+    }
+
+    #[tokio::test]
+    async fn test_fix_deletes_emptied_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let file = root.join("utils.ts");
+        fs::write(&file, "// helpers\n\nexport const unused = 1;\n").unwrap();
+
+        let args = FixArgs::from_flags(&["--force"]);
+        assert_eq!(run(root, args, None).await.unwrap(), 0);
+
+        assert!(!file.exists());
+    }
+
+    #[test]
+    fn test_apply_fixes_reports_emptied_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("utils.ts");
+        fs::write(&file, "export const a = 1;\nexport const b = 2;\n").unwrap();
+        let item = |name: &str, line: u32| {
+            DeadCodeItem::fixture("utils.ts", name, DeadCodeKind::UnusedExport)
+                .in_root(dir.path())
+                .at_line(line)
+        };
+        let (a, b) = (item("a", 1), item("b", 2));
+
+        let result = apply_fixes(&file, &[(&a, RemovalAction::Delete)]).unwrap();
+        assert_eq!(result, (1, false));
+
+        fs::write(&file, "export const a = 1;\nexport const b = 2;\n").unwrap();
+        let result = apply_fixes(
+            &file,
+            &[(&a, RemovalAction::Delete), (&b, RemovalAction::Delete)],
+        )
+        .unwrap();
+        assert_eq!(result, (2, true));
+
+        // Commented-out code keeps the file alive
+        fs::write(&file, "export const a = 1;\nexport const b = 2;\n").unwrap();
+        let result = apply_fixes(
+            &file,
+            &[(&a, RemovalAction::Delete), (&b, RemovalAction::CommentOut)],
+        )
+        .unwrap();
+        assert_eq!(result, (2, false));
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "// [clrd] export const b = 2;"
+        );
+    }
+
+    #[test]
+    fn test_prune_empty_dirs() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        let nested = root.join("src/features/legacy");
+        fs::create_dir_all(&nested).unwrap();
+        fs::write(root.join("src/main.ts"), "").unwrap();
+        fs::write(nested.join("old.ts"), "").unwrap();
+
+        let deleted = nested.join("old.ts");
+        fs::remove_file(&deleted).unwrap();
+        let removed = prune_empty_dirs(root, &[deleted]).unwrap();
+
+        assert_eq!(removed, vec![nested.clone(), root.join("src/features")]);
+        assert!(!root.join("src/features").exists());
+        assert!(root.join("src/main.ts").exists());
+    }
+
+    #[test]
+    fn test_files_filter_globs() {
+        let item = |path: &str| {
+            DeadCodeItem::fixture(path, "x", DeadCodeKind::UnusedExport)
+                .in_root(Path::new("/project"))
+        };
+        let targeted = |files: &[&str], path: &str| {
+            let files: Vec<PathBuf> = files.iter().map(PathBuf::from).collect();
+            is_targeted(&item(path), &files, &compile_file_globs(&files))
+        };
+
+        // Directory glob
+        assert!(targeted(&["src/legacy/**"], "src/legacy/old.ts"));
+        assert!(targeted(&["src/legacy/**"], "src/legacy/deep/older.ts"));
+        assert!(!targeted(&["src/legacy/**"], "src/current/new.ts"));
+
+        // Single file, by exact relative path or path suffix
+        assert!(targeted(&["src/utils.ts"], "src/utils.ts"));
+        assert!(targeted(&["utils.ts"], "src/utils.ts"));
+        assert!(!targeted(&["src/utils.ts"], "src/other.ts"));
+    }
+
+    #[tokio::test]
+    async fn test_file_modified_after_scan_is_stale() {
+        let dir = tempfile::tempdir().unwrap();
+        let edited = dir.path().join("edited.ts");
+        let untouched = dir.path().join("untouched.ts");
+        let latin1 = dir.path().join("latin1.ts");
+        fs::write(&edited, "export const a = 1;\n").unwrap();
+        fs::write(&untouched, "export const b = 2;\n").unwrap();
+        fs::write(&latin1, b"// caf\xe9\nexport const c = 3;\n").unwrap();
+
+        let scan_output = Scanner::new(dir.path()).scan().await.unwrap();
+        fs::write(&edited, "// new header\nexport const a = 1;\n").unwrap();
+
+        let hashes = &scan_output.content_hashes;
+        assert!(!unchanged_since_scan(&edited, hashes));
+        assert!(unchanged_since_scan(&untouched, hashes));
+        assert!(unchanged_since_scan(&latin1, hashes));
+        assert!(!unchanged_since_scan(
+            &dir.path().join("missing.ts"),
+            hashes
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fix_rewrites_type_only_import() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::write(
+            root.join("config.ts"),
+            "export class Config {}\nexport function load(): Config { return new Config(); }\n",
+        )
+        .unwrap();
+        let index = root.join("index.ts");
+        fs::write(
+            &index,
+            "import { Config, load } from './config';\n\
+             import { Config as Settings } from './config';\n\
+             export const config: Config = load();\n\
+             export const settings: Settings = config;\n",
+        )
+        .unwrap();
+
+        let args = FixArgs::from_flags(&["--force", "--confidence", "0.5", "--files", "index.ts"]);
+        assert_eq!(run(root, args, None).await.unwrap(), 0);
+
+        // The unused `settings` export goes too; the imports are rewritten in place
+        let fixed = fs::read_to_string(&index).unwrap();
+        assert!(fixed.starts_with(
+            "import { type Config, load } from './config';\n\
+             import type { Config as Settings } from './config';\n"
+        ));
+    }
+
+    #[tokio::test]
+    async fn test_fix_honors_root_config() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        fs::write(
+            root.join("clrd.toml"),
+            "always_used_exports = [\"keepMe\"]\n",
+        )
+        .unwrap();
+        let file = root.join("utils.ts");
+        fs::write(&file, "export const keepMe = 1;\nexport const drop = 2;\n").unwrap();
+
+        let args = FixArgs::from_flags(&["--force"]);
+        assert_eq!(run(root, args, None).await.unwrap(), 0);
+
+        assert_eq!(
+            fs::read_to_string(&file).unwrap(),
+            "export const keepMe = 1;"
+        );
     }
 }
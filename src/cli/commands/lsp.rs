@@ -0,0 +1,274 @@
+//! LSP command - Publish dead code findings as editor diagnostics
+
+use crate::cli::LspArgs;
+use crate::scanner::{load_root_config, read_source, Scanner, SourceText};
+use crate::types::{ClrConfig, DeadCodeItem};
+use anyhow::Result;
+use std::collections::{HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use tower_lsp::jsonrpc::Result as RpcResult;
+use tower_lsp::lsp_types::*;
+use tower_lsp::{Client, LanguageServer, LspService, Server};
+
+pub async fn run(root: PathBuf, args: LspArgs, config_file: Option<PathBuf>) -> Result<i32> {
+    let (service, socket) = LspService::new(|client| Backend {
+        client,
+        root: Mutex::new(root),
+        confidence: args.confidence,
+        include_tests: args.include_tests,
+        config_file,
+        published: tokio::sync::Mutex::new(HashSet::new()),
+    });
+
+    Server::new(tokio::io::stdin(), tokio::io::stdout(), socket)
+        .serve(service)
+        .await;
+
+    Ok(0)
+}
+
+struct Backend {
+    client: Client,
+    root: Mutex<PathBuf>,
+    confidence: f64,
+    include_tests: bool,
+    /// `--config`, read on every rescan in place of the root `clrd.toml`
+    config_file: Option<PathBuf>,
+    /// Documents that currently have diagnostics; also serializes rescans
+    published: tokio::sync::Mutex<HashSet<Url>>,
+}
+
+impl Backend {
+    /// Rescan the workspace and replace all published diagnostics
+    async fn refresh(&self) {
+        let mut published = self.published.lock().await;
+
+        let root = self.root.lock().unwrap().clone();
+        let config =
+            match load_root_config(&root, self.config_file.as_deref(), ClrConfig::default()) {
+                Ok(mut config) => {
+                    config.confidence_threshold = self.confidence;
+                    config.include_tests |= self.include_tests;
+                    config
+                }
+                Err(e) => {
+                    self.client
+                        .log_message(MessageType::ERROR, format!("clrd config invalid: {e:#}"))
+                        .await;
+                    return;
+                }
+            };
+        let scanner = Scanner::new(&root).with_config(&config);
+
+        let output = match scanner.scan().await {
+            Ok(output) => output,
+            Err(e) => {
+                self.client
+                    .log_message(MessageType::ERROR, format!("clrd scan failed: {e}"))
+                    .await;
+                return;
+            }
+        };
+
+        let mut diagnostics = diagnostics_by_file(&output.dead_code);
+
+        // Clear files whose findings have all gone away
+        for uri in published.drain() {
+            diagnostics.entry(uri).or_default();
+        }
+
+        for (uri, file_diagnostics) in diagnostics {
+            if !file_diagnostics.is_empty() {
+                published.insert(uri.clone());
+            }
+            self.client
+                .publish_diagnostics(uri, file_diagnostics, None)
+                .await;
+        }
+    }
+}
+
+#[tower_lsp::async_trait]
+impl LanguageServer for Backend {
+    async fn initialize(&self, params: InitializeParams) -> RpcResult<InitializeResult> {
+        #[allow(deprecated)]
+        let workspace_root = params
+            .workspace_folders
+            .and_then(|folders| folders.into_iter().next())
+            .map(|folder| folder.uri)
+            .or(params.root_uri)
+            .and_then(|uri| uri.to_file_path().ok());
+
+        if let Some(root) = workspace_root {
+            *self.root.lock().unwrap() = root;
+        }
+
+        Ok(InitializeResult {
+            capabilities: ServerCapabilities {
+                text_document_sync: Some(TextDocumentSyncCapability::Options(
+                    TextDocumentSyncOptions {
+                        open_close: Some(true),
+                        save: Some(TextDocumentSyncSaveOptions::Supported(true)),
+                        ..Default::default()
+                    },
+                )),
+                ..Default::default()
+            },
+            server_info: Some(ServerInfo {
+                name: "clrd".to_string(),
+                version: Some(env!("CARGO_PKG_VERSION").to_string()),
+            }),
+        })
+    }
+
+    async fn initialized(&self, _: InitializedParams) {
+        self.refresh().await;
+    }
+
+    async fn shutdown(&self) -> RpcResult<()> {
+        Ok(())
+    }
+
+    async fn did_open(&self, _: DidOpenTextDocumentParams) {
+        self.refresh().await;
+    }
+
+    async fn did_save(&self, _: DidSaveTextDocumentParams) {
+        self.refresh().await;
+    }
+}
+
+/// Group findings into diagnostics per document
+pub fn diagnostics_by_file(items: &[DeadCodeItem]) -> HashMap<Url, Vec<Diagnostic>> {
+    let mut diagnostics: HashMap<Url, Vec<Diagnostic>> = HashMap::new();
+    let mut sources: HashMap<&Path, Option<SourceText>> = HashMap::new();
+    for item in items {
+        if let Ok(uri) = Url::from_file_path(&item.file_path) {
+            let source = sources
+                .entry(&item.file_path)
+                .or_insert_with(|| read_source(&item.file_path, None).ok());
+            let source = source.as_ref().map_or("", SourceText::as_str);
+            diagnostics
+                .entry(uri)
+                .or_default()
+                .push(to_diagnostic(item, source));
+        }
+    }
+    diagnostics
+}
+
+/// Convert a finding in `source` into an LSP diagnostic
+pub fn to_diagnostic(item: &DeadCodeItem, source: &str) -> Diagnostic {
+    let range = Range {
+        start: to_position(source, item.span.start, item.span.col_start),
+        end: to_position(source, item.span.end, item.span.col_end),
+    };
+
+    let severity = if item.confidence >= 0.8 {
+        DiagnosticSeverity::WARNING
+    } else if item.confidence >= 0.5 {
+        DiagnosticSeverity::INFORMATION
+    } else {
+        DiagnosticSeverity::HINT
+    };
+
+    Diagnostic {
+        range,
+        severity: Some(severity),
+        code: Some(NumberOrString::String(item.kind.to_string())),
+        source: Some("clrd".to_string()),
+        message: format!(
+            "{} (confidence {:.0}%)",
+            item.reason,
+            item.confidence * 100.0
+        ),
+        tags: Some(vec![DiagnosticTag::UNNECESSARY]),
+        ..Default::default()
+    }
+}
+
+/// Convert a 1-indexed line and byte column into an LSP position, which
+/// counts UTF-16 code units; the byte column stands if the line is unknown
+fn to_position(source: &str, line: u32, col: u32) -> Position {
+    let line = line.saturating_sub(1);
+    // Columns count from after the BOM, as editors show the text
+    let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+    let text = source.split('\n').nth(line as usize).unwrap_or("");
+    let character = match text.get(..col as usize) {
+        Some(prefix) => prefix.encode_utf16().count() as u32,
+        None => col,
+    };
+    Position::new(line, character)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DeadCodeKind;
+
+    fn item(path: &str, line: u32, confidence: f64) -> DeadCodeItem {
+        let mut item = DeadCodeItem {
+            confidence,
+            reason: "Export 'helper' has 0 references in the codebase".to_string(),
+            ..DeadCodeItem::fixture(
+                path.trim_start_matches('/'),
+                "helper",
+                DeadCodeKind::UnusedExport,
+            )
+            .in_root(Path::new("/"))
+            .at_line(line)
+        };
+        item.span.col_end = 24;
+        item
+    }
+
+    #[test]
+    fn test_to_diagnostic() {
+        let diagnostic = to_diagnostic(&item("/repo/utils.ts", 3, 0.9), "");
+
+        assert_eq!(diagnostic.range.start, Position::new(2, 0));
+        assert_eq!(diagnostic.range.end, Position::new(2, 24));
+        assert_eq!(diagnostic.severity, Some(DiagnosticSeverity::WARNING));
+        assert_eq!(diagnostic.source.as_deref(), Some("clrd"));
+        assert_eq!(
+            diagnostic.code,
+            Some(NumberOrString::String("unused_export".to_string()))
+        );
+        assert_eq!(
+            to_diagnostic(&item("/repo/utils.ts", 3, 0.6), "").severity,
+            Some(DiagnosticSeverity::INFORMATION)
+        );
+        assert_eq!(
+            to_diagnostic(&item("/repo/utils.ts", 3, 0.3), "").severity,
+            Some(DiagnosticSeverity::HINT)
+        );
+    }
+
+    #[test]
+    fn test_diagnostic_columns_are_utf16() {
+        let source = "\u{feff}// 😀\n\n/* 😀 */ export const helper = 1;\n";
+        let mut finding = item("/repo/utils.ts", 3, 0.9);
+        (finding.span.col_start, finding.span.col_end) = (11, 35);
+
+        let diagnostic = to_diagnostic(&finding, source);
+
+        assert_eq!(diagnostic.range.start, Position::new(2, 9));
+        assert_eq!(diagnostic.range.end, Position::new(2, 33));
+    }
+
+    #[test]
+    fn test_diagnostics_grouped_by_file() {
+        let items = [
+            item("/repo/a.ts", 1, 0.9),
+            item("/repo/b.ts", 2, 0.9),
+            item("/repo/a.ts", 5, 0.9),
+        ];
+
+        let diagnostics = diagnostics_by_file(&items);
+
+        assert_eq!(diagnostics.len(), 2);
+        let a = &diagnostics[&Url::from_file_path("/repo/a.ts").unwrap()];
+        assert_eq!(a.len(), 2);
+    }
+}
@@ -0,0 +1,46 @@
+//! Map command - Write the latest dead code report into AI context files
+
+use crate::cli::MapArgs;
+use crate::mapper::Mapper;
+use crate::scanner::{load_root_config, Scanner};
+use crate::types::ClrConfig;
+use anyhow::Result;
+use colored::Colorize;
+use std::path::PathBuf;
+
+pub async fn run(root: PathBuf, args: MapArgs, config_file: Option<PathBuf>) -> Result<i32> {
+    println!("{}", "🔍 Scanning for dead code...".bold());
+    let mut config = load_root_config(&root, config_file.as_deref(), ClrConfig::default())?;
+    config.confidence_threshold = args.confidence;
+    let scanner = Scanner::new(&root).with_config(&config);
+    let scan_output = scanner.scan().await?;
+
+    let mapper = Mapper::new(&root);
+    let report = mapper.update(&scan_output)?;
+
+    println!();
+    if report.updated.is_empty() {
+        println!(
+            "{}",
+            "No context files to update (run clrd init first, or reports are current)".yellow()
+        );
+    } else {
+        println!("{}", "Updated:".cyan().bold());
+        for file in &report.updated {
+            println!("  {} {}", "~".cyan(), file);
+        }
+    }
+
+    println!();
+    println!(
+        "Reported {} issues ({} high confidence)",
+        scan_output.summary.total_issues.to_string().cyan(),
+        scan_output
+            .summary
+            .high_confidence_issues
+            .to_string()
+            .cyan()
+    );
+
+    Ok(0)
+}
@@ -1,6 +1,14 @@
 //! CLI Command implementations
 
+pub mod api;
+pub mod diff;
+pub mod doctor;
+pub mod explain;
 pub mod fix;
 pub mod init;
+pub mod lsp;
+pub mod map;
 pub mod scan;
 pub mod schema;
+pub mod undo;
+pub mod validate_judgment;
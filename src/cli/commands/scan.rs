@@ -1,61 +1,407 @@
 //! Scan command - Detect dead code
 
-use crate::cli::{OutputFormat, ScanArgs};
-use crate::scanner::Scanner;
+use crate::cli::{GroupBy, OutputFormat, Profile, ScanArgs};
+use crate::scanner::{content_hash, load_root_config, CacheMode, ProgressSink, ScanPhase, Scanner};
 use crate::tui;
-use crate::types::ScanOutput;
-use anyhow::Result;
+use crate::types::{ClrConfig, DeadCodeItem, ScanOutput, ScanStats, ScanSummary, Severity};
+use anyhow::{bail, Context, Result};
 use colored::Colorize;
 use indicatif::{ProgressBar, ProgressStyle};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashSet};
 use std::fs;
-use std::path::PathBuf;
-use std::time::Duration;
-
-pub async fn run(root: PathBuf, args: ScanArgs, verbose: bool) -> Result<i32> {
-    // Show progress spinner
-    let spinner = ProgressBar::new_spinner();
-    spinner.set_style(
-        ProgressStyle::default_spinner()
-            .tick_strings(&["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"])
-            .template("{spinner:.cyan} {msg}")?,
+use std::io::{BufRead, BufWriter, IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::Command;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+/// Saved scans of clean commits, relative to the scan root
+const SCAN_CACHE_DIR: &str = ".clrd/scans";
+
+pub async fn run(
+    root: PathBuf,
+    args: ScanArgs,
+    config_file: Option<PathBuf>,
+    verbose: bool,
+) -> Result<i32> {
+    let staged = args.staged.then(|| staged_files(&root)).transpose()?;
+    // Positional paths replace the working directory as the scan roots
+    let mut roots: Vec<PathBuf> = args.paths.iter().map(|path| root.join(path)).collect();
+    let root = if roots.is_empty() {
+        root
+    } else {
+        roots.remove(0)
+    };
+
+    let config = effective_config(&args, &root, config_file.as_deref())?;
+    if config.no_color {
+        colored::control::set_override(false);
+    }
+
+    // Show progress, only when a person is watching and not for JSON output
+    let machine_output = matches!(
+        args.format,
+        OutputFormat::Json | OutputFormat::Jsonl | OutputFormat::Yaml
     );
-    spinner.set_message("Scanning for dead code...");
-    spinner.enable_steady_tick(Duration::from_millis(80));
+    let progress = if std::io::stderr().is_terminal() && !machine_output {
+        ProgressBar::new_spinner()
+    } else {
+        ProgressBar::hidden()
+    };
+    let progress = BarProgress::new(progress)?;
+    progress.bar.set_message("Scanning for dead code...");
+    progress.bar.enable_steady_tick(Duration::from_millis(80));
+    let bar = progress.bar.clone();
+
+    // Everything that shapes the output besides the sources themselves
+    let fingerprint = content_hash(&format!(
+        "{:?}",
+        (
+            &config,
+            args.ignore_list(),
+            &args.paths,
+            &args.entry,
+            args.strict,
+            args.max_results,
+            args.max_per_file,
+            args.no_snippet,
+            args.no_context,
+            args.summary_only,
+        )
+    ));
+    // A listed subset is not the whole commit, file ages change without a
+    // commit, and --stats profiles a real scan
+    let commit = if args.no_scan_cache || args.stdin || args.min_age.is_some() || args.stats {
+        None
+    } else {
+        clean_head(std::iter::once(&root).chain(&roots))
+    };
 
     // Build scanner
-    let mut scanner = Scanner::new(&root).with_confidence_threshold(args.confidence);
+    let mut scanner = Scanner::new(&root).with_config(&config);
 
-    if let Some(extensions) = args.extensions {
-        scanner = scanner.with_extensions(extensions);
+    if let Some(ignore) = args.ignore_list() {
+        scanner = scanner.with_ignore_patterns(ignore);
     }
 
-    if let Some(ignore) = args.ignore {
-        scanner = scanner.with_ignore_patterns(ignore);
+    if args.stdin {
+        let files = read_file_list(std::io::stdin().lock(), &root)?;
+        scanner = scanner.with_files(files);
     }
 
-    scanner = scanner.include_tests(args.include_tests);
+    scanner = scanner
+        .with_additional_roots(roots)
+        .with_entry_files(args.entry)
+        .with_min_file_age(args.min_age)
+        .with_framework_naming(!args.strict)
+        .with_read_threads(args.read_threads)
+        .with_mmap_threshold(args.mmap_threshold)
+        .with_snippets(!args.no_snippet && !args.summary_only)
+        .with_context(!args.no_context && !args.summary_only)
+        .with_max_results(args.max_results)
+        .with_max_per_file(args.max_per_file)
+        .with_cache_mode(if args.incremental {
+            CacheMode::Incremental
+        } else if args.full {
+            CacheMode::Rebuild
+        } else {
+            CacheMode::Disabled
+        })
+        .with_stats(args.stats)
+        .with_progress(Arc::new(progress));
 
-    // Run scan
-    let result = scanner.scan().await?;
-    spinner.finish_and_clear();
+    // Run scan, unless this commit was already scanned with the same settings
+    let started = Instant::now();
+    let cached = commit
+        .as_deref()
+        .and_then(|sha| load_cached_scan(&root, sha, fingerprint));
+    let mut result = match cached {
+        Some(mut result) => {
+            result.scan_duration_ms = started.elapsed().as_millis() as u64;
+            result
+        }
+        None => {
+            let result = scanner.scan().await?;
+            if let Some(sha) = &commit {
+                if let Err(e) = save_cached_scan(&root, sha, fingerprint, &result) {
+                    tracing::warn!("Failed to save scan cache: {}", e);
+                }
+            }
+            result
+        }
+    };
+    bar.finish_and_clear();
+
+    // The whole project is scanned so usage is complete; only the report is
+    // narrowed. `total_findings`, the summary and the health score still
+    // describe the whole project, so they agree with each other
+    if let Some(staged) = &staged {
+        result
+            .dead_code
+            .retain(|item| staged.contains(&item.file_path));
+    }
+
+    // Decided before --summary-only drops the findings
+    let failing = result
+        .dead_code
+        .iter()
+        .any(|item| item.confidence >= config.fail_on);
+
+    if args.summary_only {
+        strip_findings(&mut result);
+    }
 
     // Output based on format
     match args.format {
-        OutputFormat::Pretty => print_pretty(&result, verbose),
-        OutputFormat::Json => print_json(&result, args.output)?,
+        OutputFormat::Pretty if staged.is_some() => print_staged(&result),
+        OutputFormat::Pretty => print_pretty(
+            &result,
+            verbose,
+            verbose || args.report_unresolved,
+            args.group_by,
+            args.summary_only,
+        ),
+        OutputFormat::Json => print_json(&result, args.output, args.json_compact)?,
+        OutputFormat::Jsonl => print_jsonl(&result, args.output)?,
+        OutputFormat::Yaml => print_yaml(&result, args.output)?,
         OutputFormat::Compact => print_compact(&result),
+        OutputFormat::Llm => print_llm(&result, args.output)?,
         OutputFormat::Tui => tui::run_tui(&result)?,
     }
 
+    if let Some(dir) = &args.output_dir {
+        write_reports(&result, dir)?;
+        eprintln!("Reports written to: {}", dir.display());
+    }
+
+    if let Some(path) = &args.summary_out {
+        write_summary(&result, failing, path)?;
+    }
+
+    if let Some(stats) = &result.stats {
+        print_stats(stats);
+    }
+
+    if args.strict && !result.parse_errors.is_empty() {
+        eprintln!(
+            "{} {} file(s) failed to parse (--strict)",
+            "✗".red(),
+            result.summary.parse_errors
+        );
+        return Ok(1);
+    }
+
     // Return exit code based on findings
-    if result.summary.high_confidence_issues > 0 {
+    if failing {
         Ok(1)
     } else {
         Ok(0)
     }
 }
 
-fn print_pretty(result: &ScanOutput, verbose: bool) {
+/// Renders scan progress: a spinner while totals are unknown, then a
+/// `files done / total` bar
+struct BarProgress {
+    bar: ProgressBar,
+    spinner_style: ProgressStyle,
+    bar_style: ProgressStyle,
+}
+
+impl BarProgress {
+    fn new(bar: ProgressBar) -> Result<Self> {
+        let ticks = ["⠋", "⠙", "⠹", "⠸", "⠼", "⠴", "⠦", "⠧", "⠇", "⠏"];
+        let spinner_style = ProgressStyle::default_spinner()
+            .tick_strings(&ticks)
+            .template("{spinner:.cyan} {msg}")?;
+        let bar_style = ProgressStyle::default_bar()
+            .tick_strings(&ticks)
+            .template("{spinner:.cyan} {msg} [{bar:30.cyan/blue}] {pos}/{len} files")?
+            .progress_chars("=> ");
+        bar.set_style(spinner_style.clone());
+        Ok(Self {
+            bar,
+            spinner_style,
+            bar_style,
+        })
+    }
+}
+
+impl ProgressSink for BarProgress {
+    fn start_phase(&self, phase: ScanPhase, total: Option<u64>) {
+        match total {
+            Some(total) => {
+                self.bar.set_style(self.bar_style.clone());
+                self.bar.set_length(total);
+                self.bar.set_position(0);
+            }
+            None => self.bar.set_style(self.spinner_style.clone()),
+        }
+        self.bar.set_message(phase.label());
+    }
+
+    fn file_done(&self) {
+        self.bar.inc(1);
+    }
+}
+
+/// Start from the selected profile (or the defaults), apply the root's
+/// `clrd.toml` (or the `--config` file in its place), then explicit flags
+fn effective_config(args: &ScanArgs, root: &Path, config_file: Option<&Path>) -> Result<ClrConfig> {
+    let profile = args
+        .profile
+        .map_or_else(ClrConfig::default, Profile::config);
+    let mut config = load_root_config(root, config_file, profile)?;
+    if let Some(extensions) = args.extension_list() {
+        config.extensions = extensions;
+    }
+    config.always_used_exports.extend(args.keep.iter().cloned());
+    if let Some(confidence) = args.confidence {
+        config.confidence_threshold = confidence;
+    }
+    if let Some(fail_on) = args.fail_on {
+        config.fail_on = fail_on;
+    } else if args.staged {
+        // A pre-commit hook blocks on any new dead code
+        config.fail_on = 0.0;
+    }
+    if let Some(context) = args.context {
+        config.snippet_context = context;
+    }
+    if let Some(max_lines) = args.snippet_max_lines {
+        config.snippet_max_lines = max_lines;
+    }
+    if let Some(bytes) = args.max_file_size {
+        config.max_file_bytes = bytes;
+    }
+    config.detect_cycles |= args.detect_cycles;
+    config.consider_tests_usage |= args.consider_tests_usage;
+    if args.include_tests {
+        // Test files are analyzed outright, so they are no longer usage-only
+        config.include_tests = true;
+        config.consider_tests_usage = false;
+    }
+    Ok(config)
+}
+
+/// Drop individual findings, keeping the summary and totals
+///
+/// `dead_code` stays in the output as an empty array so the JSON shape is
+/// the same with and without `--summary-only`.
+fn strip_findings(result: &mut ScanOutput) {
+    result.dead_code = Vec::new();
+    result.truncated = false;
+}
+
+/// Files staged for commit in the repository holding `root`, below `root`
+fn staged_files(root: &Path) -> Result<HashSet<PathBuf>> {
+    let output = Command::new("git")
+        .args([
+            "diff",
+            "--cached",
+            "--name-only",
+            "--relative",
+            "--diff-filter=ACMR",
+        ])
+        .current_dir(root)
+        .output()
+        .context("--staged needs git, which could not be run")?;
+    if !output.status.success() {
+        bail!(
+            "--staged needs a git repository, and {} is not in one",
+            root.display()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .filter(|line| !line.is_empty())
+        .map(|line| root.join(line))
+        .collect())
+}
+
+/// A scan saved for one commit, reused while the settings match
+#[derive(Serialize, Deserialize)]
+struct CachedScan {
+    /// clrd version that wrote the scan; other versions scan again
+    version: String,
+    fingerprint: u64,
+    output: ScanOutput,
+}
+
+/// HEAD of the repository holding every root, if nothing below any root
+/// differs from it; `.clrd` itself is ignored
+fn clean_head(roots: impl IntoIterator<Item = impl AsRef<Path>>) -> Option<String> {
+    let mut head = None;
+    for root in roots {
+        let git = |args: &[&str]| {
+            Command::new("git")
+                .args(args)
+                .current_dir(&root)
+                .output()
+                .ok()
+                .filter(|output| output.status.success())
+                .map(|output| String::from_utf8_lossy(&output.stdout).trim().to_string())
+        };
+        let status = git(&["status", "--porcelain", "--", ".", ":(exclude).clrd"])?;
+        if !status.is_empty() {
+            return None;
+        }
+        // Roots in different repositories have no single commit
+        let root_head = git(&["rev-parse", "HEAD"])?;
+        if head.get_or_insert_with(|| root_head.clone()) != &root_head {
+            return None;
+        }
+    }
+    head
+}
+
+fn scan_cache_path(root: &Path, sha: &str) -> PathBuf {
+    root.join(SCAN_CACHE_DIR).join(format!("{}.json", sha))
+}
+
+/// The scan saved for `sha`, unless it is missing, unreadable or was made
+/// by another clrd version or with other settings
+fn load_cached_scan(root: &Path, sha: &str, fingerprint: u64) -> Option<ScanOutput> {
+    let text = fs::read_to_string(scan_cache_path(root, sha)).ok()?;
+    let cached: CachedScan = serde_json::from_str(&text).ok()?;
+    (cached.version == env!("CARGO_PKG_VERSION") && cached.fingerprint == fingerprint)
+        .then_some(cached.output)
+}
+
+fn save_cached_scan(root: &Path, sha: &str, fingerprint: u64, output: &ScanOutput) -> Result<()> {
+    let path = scan_cache_path(root, sha);
+    if let Some(dir) = path.parent() {
+        fs::create_dir_all(dir)?;
+    }
+    let cached = CachedScan {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        fingerprint,
+        output: output.clone(),
+    };
+    fs::write(path, serde_json::to_string(&cached)?)?;
+    Ok(())
+}
+
+/// Read newline-separated paths, resolving relative ones against `root`
+fn read_file_list(reader: impl BufRead, root: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    for line in reader.lines() {
+        let line = line?;
+        let line = line.trim();
+        if !line.is_empty() {
+            files.push(root.join(line));
+        }
+    }
+    Ok(files)
+}
+
+fn print_pretty(
+    result: &ScanOutput,
+    verbose: bool,
+    report_unresolved: bool,
+    group_by: Option<GroupBy>,
+    summary_only: bool,
+) {
     println!();
     println!("{}", "━".repeat(60).dimmed());
     println!("{}", " 🧹 clrd - Dead Code Report".bold());
@@ -80,9 +426,93 @@ fn print_pretty(result: &ScanOutput, verbose: bool) {
         "  High confidence:   {}",
         colorize_count(result.summary.high_confidence_issues)
     );
+    println!(
+        "  Health score:      {} (grade {})",
+        result.summary.health_score.to_string().cyan(),
+        result.summary.health_grade.bold()
+    );
+    println!(
+        "  Dead code ratio:   {} of lines ({} files)",
+        format!("{:.1}%", result.summary.dead_code_ratio * 100.0).cyan(),
+        result.summary.files_with_issues
+    );
+    if result.summary.estimated_removal.lines > 0 {
+        println!(
+            "  Fixable (≥80%):    ~{} lines in {} files",
+            result.summary.estimated_removal.lines.to_string().cyan(),
+            result.summary.estimated_removal.files
+        );
+    }
+    if result.summary.parse_errors > 0 {
+        println!(
+            "  Parse errors:      {}",
+            result.summary.parse_errors.to_string().red()
+        );
+    }
+    if result.summary.skipped_files > 0 {
+        println!(
+            "  Skipped (size):    {}",
+            result.summary.skipped_files.to_string().yellow()
+        );
+    }
+    if result.summary.total_issues > 0 {
+        println!("  Confidence:");
+        for line in histogram_lines(&result.summary.confidence_histogram) {
+            println!("    {}", line);
+        }
+    }
     println!();
 
-    if result.dead_code.is_empty() {
+    if verbose && !result.skipped_files.is_empty() {
+        println!("{}", "Skipped Files".bold().underline());
+        for file in &result.skipped_files {
+            println!(
+                "  {} {} {}",
+                "⚠".yellow(),
+                file.path,
+                format!("({} bytes)", file.bytes).dimmed()
+            );
+        }
+        println!();
+    }
+
+    if verbose && !result.ignore_warnings.is_empty() {
+        println!("{}", "Ignore Patterns".bold().underline());
+        for warning in &result.ignore_warnings {
+            println!("  {} {}", "⚠".yellow(), warning);
+        }
+        println!();
+    }
+
+    if report_unresolved && !result.unresolved_imports.is_empty() {
+        println!("{}", "Unresolved Imports".bold().underline());
+        for import in &result.unresolved_imports {
+            println!(
+                "  {} {}:{} {}",
+                "?".yellow(),
+                import.path,
+                import.line,
+                import.source.dimmed()
+            );
+        }
+        println!();
+    }
+
+    if verbose && !result.parse_errors.is_empty() {
+        println!("{}", "Parse Errors".bold().underline());
+        for error in &result.parse_errors {
+            println!(
+                "  {} {}:{} {}",
+                "✗".red(),
+                error.path,
+                error.line,
+                error.message.dimmed()
+            );
+        }
+        println!();
+    }
+
+    if result.total_findings == 0 {
         println!("{}", "✅ No dead code detected!".green().bold());
         return;
     }
@@ -120,6 +550,10 @@ fn print_pretty(result: &ScanOutput, verbose: bool) {
         );
     }
 
+    if summary_only {
+        return;
+    }
+
     println!();
     println!("{}", "Details".bold().underline());
     println!();
@@ -131,32 +565,29 @@ fn print_pretty(result: &ScanOutput, verbose: bool) {
         result.dead_code.len().min(20)
     };
 
-    for (i, item) in result.dead_code.iter().take(items_to_show).enumerate() {
-        let confidence_bar = confidence_to_bar(item.confidence);
-        let kind_icon = kind_to_icon(&item.kind);
-
-        println!(
-            "{:>3}. {} {} {}",
-            i + 1,
-            kind_icon,
-            item.name.bold(),
-            confidence_bar
-        );
-        println!("     {} {}", "→".dimmed(), item.relative_path.dimmed());
-        println!(
-            "     {} Line {}",
-            "↳".dimmed(),
-            item.span.start.to_string().cyan()
-        );
-
-        if verbose {
-            println!("     {}", item.reason.dimmed());
-            println!("     {}", "─".repeat(40).dimmed());
-            for line in item.code_snippet.lines().take(5) {
-                println!("     {}", line.dimmed());
+    let shown: Vec<&DeadCodeItem> = result.dead_code.iter().take(items_to_show).collect();
+    match group_by {
+        None => {
+            for (i, item) in shown.iter().enumerate() {
+                print_item(i, item, verbose, true);
+            }
+        }
+        Some(group_by) => {
+            let mut index = 0;
+            for (key, items) in group_items(&shown, group_by) {
+                println!(
+                    "{} {} ({})",
+                    "▸".cyan(),
+                    key.bold(),
+                    items.len().to_string().cyan()
+                );
+                println!();
+                for item in items {
+                    print_item(index, item, verbose, group_by != GroupBy::File);
+                    index += 1;
+                }
             }
         }
-        println!();
     }
 
     if result.dead_code.len() > items_to_show {
@@ -168,6 +599,24 @@ fn print_pretty(result: &ScanOutput, verbose: bool) {
         );
     }
 
+    if result.truncated {
+        let flag = if result.summary.suppressed_per_file.is_empty() {
+            "--max-results"
+        } else {
+            "--max-per-file"
+        };
+        println!(
+            "  {} {} of {} findings shown ({})",
+            "⋯".dimmed(),
+            result.dead_code.len(),
+            result.total_findings,
+            flag.cyan()
+        );
+    }
+    for (path, count) in &result.summary.suppressed_per_file {
+        println!("      {} more in {}", count, path.dimmed());
+    }
+
     println!();
     println!("{}", "━".repeat(60).dimmed());
     println!(
@@ -177,8 +626,68 @@ fn print_pretty(result: &ScanOutput, verbose: bool) {
     println!("Run {} to update AI context files", "clrd map".cyan());
 }
 
-fn print_json(result: &ScanOutput, output: Option<PathBuf>) -> Result<()> {
-    let json = serde_json::to_string_pretty(result)?;
+fn print_item(index: usize, item: &DeadCodeItem, verbose: bool, show_path: bool) {
+    let confidence_bar = confidence_to_bar(item.confidence);
+    let kind_icon = kind_to_icon(&item.kind);
+
+    let todo = if has_todo(item) {
+        format!(" {}", "TODO".yellow())
+    } else {
+        String::new()
+    };
+
+    println!(
+        "{:>3}. {} {} {} {}{}",
+        index + 1,
+        kind_icon,
+        item.name.bold(),
+        confidence_bar,
+        severity_label(item.severity),
+        todo
+    );
+    if show_path {
+        println!("     {} {}", "→".dimmed(), item.location().cyan());
+    } else {
+        println!(
+            "     {} Line {}",
+            "↳".dimmed(),
+            item.span.start.to_string().cyan()
+        );
+    }
+
+    if verbose {
+        println!("     {}", item.reason.dimmed());
+        println!("     {}", "─".repeat(40).dimmed());
+        for line in item.code_snippet.lines().take(5) {
+            println!("     {}", line.dimmed());
+        }
+    }
+    println!();
+}
+
+/// Bucket items by file path or kind, ordered by key
+fn group_items<'a>(
+    items: &[&'a DeadCodeItem],
+    group_by: GroupBy,
+) -> Vec<(String, Vec<&'a DeadCodeItem>)> {
+    let mut groups: BTreeMap<String, Vec<&'a DeadCodeItem>> = BTreeMap::new();
+    for item in items {
+        let key = match group_by {
+            GroupBy::File => item.relative_path.clone(),
+            GroupBy::Kind => item.kind.to_string(),
+        };
+        groups.entry(key).or_default().push(item);
+    }
+
+    for items in groups.values_mut() {
+        items.sort_by_key(|item| item.span.start);
+    }
+
+    groups.into_iter().collect()
+}
+
+fn print_json(result: &ScanOutput, output: Option<PathBuf>, compact: bool) -> Result<()> {
+    let json = to_json(result, compact)?;
 
     if let Some(path) = output {
         fs::write(&path, &json)?;
@@ -190,6 +699,229 @@ fn print_json(result: &ScanOutput, output: Option<PathBuf>) -> Result<()> {
     Ok(())
 }
 
+/// Serialize a scan as indented JSON, or on a single line when `compact`
+fn to_json(result: &ScanOutput, compact: bool) -> Result<String> {
+    Ok(if compact {
+        serde_json::to_string(result)?
+    } else {
+        serde_json::to_string_pretty(result)?
+    })
+}
+
+fn print_yaml(result: &ScanOutput, output: Option<PathBuf>) -> Result<()> {
+    let yaml = serde_yaml::to_string(result)?;
+
+    if let Some(path) = output {
+        fs::write(&path, &yaml)?;
+        eprintln!("Output written to: {}", path.display());
+    } else {
+        print!("{}", yaml);
+    }
+
+    Ok(())
+}
+
+fn print_jsonl(result: &ScanOutput, output: Option<PathBuf>) -> Result<()> {
+    if let Some(path) = output {
+        write_jsonl(result, BufWriter::new(fs::File::create(&path)?))?;
+        eprintln!("Output written to: {}", path.display());
+    } else {
+        write_jsonl(result, BufWriter::new(std::io::stdout().lock()))?;
+    }
+
+    Ok(())
+}
+
+/// Final line of jsonl output
+#[derive(serde::Serialize)]
+struct JsonlSummary<'a> {
+    r#type: &'static str,
+    #[serde(flatten)]
+    summary: &'a ScanSummary,
+    total_files_scanned: u32,
+    total_findings: u32,
+    truncated: bool,
+}
+
+/// Write each finding as compact JSON on its own line, then the summary
+fn write_jsonl(result: &ScanOutput, mut writer: impl Write) -> Result<()> {
+    for item in &result.dead_code {
+        serde_json::to_writer(&mut writer, item)?;
+        writeln!(writer)?;
+    }
+
+    let summary = JsonlSummary {
+        r#type: "summary",
+        summary: &result.summary,
+        total_files_scanned: result.total_files_scanned,
+        total_findings: result.total_findings,
+        truncated: result.truncated,
+    };
+    serde_json::to_writer(&mut writer, &summary)?;
+    writeln!(writer)?;
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// File names written by `--output-dir`, one per file-based format
+const REPORT_FILES: [&str; 3] = ["clrd.json", "clrd.jsonl", "clrd.txt"];
+
+/// Write the json, jsonl and llm reports of one scan into `dir`
+fn write_reports(result: &ScanOutput, dir: &Path) -> Result<()> {
+    fs::create_dir_all(dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let [json, jsonl, llm] = REPORT_FILES.map(|name| dir.join(name));
+    fs::write(&json, serde_json::to_string_pretty(result)?)?;
+    write_jsonl(result, BufWriter::new(fs::File::create(&jsonl)?))?;
+    write_llm(result, BufWriter::new(fs::File::create(&llm)?))?;
+    Ok(())
+}
+
+/// What `--summary-out` writes: the summary with the scan's totals
+#[derive(Serialize)]
+struct SummaryReport<'a> {
+    version: &'a str,
+    total_files_scanned: u32,
+    total_lines: u64,
+    total_findings: u32,
+    /// Whether a finding reached `--fail-on`, making the scan exit 1
+    failing: bool,
+    summary: &'a ScanSummary,
+}
+
+fn write_summary(result: &ScanOutput, failing: bool, path: &Path) -> Result<()> {
+    let report = SummaryReport {
+        version: &result.version,
+        total_files_scanned: result.total_files_scanned,
+        total_lines: result.total_lines,
+        total_findings: result.total_findings,
+        failing,
+        summary: &result.summary,
+    };
+    fs::write(path, serde_json::to_string_pretty(&report)?)
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+fn print_llm(result: &ScanOutput, output: Option<PathBuf>) -> Result<()> {
+    if let Some(path) = output {
+        write_llm(result, BufWriter::new(fs::File::create(&path)?))?;
+        eprintln!("Output written to: {}", path.display());
+    } else {
+        write_llm(result, BufWriter::new(std::io::stdout().lock()))?;
+    }
+
+    Ok(())
+}
+
+/// Write a token-lean plain-text report: a short header, then one
+/// `kind | name | path:line:col | severity | conf% | reason` line per finding,
+/// most severe first, then most confident
+fn write_llm(result: &ScanOutput, mut writer: impl Write) -> Result<()> {
+    writeln!(writer, "# clrd dead code report")?;
+    writeln!(
+        writer,
+        "files: {} | lines: {} | findings: {} | high confidence: {} | health: {} ({})",
+        result.total_files_scanned,
+        result.total_lines,
+        result.total_findings,
+        result.summary.high_confidence_issues,
+        result.summary.health_score,
+        result.summary.health_grade
+    )?;
+    writeln!(
+        writer,
+        "confidence: >=80% safe to remove | 50-79% review | <50% needs judgment"
+    )?;
+    if result.truncated {
+        writeln!(
+            writer,
+            "showing {} of {} findings",
+            result.dead_code.len(),
+            result.total_findings
+        )?;
+    }
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "kind | name | path:line:col | severity | conf% | reason"
+    )?;
+
+    let mut items: Vec<&DeadCodeItem> = result.dead_code.iter().collect();
+    items.sort_by(|a, b| {
+        b.severity
+            .cmp(&a.severity)
+            .then(b.confidence.total_cmp(&a.confidence))
+    });
+    for item in items {
+        writeln!(
+            writer,
+            "{} | {} | {} | {} | {:.0}% | {}{}",
+            item.kind,
+            item.name,
+            item.location(),
+            item.severity,
+            item.confidence * 100.0,
+            item.reason.replace('\n', " "),
+            if has_todo(item) { " (TODO nearby)" } else { "" }
+        )?;
+    }
+    writer.flush()?;
+
+    Ok(())
+}
+
+/// Widest bar of the confidence histogram, in characters
+const HISTOGRAM_WIDTH: u32 = 20;
+
+/// One `0.8-1.0 ████ 12` line per confidence bucket, bars scaled to the fullest
+fn histogram_lines(histogram: &[u32; 5]) -> Vec<String> {
+    let max = histogram.iter().copied().max().unwrap_or(0).max(1);
+    histogram
+        .iter()
+        .enumerate()
+        .map(|(i, &count)| {
+            // A non-empty bucket always shows at least one block
+            let width = (count * HISTOGRAM_WIDTH).div_ceil(max);
+            format!(
+                "{:.1}-{:.1} {:<width$} {}",
+                i as f64 * 0.2,
+                (i + 1) as f64 * 0.2,
+                "█".repeat(width as usize),
+                count,
+                width = HISTOGRAM_WIDTH as usize
+            )
+        })
+        .collect()
+}
+
+/// Findings in staged files, one per line, with what to do about them
+fn print_staged(result: &ScanOutput) {
+    if result.dead_code.is_empty() {
+        println!("{} No dead code in staged files", "✓".green());
+        return;
+    }
+
+    println!(
+        "{} {} dead code finding(s) in staged files:",
+        "✗".red(),
+        result.dead_code.len()
+    );
+    for item in &result.dead_code {
+        println!(
+            "  {} {} {} {}",
+            item.location(),
+            item.kind.to_string().dimmed(),
+            item.name.bold(),
+            format!("({:.0}%)", item.confidence * 100.0).dimmed()
+        );
+    }
+    println!();
+    println!(
+        "Remove them and stage again, or keep an export with {}.",
+        "--keep <NAME>".cyan()
+    );
+}
+
 fn print_compact(result: &ScanOutput) {
     println!(
         "clrd: {} files scanned, {} issues ({} high confidence)",
@@ -200,15 +932,56 @@ fn print_compact(result: &ScanOutput) {
 
     for item in &result.dead_code {
         println!(
-            "  {}:{} {} ({:.0}%)",
-            item.relative_path,
-            item.span.start,
+            "  {} {} {} ({:.0}%)",
+            item.location(),
+            item.kind,
             item.name,
             item.confidence * 100.0
         );
     }
 }
 
+/// Print the profiling table to stderr so it never mixes with JSON on stdout
+fn print_stats(stats: &ScanStats) {
+    let total = stats.walk + stats.read + stats.parse + stats.graph + stats.detect;
+
+    eprintln!();
+    eprintln!("{}", "Scan Statistics".bold().underline());
+    for (phase, duration) in [
+        ("File walking", stats.walk),
+        ("Reading", stats.read),
+        ("Parsing", stats.parse),
+        ("Graph construction", stats.graph),
+        ("Dead code detection", stats.detect),
+        ("Total", total),
+    ] {
+        eprintln!(
+            "  {:<22} {:>10.2}ms",
+            phase,
+            duration.as_secs_f64() * 1000.0
+        );
+    }
+    eprintln!();
+    eprintln!("  Files parsed:          {}", stats.files_parsed);
+    if stats.files_cached > 0 {
+        eprintln!("  Files from cache:      {}", stats.files_cached);
+    }
+    eprintln!("  Files failed:          {}", stats.files_failed);
+    eprintln!("  Parse errors:          {}", stats.parse_errors);
+
+    if !stats.slowest_files.is_empty() {
+        eprintln!();
+        eprintln!("{}", "Slowest Files".bold().underline());
+        for timing in &stats.slowest_files {
+            eprintln!(
+                "  {:>10.2}ms  {}",
+                timing.duration.as_secs_f64() * 1000.0,
+                timing.path.dimmed()
+            );
+        }
+    }
+}
+
 fn colorize_count(count: u32) -> String {
     if count == 0 {
         count.to_string().green().to_string()
@@ -234,6 +1007,22 @@ fn confidence_to_bar(confidence: f64) -> String {
     }
 }
 
+/// Whether a `TODO`-style comment near the finding suggests work in progress
+fn has_todo(item: &DeadCodeItem) -> bool {
+    item.context
+        .as_ref()
+        .is_some_and(|context| context.has_todo)
+}
+
+fn severity_label(severity: Severity) -> String {
+    let label = format!("[{}]", severity);
+    match severity {
+        Severity::High => label.red().to_string(),
+        Severity::Medium => label.yellow().to_string(),
+        Severity::Low => label.dimmed().to_string(),
+    }
+}
+
 fn kind_to_icon(kind: &crate::types::DeadCodeKind) -> &'static str {
     use crate::types::DeadCodeKind::*;
     match kind {
@@ -246,5 +1035,616 @@ fn kind_to_icon(kind: &crate::types::DeadCodeKind) -> &'static str {
         UnusedClass => "🏛️",
         UnusedEnum => "🔢",
         DeadBranch => "🌿",
+        TypeOnlyImport => "🏷️",
+        CircularImport => "🔁",
+        RedundantExport => "🏠",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DeadCodeKind;
+    use std::path::PathBuf;
+
+    fn item(path: &str, name: &str, kind: DeadCodeKind, line: u32) -> DeadCodeItem {
+        DeadCodeItem::fixture(path, name, kind).at_line(line)
+    }
+
+    #[test]
+    fn test_confidence_histogram() {
+        let mut summary = ScanSummary::new();
+        for confidence in [0.0, 0.1, 0.2, 0.45, 0.5, 0.6, 0.79, 0.8, 0.9, 1.0] {
+            let mut finding = item("src/a.ts", "x", DeadCodeKind::UnusedExport, 1);
+            finding.confidence = confidence;
+            summary.add(&finding);
+        }
+        assert_eq!(summary.confidence_histogram, [2, 1, 2, 2, 3]);
+
+        let lines = histogram_lines(&summary.confidence_histogram);
+        assert_eq!(lines[4], format!("0.8-1.0 {} 3", "█".repeat(20)));
+        assert_eq!(lines[1], format!("0.2-0.4 {:<20} 1", "█".repeat(7)));
+    }
+
+    #[test]
+    fn test_location() {
+        let mut finding = item("src/utils/format.ts", "pad", DeadCodeKind::UnusedExport, 7);
+        finding.span.col_start = 4;
+
+        // Line and column as editors count them, with no padding
+        assert_eq!(finding.location(), "src/utils/format.ts:7:5");
+    }
+
+    #[test]
+    fn test_group_items_by_file() {
+        let items = [
+            item("src/b.ts", "b2", DeadCodeKind::UnusedExport, 9),
+            item("src/a.ts", "a1", DeadCodeKind::UnusedImport, 1),
+            item("src/b.ts", "b1", DeadCodeKind::UnusedImport, 2),
+        ];
+        let refs: Vec<&DeadCodeItem> = items.iter().collect();
+
+        let groups = group_items(&refs, GroupBy::File);
+
+        assert_eq!(groups.len(), 2);
+        assert_eq!(groups[0].0, "src/a.ts");
+        assert_eq!(groups[0].1.len(), 1);
+        assert_eq!(groups[1].0, "src/b.ts");
+        let names: Vec<&str> = groups[1].1.iter().map(|i| i.name.as_str()).collect();
+        assert_eq!(names, vec!["b1", "b2"]);
+    }
+
+    #[test]
+    fn test_group_items_by_kind() {
+        let items = [
+            item("src/b.ts", "b", DeadCodeKind::UnusedImport, 1),
+            item("src/a.ts", "a", DeadCodeKind::UnusedExport, 1),
+        ];
+        let refs: Vec<&DeadCodeItem> = items.iter().collect();
+
+        let groups = group_items(&refs, GroupBy::Kind);
+
+        let keys: Vec<&str> = groups.iter().map(|(k, _)| k.as_str()).collect();
+        assert_eq!(keys, vec!["unused_export", "unused_import"]);
+    }
+
+    #[test]
+    fn test_read_file_list() {
+        let input = std::io::Cursor::new("src/a.ts\n\n  /abs/b.tsx  \n");
+
+        let files = read_file_list(input, Path::new("/repo")).unwrap();
+
+        assert_eq!(
+            files,
+            vec![PathBuf::from("/repo/src/a.ts"), PathBuf::from("/abs/b.tsx")]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_write_jsonl() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("utils.ts"),
+            "export const a = 1;\nexport const b = 2;\n",
+        )
+        .unwrap();
+        let result = Scanner::new(dir.path())
+            .with_confidence_threshold(0.0)
+            .scan()
+            .await
+            .unwrap();
+        assert!(!result.dead_code.is_empty());
+
+        let mut buffer = Vec::new();
+        write_jsonl(&result, &mut buffer).unwrap();
+
+        let lines: Vec<serde_json::Value> = String::from_utf8(buffer)
+            .unwrap()
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        let (summary, items) = lines.split_last().unwrap();
+
+        assert_eq!(items.len(), result.dead_code.len());
+        for (line, item) in items.iter().zip(&result.dead_code) {
+            assert_eq!(line["id"], item.id.as_str());
+        }
+        assert_eq!(summary["type"], "summary");
+        assert_eq!(summary["total_issues"], result.summary.total_issues);
+    }
+
+    #[tokio::test]
+    async fn test_write_llm() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut result = Scanner::new(dir.path()).scan().await.unwrap();
+        let mut low = item("src/a.ts", "maybe", DeadCodeKind::UnusedExport, 4);
+        low.confidence = 0.4;
+        low.reason = "Export 'maybe' has 0 references".to_string();
+        let mut high = item("src/b.ts", "helper", DeadCodeKind::UnusedImport, 1);
+        high.reason = "Import 'helper' is never used".to_string();
+        result.dead_code = vec![low, high];
+        result.total_findings = 2;
+
+        let mut buffer = Vec::new();
+        write_llm(&result, &mut buffer).unwrap();
+        let output = String::from_utf8(buffer).unwrap();
+        let lines: Vec<&str> = output.lines().collect();
+
+        assert_eq!(lines[0], "# clrd dead code report");
+        assert!(lines[1].starts_with("files: 0 | lines: 0 | findings: 2 |"));
+        let table = lines
+            .iter()
+            .position(|line| line.starts_with("kind |"))
+            .unwrap();
+        assert_eq!(
+            &lines[table + 1..],
+            [
+                // Severity ranks before confidence
+                "unused_export | maybe | src/a.ts:4:1 | high | 40% | Export 'maybe' has 0 references",
+                "unused_import | helper | src/b.ts:1:1 | low | 90% | Import 'helper' is never used",
+            ]
+        );
+        assert!(!output.contains('\x1b'));
+    }
+
+    #[test]
+    fn test_no_ansi_codes_without_color() {
+        colored::control::set_override(false);
+        let outputs = [
+            colorize_count(7),
+            confidence_to_bar(0.9),
+            "Summary".bold().underline().to_string(),
+        ];
+        // The override is process-wide; restore it before anything can fail
+        colored::control::unset_override();
+
+        for output in outputs {
+            assert!(!output.contains('\x1b'), "{:?}", output);
+        }
+    }
+
+    #[tokio::test]
+    async fn test_summary_only_keeps_counts() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("a.ts"), "export const unused = 1;\n").unwrap();
+        fs::write(dir.path().join("b.ts"), "export const other = 2;\n").unwrap();
+
+        let mut result = Scanner::new(dir.path())
+            .with_confidence_threshold(0.0)
+            .scan()
+            .await
+            .unwrap();
+        let summary = result.summary.clone();
+        assert!(summary.total_issues > 0);
+
+        strip_findings(&mut result);
+        let json = serde_json::to_value(&result).unwrap();
+        assert_eq!(json["dead_code"], serde_json::json!([]));
+        assert_eq!(json["total_findings"], result.total_findings);
+        assert_eq!(json["total_files_scanned"], 2);
+        assert_eq!(json["summary"]["total_issues"], summary.total_issues);
+        assert_eq!(json["summary"]["unused_exports"], summary.unused_exports);
+    }
+
+    #[tokio::test]
+    async fn test_compact_json_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("utils.ts"),
+            "export const a = 1;\nexport const b = 2;\n",
+        )
+        .unwrap();
+        let result = Scanner::new(dir.path())
+            .with_confidence_threshold(0.0)
+            .scan()
+            .await
+            .unwrap();
+        assert!(result.dead_code.len() > 1);
+
+        let compact = to_json(&result, true).unwrap();
+        assert!(!compact.contains('\n'));
+        assert!(compact.len() < to_json(&result, false).unwrap().len());
+
+        let parsed: ScanOutput = serde_json::from_str(&compact).unwrap();
+        let ids = |output: &ScanOutput| -> Vec<String> {
+            output
+                .dead_code
+                .iter()
+                .map(|item| item.id.clone())
+                .collect()
+        };
+        assert_eq!(ids(&parsed), ids(&result));
+        assert_eq!(parsed.summary.total_issues, result.summary.total_issues);
+    }
+
+    #[tokio::test]
+    async fn test_yaml_round_trips() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("utils.ts"),
+            "export const a = 1;\nexport const b = 2;\n",
+        )
+        .unwrap();
+        let result = Scanner::new(dir.path())
+            .with_confidence_threshold(0.0)
+            .with_stats(true)
+            .scan()
+            .await
+            .unwrap();
+        let path = dir.path().join("clrd.yaml");
+        print_yaml(&result, Some(path.clone())).unwrap();
+
+        let yaml = fs::read_to_string(&path).unwrap();
+        let parsed: ScanOutput = serde_yaml::from_str(&yaml).unwrap();
+        assert_eq!(parsed.dead_code.len(), result.dead_code.len());
+        assert_eq!(parsed.summary.total_issues, result.summary.total_issues);
+        // Durations are plain millisecond numbers
+        let stats: serde_yaml::Value = serde_yaml::from_str(&yaml).unwrap();
+        assert!(stats["stats"]["parse"].is_f64());
+        let (parsed, original) = (parsed.stats.unwrap().parse, result.stats.unwrap().parse);
+        assert!(parsed.abs_diff(original) < std::time::Duration::from_micros(1));
+    }
+
+    #[tokio::test]
+    async fn test_write_reports() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("utils.ts"),
+            "export const a = 1;\nexport const b = 2;\n",
+        )
+        .unwrap();
+        let result = Scanner::new(dir.path())
+            .with_confidence_threshold(0.0)
+            .scan()
+            .await
+            .unwrap();
+        let findings = result.dead_code.len();
+        assert!(findings > 0);
+
+        let reports = dir.path().join("reports");
+        write_reports(&result, &reports).unwrap();
+
+        let json: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(reports.join("clrd.json")).unwrap()).unwrap();
+        assert_eq!(json["dead_code"].as_array().unwrap().len(), findings);
+
+        let jsonl = fs::read_to_string(reports.join("clrd.jsonl")).unwrap();
+        assert_eq!(jsonl.lines().count(), findings + 1);
+
+        let llm = fs::read_to_string(reports.join("clrd.txt")).unwrap();
+        let table = llm
+            .lines()
+            .position(|line| line.starts_with("kind |"))
+            .unwrap();
+        assert_eq!(llm.lines().skip(table + 1).count(), findings);
+    }
+
+    fn effective_config_of(args: &ScanArgs) -> ClrConfig {
+        let dir = tempfile::tempdir().unwrap();
+        effective_config(args, dir.path(), None).unwrap()
+    }
+
+    #[test]
+    fn test_root_config_file_under_flags() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("clrd.toml"),
+            "confidence_threshold = 0.6\nextensions = [\"ts\"]\n",
+        )
+        .unwrap();
+
+        let config =
+            effective_config(&scan_args(&["--profile", "strict"]), dir.path(), None).unwrap();
+        assert_eq!(config.confidence_threshold, 0.6);
+        assert_eq!(config.extensions, vec!["ts".to_string()]);
+        assert!(config.detect_cycles);
+
+        let config =
+            effective_config(&scan_args(&["--confidence", "0.9"]), dir.path(), None).unwrap();
+        assert_eq!(config.confidence_threshold, 0.9);
+    }
+
+    #[test]
+    fn test_config_flag_replaces_root_config_file() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(dir.path().join("clrd.toml"), "confidence_threshold = 0.6\n").unwrap();
+        let other = tempfile::tempdir().unwrap();
+        let path = other.path().join("ci.toml");
+        fs::write(&path, "include_tests = true\n").unwrap();
+
+        let config = effective_config(&scan_args(&[]), dir.path(), Some(&path)).unwrap();
+        assert!(config.include_tests);
+        assert_eq!(config.confidence_threshold, 0.5);
+
+        let missing = other.path().join("missing.toml");
+        assert!(effective_config(&scan_args(&[]), dir.path(), Some(&missing)).is_err());
+    }
+
+    #[tokio::test]
+    async fn test_summary_out_with_pretty_output() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("utils.ts"),
+            "export const a = 1;\nexport const b = 2;\n",
+        )
+        .unwrap();
+        let summary = dir.path().join("summary.json");
+
+        let args = scan_args(&[
+            "--confidence",
+            "0",
+            "--summary-out",
+            summary.to_str().unwrap(),
+        ]);
+        assert!(matches!(args.format, OutputFormat::Pretty));
+        let code = run(dir.path().to_path_buf(), args, None, false)
+            .await
+            .unwrap();
+
+        let report: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&summary).unwrap()).unwrap();
+        assert_eq!(report["total_files_scanned"], 1);
+        assert_eq!(report["summary"]["unused_exports"], 2);
+        assert_eq!(report["total_findings"], report["summary"]["total_issues"]);
+        assert_eq!(report["failing"], code == 1);
+    }
+
+    #[tokio::test]
+    async fn test_staged_reports_only_staged_files() {
+        let git = |root: &Path, args: &[&str]| {
+            let status = Command::new("git")
+                .args(args)
+                .current_dir(root)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "--quiet"]);
+        fs::write(root.join("staged.ts"), "export const fresh = 1;\n").unwrap();
+        fs::write(root.join("other.ts"), "export const old = 1;\n").unwrap();
+        git(root, &["add", "staged.ts"]);
+
+        let output = root.join("clrd.json");
+        let args = scan_args(&[
+            "--staged",
+            "--format",
+            "json",
+            "-o",
+            output.to_str().unwrap(),
+        ]);
+        let code = run(root.to_path_buf(), args, None, false).await.unwrap();
+
+        let result: ScanOutput =
+            serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+        assert!(result.dead_code.iter().any(|item| item.name == "fresh"));
+        assert!(result
+            .dead_code
+            .iter()
+            .all(|item| item.relative_path == "staged.ts"));
+        // Totals still count the unstaged finding
+        assert_eq!(result.total_findings, result.summary.total_issues);
+        assert!(result.total_findings > result.dead_code.len() as u32);
+        assert_eq!(code, 1);
+    }
+
+    #[tokio::test]
+    async fn test_staged_outside_repository_fails() {
+        let dir = tempfile::tempdir().unwrap();
+        let result = run(
+            dir.path().to_path_buf(),
+            scan_args(&["--staged"]),
+            None,
+            false,
+        )
+        .await;
+        assert!(result.is_err());
+    }
+
+    #[tokio::test]
+    async fn test_clean_commit_reuses_cached_scan() {
+        let git = |root: &Path, args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=clrd", "-c", "user.email=clrd@example.com"])
+                .args(args)
+                .current_dir(root)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "--quiet"]);
+        fs::write(root.join("a.ts"), "export const unused = 1;\n").unwrap();
+        git(root, &["add", "a.ts"]);
+        git(root, &["commit", "--quiet", "-m", "init"]);
+
+        let out = tempfile::tempdir().unwrap();
+        let output = out.path().join("clrd.json");
+        let scan = |extra: &[&str]| {
+            let mut flags = vec!["--format", "json", "-o", output.to_str().unwrap()];
+            flags.extend_from_slice(extra);
+            run(root.to_path_buf(), scan_args(&flags), None, false)
+        };
+        let findings = || {
+            let result: ScanOutput =
+                serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+            result.dead_code.len()
+        };
+
+        scan(&[]).await.unwrap();
+        assert!(findings() > 0);
+        let sha = clean_head([root]).unwrap();
+        let cache = scan_cache_path(root, &sha);
+        assert!(cache.is_file());
+
+        // Tamper with the saved scan to tell a reuse from a rescan
+        let mut saved: serde_json::Value =
+            serde_json::from_str(&fs::read_to_string(&cache).unwrap()).unwrap();
+        saved["output"]["dead_code"] = serde_json::json!([]);
+        fs::write(&cache, saved.to_string()).unwrap();
+
+        scan(&[]).await.unwrap();
+        assert_eq!(findings(), 0);
+        scan(&["--stats"]).await.unwrap();
+        assert!(findings() > 0);
+        scan(&["--no-scan-cache"]).await.unwrap();
+        assert!(findings() > 0);
+
+        // A dirty tree always scans again
+        fs::write(root.join("a.ts"), "export const unused = 2;\n").unwrap();
+        assert!(clean_head([root]).is_none());
+        scan(&[]).await.unwrap();
+        assert!(findings() > 0);
+    }
+
+    #[tokio::test]
+    async fn test_dirty_second_root_skips_scan_cache() {
+        let git = |root: &Path, args: &[&str]| {
+            let status = Command::new("git")
+                .args(["-c", "user.name=clrd", "-c", "user.email=clrd@example.com"])
+                .args(args)
+                .current_dir(root)
+                .status()
+                .unwrap();
+            assert!(status.success());
+        };
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        git(root, &["init", "--quiet"]);
+        for path in ["web/a.ts", "api/b.ts"] {
+            fs::create_dir_all(root.join(path).parent().unwrap()).unwrap();
+            fs::write(root.join(path), "export const unused = 1;\n").unwrap();
+        }
+        git(root, &["add", "."]);
+        git(root, &["commit", "--quiet", "-m", "init"]);
+
+        let out = tempfile::tempdir().unwrap();
+        let output = out.path().join("clrd.json");
+        let scan = || {
+            let flags = [
+                "web",
+                "api",
+                "--format",
+                "json",
+                "-o",
+                output.to_str().unwrap(),
+            ];
+            run(root.to_path_buf(), scan_args(&flags), None, false)
+        };
+        let findings = || {
+            let result: ScanOutput =
+                serde_json::from_str(&fs::read_to_string(&output).unwrap()).unwrap();
+            result.dead_code.len()
+        };
+
+        scan().await.unwrap();
+        let before = findings();
+        assert!(clean_head([root.join("web"), root.join("api")]).is_some());
+
+        fs::write(
+            root.join("api/b.ts"),
+            "export const unused = 1;\nexport const added = 2;\n",
+        )
+        .unwrap();
+        assert!(clean_head([root.join("web"), root.join("api")]).is_none());
+        scan().await.unwrap();
+        assert!(findings() > before);
+    }
+
+    #[test]
+    fn test_repeated_and_comma_separated_ignores_merge() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        for file in [
+            "keep.ts", "a/x.ts", "b/x.ts", "c.gen.ts", "d.gen.ts", "e.gen.ts",
+        ] {
+            let path = root.join(file);
+            fs::create_dir_all(path.parent().unwrap()).unwrap();
+            fs::write(path, "export const x = 1;\n").unwrap();
+        }
+
+        let args = scan_args(&[
+            "--ignore",
+            "**/a/**,**/b/**",
+            "--ignore",
+            "**/{c,d}.gen.ts",
+            "-e",
+            "ts,tsx",
+            "-e",
+            "mts",
+        ]);
+        let ignore = args.ignore_list().unwrap();
+        assert_eq!(ignore, vec!["**/a/**", "**/b/**", "**/{c,d}.gen.ts"]);
+        assert_eq!(args.extension_list().unwrap(), vec!["ts", "tsx", "mts"]);
+
+        let mut files: Vec<String> = crate::scanner::FileWalker::new(root)
+            .with_extensions(&args.extension_list().unwrap())
+            .with_ignore_patterns(&ignore)
+            .collect_files()
+            .unwrap()
+            .iter()
+            .map(|path| {
+                path.strip_prefix(root)
+                    .unwrap()
+                    .to_string_lossy()
+                    .to_string()
+            })
+            .collect();
+        files.sort();
+        assert_eq!(files, vec!["e.gen.ts", "keep.ts"]);
+    }
+
+    fn scan_args(flags: &[&str]) -> ScanArgs {
+        use clap::Parser;
+        ScanArgs::parse_from(std::iter::once("scan").chain(flags.iter().copied()))
+    }
+
+    #[test]
+    fn test_ci_profile_config() {
+        let config = effective_config_of(&scan_args(&["--profile", "ci"]));
+        assert!(config.consider_tests_usage);
+        assert!(config.no_color);
+        assert_eq!(config.fail_on, 0.9);
+        assert_eq!(config.confidence_threshold, 0.5);
+    }
+
+    #[test]
+    fn test_strict_profile_config() {
+        let config = effective_config_of(&scan_args(&["--profile", "strict"]));
+        assert_eq!(config.confidence_threshold, 0.1);
+        assert!(config.detect_cycles);
+        assert!(config.excluded_kinds.is_empty());
+        assert!(!config.exclude_possibly_dynamic);
+    }
+
+    #[test]
+    fn test_safe_profile_config() {
+        let config = effective_config_of(&scan_args(&["--profile", "safe"]));
+        assert_eq!(config.confidence_threshold, 0.8);
+        assert_eq!(config.excluded_kinds, vec![DeadCodeKind::ZombieFile]);
+        assert!(config.exclude_possibly_dynamic);
+    }
+
+    #[test]
+    fn test_explicit_flags_override_profile() {
+        let config = effective_config_of(&scan_args(&[
+            "--profile",
+            "ci",
+            "--confidence",
+            "0.7",
+            "--fail-on",
+            "0.6",
+            "--include-tests",
+        ]));
+        assert_eq!(config.confidence_threshold, 0.7);
+        assert_eq!(config.fail_on, 0.6);
+        assert!(config.include_tests);
+        assert!(!config.consider_tests_usage);
+
+        let config = effective_config_of(&scan_args(&[]));
+        assert_eq!(config.confidence_threshold, 0.5);
+        assert_eq!(config.fail_on, 0.8);
     }
 }
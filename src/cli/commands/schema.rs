@@ -1,9 +1,23 @@
 //! Schema command - Output JSON schema for LLM integration
 
-use crate::mapper::templates::DEAD_CODE_JSON_SCHEMA;
+use crate::types::output_schema;
 use anyhow::Result;
 
 pub async fn run() -> Result<i32> {
-    println!("{}", DEAD_CODE_JSON_SCHEMA);
+    println!("{}", output_schema());
     Ok(0)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_output_schema_describes_findings() {
+        let schema: serde_json::Value = serde_json::from_str(&output_schema()).unwrap();
+
+        assert_eq!(schema["title"], "ScanOutput");
+        assert!(schema["definitions"]["DeadCodeItem"]["properties"]["confidence"].is_object());
+        assert!(schema["properties"]["content_hashes"].is_null());
+    }
+}
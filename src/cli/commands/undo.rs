@@ -0,0 +1,174 @@
+//! Undo command - Revert the most recent fix from its journal
+
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Directory holding fix journals, relative to the project root
+const HISTORY_DIR: &str = ".clrd/history";
+
+/// The files touched by one `clrd fix`
+///
+/// Their original bytes are copied, unchanged, into a directory next to the
+/// journal named after it (`<millis>.json` and `<millis>/`), so files that
+/// aren't valid UTF-8 restore exactly.
+#[derive(Debug, Serialize, Deserialize)]
+struct FixJournal {
+    files: Vec<JournalEntry>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct JournalEntry {
+    path: PathBuf,
+    /// Name of the copy of the original in the journal's directory
+    backup: String,
+}
+
+pub async fn run(root: PathBuf) -> Result<i32> {
+    let Some(journal_path) = latest_journal(&root)? else {
+        println!("{}", "Nothing to undo.".yellow());
+        return Ok(1);
+    };
+
+    let journal: FixJournal = serde_json::from_str(&fs::read_to_string(&journal_path)?)
+        .with_context(|| format!("Invalid undo journal {}", journal_path.display()))?;
+
+    let backups = journal_path.with_extension("");
+    for entry in &journal.files {
+        let original = fs::read(backups.join(&entry.backup))
+            .with_context(|| format!("Missing undo backup of {}", entry.path.display()))?;
+        // The fix may have pruned the file's directory
+        if let Some(dir) = entry.path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(&entry.path, original)?;
+        println!("  {} Restored {}", "✓".green(), entry.path.display());
+    }
+    fs::remove_file(&journal_path)?;
+    fs::remove_dir_all(&backups)?;
+
+    println!();
+    println!(
+        "{}",
+        format!("Restored {} files", journal.files.len()).bold()
+    );
+
+    Ok(0)
+}
+
+/// Save the current contents of `files` as a new journal and return its path
+pub(crate) fn record<'a>(
+    root: &Path,
+    files: impl IntoIterator<Item = &'a PathBuf>,
+) -> Result<PathBuf> {
+    let dir = root.join(HISTORY_DIR);
+    fs::create_dir_all(&dir)?;
+
+    // Millisecond names sort in the order fixes were made
+    let mut millis = SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+        .as_millis();
+    while dir.join(format!("{}.json", millis)).exists() || dir.join(millis.to_string()).exists() {
+        millis += 1;
+    }
+    let path = dir.join(format!("{}.json", millis));
+    let backups = dir.join(millis.to_string());
+    fs::create_dir_all(&backups)?;
+
+    let files = files
+        .into_iter()
+        .enumerate()
+        .map(|(i, path)| {
+            let original = fs::read(path).with_context(|| {
+                format!("Failed to read {} for the undo journal", path.display())
+            })?;
+            let backup = i.to_string();
+            fs::write(backups.join(&backup), original)?;
+            Ok(JournalEntry {
+                path: path.clone(),
+                backup,
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    fs::write(&path, serde_json::to_string_pretty(&FixJournal { files })?)?;
+    Ok(path)
+}
+
+/// The journal of the most recent fix, if any
+fn latest_journal(root: &Path) -> Result<Option<PathBuf>> {
+    let dir = root.join(HISTORY_DIR);
+    if !dir.exists() {
+        return Ok(None);
+    }
+
+    let mut latest: Option<(u128, PathBuf)> = None;
+    for entry in fs::read_dir(&dir)? {
+        let path = entry?.path();
+        // Skip the backup directories next to the journals
+        if path.extension().and_then(|ext| ext.to_str()) != Some("json") {
+            continue;
+        }
+        let Some(millis) = path
+            .file_stem()
+            .and_then(|stem| stem.to_str())
+            .and_then(|stem| stem.parse::<u128>().ok())
+        else {
+            continue;
+        };
+        if latest.as_ref().map_or(true, |(best, _)| millis > *best) {
+            latest = Some((millis, path));
+        }
+    }
+
+    Ok(latest.map(|(_, path)| path))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::cli::FixArgs;
+
+    #[tokio::test]
+    async fn test_fix_then_undo_restores_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let file = root.join("utils.ts");
+        let original = "export const unused = 1;\r\nconst kept = 2;\nconsole.log(kept);\n\n";
+        fs::write(&file, original).unwrap();
+
+        let args = FixArgs::from_flags(&["--force"]);
+        crate::cli::commands::fix::run(root.clone(), args, None)
+            .await
+            .unwrap();
+        assert_ne!(fs::read_to_string(&file).unwrap(), original);
+
+        assert_eq!(run(root.clone()).await.unwrap(), 0);
+        assert_eq!(fs::read(&file).unwrap(), original.as_bytes());
+
+        // The journal is consumed
+        assert_eq!(latest_journal(&root).unwrap(), None);
+        assert_eq!(run(root).await.unwrap(), 1);
+    }
+
+    #[tokio::test]
+    async fn test_undo_restores_bytes_that_are_not_utf8() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().to_path_buf();
+        let file = root.join("latin1.ts");
+        let original = b"// caf\xe9\nexport const unused = 1;\n";
+        fs::write(&file, original).unwrap();
+
+        record(&root, [&file]).unwrap();
+        fs::write(&file, "// edited\n").unwrap();
+
+        assert_eq!(run(root.clone()).await.unwrap(), 0);
+        assert_eq!(fs::read(&file).unwrap(), original);
+        // The journal and its backups are gone
+        assert_eq!(fs::read_dir(root.join(HISTORY_DIR)).unwrap().count(), 0);
+    }
+}
@@ -0,0 +1,193 @@
+//! Validate-judgment command - Check an LLM judgment file before fixing
+//!
+//! Reports malformed JSON and fields that don't match
+//! `LlmJudgmentResponse`, then warns about items no scan finding matches,
+//! since `clrd fix --from-judgment` would silently skip those.
+
+use super::diff::load_output;
+use super::fix::refers_to;
+use crate::cli::ValidateJudgmentArgs;
+use crate::scanner::{load_root_config, Scanner};
+use crate::types::{ClrConfig, ConfirmedDeadCode, LlmJudgmentResponse, RejectedItem, ScanOutput};
+use anyhow::{Context, Result};
+use colored::Colorize;
+use serde::de::DeserializeOwned;
+use serde_json::Value;
+use std::fs;
+use std::path::PathBuf;
+
+pub async fn run(
+    root: PathBuf,
+    args: ValidateJudgmentArgs,
+    config_file: Option<PathBuf>,
+) -> Result<i32> {
+    let text = fs::read_to_string(root.join(&args.file))
+        .with_context(|| format!("Failed to read {}", args.file.display()))?;
+
+    let judgment = match parse_judgment(&text) {
+        Ok(judgment) => judgment,
+        Err(errors) => {
+            println!(
+                "{} {} is not a valid judgment:",
+                "✗".red(),
+                args.file.display()
+            );
+            for error in errors {
+                println!("  {} {}", "-".red(), error);
+            }
+            return Ok(1);
+        }
+    };
+
+    let scan = match &args.scan {
+        Some(path) => load_output(&root.join(path))?,
+        // Every finding counts, whatever threshold the judged scan used
+        None => {
+            let mut config = load_root_config(&root, config_file.as_deref(), ClrConfig::default())?;
+            config.confidence_threshold = 0.0;
+            Scanner::new(&root).with_config(&config).scan().await?
+        }
+    };
+
+    let warnings = unknown_items(&judgment, &scan);
+    for warning in &warnings {
+        println!("  {} {}", "⚠".yellow(), warning);
+    }
+    println!(
+        "{} {} confirmed, {} rejected, {} unknown",
+        "✓".green(),
+        judgment.confirmed.len(),
+        judgment.rejected.len(),
+        warnings.len()
+    );
+
+    Ok(0)
+}
+
+/// Deserialize a judgment, or list every structural problem: the first
+/// with its line and column, then each offending item by field path
+fn parse_judgment(text: &str) -> Result<LlmJudgmentResponse, Vec<String>> {
+    let value: Value = serde_json::from_str(text).map_err(|e| vec![e.to_string()])?;
+    let first = match serde_json::from_str(text) {
+        Ok(judgment) => return Ok(judgment),
+        Err(e) => e.to_string(),
+    };
+
+    let mut errors = vec![first];
+    let Some(object) = value.as_object() else {
+        errors.push("expected an object with `confirmed` and `rejected` arrays".to_string());
+        return Err(errors);
+    };
+    check_items::<ConfirmedDeadCode>(object.get("confirmed"), "confirmed", &mut errors);
+    check_items::<RejectedItem>(object.get("rejected"), "rejected", &mut errors);
+    Err(errors)
+}
+
+fn check_items<T: DeserializeOwned>(items: Option<&Value>, field: &str, errors: &mut Vec<String>) {
+    let Some(items) = items else {
+        errors.push(format!("`{}`: missing", field));
+        return;
+    };
+    let Some(items) = items.as_array() else {
+        errors.push(format!("`{}`: expected an array", field));
+        return;
+    };
+    for (index, item) in items.iter().enumerate() {
+        if let Err(e) = serde_json::from_value::<T>(item.clone()) {
+            errors.push(format!("`{}[{}]`: {}", field, index, e));
+        }
+    }
+}
+
+/// Judged items that match no finding of the scan
+fn unknown_items(judgment: &LlmJudgmentResponse, scan: &ScanOutput) -> Vec<String> {
+    let confirmed: Vec<(&str, &str)> = judgment
+        .confirmed
+        .iter()
+        .map(|item| (item.file_path.as_str(), item.name.as_str()))
+        .collect();
+    let rejected: Vec<(&str, &str)> = judgment
+        .rejected
+        .iter()
+        .map(|item| (item.file_path.as_str(), item.name.as_str()))
+        .collect();
+
+    let mut warnings = Vec::new();
+    for (field, items) in [("confirmed", confirmed), ("rejected", rejected)] {
+        for (index, (file_path, name)) in items.into_iter().enumerate() {
+            if !scan
+                .dead_code
+                .iter()
+                .any(|item| refers_to(file_path, name, item))
+            {
+                warnings.push(format!(
+                    "`{}[{}]`: no finding named `{}` in {}",
+                    field, index, name, file_path
+                ));
+            }
+        }
+    }
+    warnings
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::{DeadCodeItem, DeadCodeKind};
+
+    async fn scan_with(path: &str, name: &str) -> ScanOutput {
+        let dir = tempfile::tempdir().unwrap();
+        let mut scan = Scanner::new(dir.path()).scan().await.unwrap();
+        scan.dead_code =
+            vec![DeadCodeItem::fixture(path, name, DeadCodeKind::UnusedExport).in_root(dir.path())];
+        scan
+    }
+
+    #[tokio::test]
+    async fn test_valid_judgment() {
+        let text = r#"{
+            "confirmed": [{ "file_path": "src/a.ts", "name": "helper", "action": "delete" }],
+            "rejected": []
+        }"#;
+        let judgment = parse_judgment(text).unwrap();
+
+        let scan = scan_with("src/a.ts", "helper").await;
+        assert!(unknown_items(&judgment, &scan).is_empty());
+    }
+
+    #[test]
+    fn test_schema_invalid_judgment() {
+        let text = r#"{
+            "confirmed": [
+                { "file_path": "src/a.ts", "name": "ok", "action": "delete" },
+                { "file_path": "src/a.ts", "name": "helper", "action": "remove" }
+            ]
+        }"#;
+        let errors = parse_judgment(text).unwrap_err();
+
+        assert!(errors[0].contains("line 4"), "{:?}", errors);
+        assert!(errors
+            .iter()
+            .any(|e| e.starts_with("`confirmed[1]`: unknown variant `remove`")));
+        assert!(errors.contains(&"`rejected`: missing".to_string()));
+
+        let errors = parse_judgment("{ \"confirmed\": [").unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("line 1"));
+    }
+
+    #[tokio::test]
+    async fn test_unknown_symbol_is_reported() {
+        let text = r#"{
+            "confirmed": [{ "file_path": "src/a.ts", "name": "helper", "action": "delete" }],
+            "rejected": [{ "file_path": "src/a.ts", "name": "gone", "reason": "used" }]
+        }"#;
+        let judgment = parse_judgment(text).unwrap();
+
+        let scan = scan_with("src/a.ts", "helper").await;
+        assert_eq!(
+            unknown_items(&judgment, &scan),
+            vec!["`rejected[0]`: no finding named `gone` in src/a.ts"]
+        );
+    }
+}
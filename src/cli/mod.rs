@@ -1,12 +1,15 @@
 //! CLI Module - Command Line Interface
 //!
-//! Implements the clrd commands: init, scan, fix, schema
+//! Implements the clrd commands: init, scan, fix, undo, map, explain, diff,
+//! validate-judgment, api, doctor, lsp, schema
 
 mod commands;
 
 use anyhow::Result;
 use clap::{Parser, Subcommand};
+use std::io::IsTerminal;
 use std::path::PathBuf;
+use std::time::Duration;
 
 pub use commands::*;
 
@@ -28,13 +31,23 @@ pub struct Cli {
     /// Working directory (defaults to current directory)
     #[arg(short = 'C', long, global = true)]
     pub directory: Option<PathBuf>,
+
+    /// Config file to use instead of the root clrd.toml (TOML, JSON or YAML,
+    /// by extension)
+    #[arg(long, global = true, value_name = "FILE")]
+    pub config: Option<PathBuf>,
+
+    /// Disable colored output (also disabled by NO_COLOR or a non-terminal stdout)
+    #[arg(long, global = true)]
+    pub no_color: bool,
 }
 
 #[derive(Subcommand, Debug)]
 pub enum Commands {
     /// Initialize clrd in the current project
     ///
-    /// Creates clrd.md with usage instructions for AI agents.
+    /// Creates clrd.md with usage instructions for AI agents, plus
+    /// .github/copilot-instructions.md if missing.
     /// If claude.md, agent.md, or .cursorrules exist, adds a reference to clrd.md.
     Init(InitArgs),
 
@@ -42,7 +55,7 @@ pub enum Commands {
     ///
     /// Analyzes the codebase and identifies unused exports,
     /// unreachable functions, zombie files, and more.
-    Scan(ScanArgs),
+    Scan(Box<ScanArgs>),
 
     /// Fix dead code issues
     ///
@@ -50,6 +63,55 @@ pub enum Commands {
     /// Requires confirmation or --force flag.
     Fix(FixArgs),
 
+    /// Undo the most recent fix
+    ///
+    /// Restores the files changed by the last `clrd fix` from the
+    /// journal in .clrd/history.
+    Undo,
+
+    /// Update AI context files with the latest scan report
+    ///
+    /// Writes a dead code report section into claude.md and agent.md,
+    /// replacing the section from a previous run.
+    Map(MapArgs),
+
+    /// Explain a single finding in detail
+    ///
+    /// Shows the full code, confidence breakdown, context and
+    /// references for one `<file>:<name>` finding.
+    Explain(ExplainArgs),
+
+    /// Compare two JSON scan outputs
+    ///
+    /// Matches findings by id and reports those added, removed and
+    /// unchanged. Exits non-zero when findings were added.
+    Diff(DiffArgs),
+
+    /// Check an LLM judgment file
+    ///
+    /// Reports JSON that doesn't match the judgment schema and warns
+    /// about items no finding of the scan matches.
+    ValidateJudgment(ValidateJudgmentArgs),
+
+    /// List the public API surface
+    ///
+    /// Prints every symbol exported from the package entry points in
+    /// package.json (or --entry), located where it is declared.
+    Api(ApiArgs),
+
+    /// Diagnose configuration and environment
+    ///
+    /// Reports the resolved root, config files, extensions and ignore
+    /// patterns in effect, how many files a scan would collect, and
+    /// whether tsconfig.json, package.json and git were found.
+    Doctor,
+
+    /// Start a language server publishing dead code diagnostics
+    ///
+    /// Speaks LSP over stdio and rescans the workspace whenever a
+    /// document is opened or saved.
+    Lsp(LspArgs),
+
     /// Output JSON schema for LLM integration
     Schema,
 }
@@ -67,25 +129,196 @@ pub struct ScanArgs {
     #[arg(short, long, value_enum, default_value = "pretty")]
     pub format: OutputFormat,
 
-    /// File extensions to scan (comma-separated)
-    #[arg(short, long, value_delimiter = ',')]
+    /// File extensions to scan (repeatable or comma-separated)
+    #[arg(short, long)]
     pub extensions: Option<Vec<String>>,
 
-    /// Patterns to ignore (comma-separated glob patterns)
-    #[arg(short, long, value_delimiter = ',')]
+    /// Patterns to ignore (repeatable or comma-separated glob patterns)
+    #[arg(short, long)]
     pub ignore: Option<Vec<String>>,
 
     /// Include test files in analysis
     #[arg(long)]
     pub include_tests: bool,
 
-    /// Minimum confidence threshold (0.0 - 1.0)
-    #[arg(long, default_value = "0.5")]
-    pub confidence: f64,
+    /// Count imports from test files as usage without reporting findings in them
+    #[arg(long, conflicts_with = "include_tests")]
+    pub consider_tests_usage: bool,
+
+    /// Preset configuration; explicit flags override its settings
+    #[arg(long, value_enum)]
+    pub profile: Option<Profile>,
+
+    /// Minimum confidence threshold (0.0 - 1.0) [default: 0.5]
+    #[arg(long)]
+    pub confidence: Option<f64>,
+
+    /// Exit with a non-zero code when a finding reaches this confidence [default: 0.8]
+    #[arg(long, value_name = "CONFIDENCE")]
+    pub fail_on: Option<f64>,
 
-    /// Output file (for json format)
+    /// Report only findings in files staged for commit, for pre-commit hooks;
+    /// any finding fails unless --fail-on is given
+    #[arg(long)]
+    pub staged: bool,
+
+    /// Output file (for json, jsonl, yaml and llm formats)
     #[arg(short, long)]
     pub output: Option<PathBuf>,
+
+    /// Write `--format json` on a single line instead of indented
+    #[arg(long)]
+    pub json_compact: bool,
+
+    /// Also write clrd.json, clrd.jsonl and clrd.txt (llm) reports into this directory
+    #[arg(long, value_name = "DIR")]
+    pub output_dir: Option<PathBuf>,
+
+    /// Also write the summary, totals and pass/fail as JSON to this file
+    #[arg(long, value_name = "FILE")]
+    pub summary_out: Option<PathBuf>,
+
+    /// Print per-phase timing statistics to stderr
+    #[arg(long)]
+    pub stats: bool,
+
+    /// Emit only the summary numbers; `dead_code` is left empty
+    #[arg(long)]
+    pub summary_only: bool,
+
+    /// Exit with a non-zero code if any file failed to parse, and keep full
+    /// confidence for hook-like (`useX`) and HOC-like (`withX`) export names
+    #[arg(long)]
+    pub strict: bool,
+
+    /// Group pretty output by file or kind (default: flat list)
+    #[arg(long, value_enum)]
+    pub group_by: Option<GroupBy>,
+
+    /// Never report this export name as unused (repeatable, accepts globs)
+    #[arg(long = "keep", value_name = "NAME")]
+    pub keep: Vec<String>,
+
+    /// Emit at most N findings, most confident first
+    #[arg(long, value_name = "N")]
+    pub max_results: Option<usize>,
+
+    /// Emit at most N findings per file, most confident first
+    #[arg(long, value_name = "N")]
+    pub max_per_file: Option<usize>,
+
+    /// Reuse the cached graph, re-parsing only files that changed
+    #[arg(long, conflicts_with = "full")]
+    pub incremental: bool,
+
+    /// Re-parse every file and rebuild the graph cache
+    #[arg(long)]
+    pub full: bool,
+
+    /// Always scan, even when the clean tree's commit was scanned before
+    #[arg(long)]
+    pub no_scan_cache: bool,
+
+    /// Read newline-separated file paths from stdin and analyze only those
+    ///
+    /// Files not listed are not parsed, so exports used only from
+    /// unlisted files are reported as unused.
+    #[arg(long)]
+    pub stdin: bool,
+
+    /// Threads reading files before parsing (defaults to the number of CPUs)
+    #[arg(long, value_name = "N")]
+    pub read_threads: Option<usize>,
+
+    /// Memory-map files of at least this many bytes instead of buffering them
+    #[arg(long, value_name = "BYTES")]
+    pub mmap_threshold: Option<u64>,
+
+    /// Skip files larger than this many bytes, 0 for no limit [default: 2 MiB]
+    #[arg(long, value_name = "BYTES")]
+    pub max_file_size: Option<u64>,
+
+    /// Entry file for reachability analysis (repeatable); files not reachable
+    /// from any entry are reported as zombies
+    #[arg(long, value_name = "FILE")]
+    pub entry: Vec<PathBuf>,
+
+    /// Report import cycles as informational `circular_import` findings
+    #[arg(long)]
+    pub detect_cycles: bool,
+
+    /// List relative imports that resolve to no scanned file (also with --verbose)
+    #[arg(long)]
+    pub report_unresolved: bool,
+
+    /// Skip findings in files changed more recently than this (e.g. 30m, 12h, 7d, 2w),
+    /// by last git commit or modification time
+    #[arg(long, value_name = "DURATION", value_parser = parse_duration)]
+    pub min_age: Option<Duration>,
+
+    /// Leave code snippets out of findings
+    #[arg(long)]
+    pub no_snippet: bool,
+
+    /// Lines of surrounding code shown before and after each snippet
+    #[arg(long, value_name = "LINES")]
+    pub context: Option<usize>,
+
+    /// Cut snippets after this many lines (0 for no limit)
+    #[arg(long, value_name = "LINES")]
+    pub snippet_max_lines: Option<usize>,
+
+    /// Leave the context hints out of findings
+    #[arg(long)]
+    pub no_context: bool,
+
+    /// Directories to scan together (defaults to the working directory)
+    ///
+    /// Findings are merged into one report and imports between the
+    /// directories resolve; each file's path is relative to its own root.
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<PathBuf>,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Profile {
+    /// Count test imports as usage, fail only on findings of 90%+, no color
+    Ci,
+    /// Report every kind, including import cycles, down to 10% confidence
+    Strict,
+    /// Only findings of 80%+, without zombie files or possibly dynamic symbols
+    Safe,
+}
+
+impl ScanArgs {
+    /// `--extensions`, every occurrence split at commas
+    pub fn extension_list(&self) -> Option<Vec<String>> {
+        self.extensions.as_deref().map(split_list)
+    }
+
+    /// `--ignore`, every occurrence split at commas outside `{..}` alternatives
+    pub fn ignore_list(&self) -> Option<Vec<String>> {
+        self.ignore.as_deref().map(split_list)
+    }
+}
+
+impl Profile {
+    /// The preset configuration this profile starts from
+    pub fn config(self) -> crate::types::ClrConfig {
+        match self {
+            Profile::Ci => crate::types::ClrConfig::ci(),
+            Profile::Strict => crate::types::ClrConfig::strict(),
+            Profile::Safe => crate::types::ClrConfig::safe(),
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GroupBy {
+    /// Nest findings under their file path
+    File,
+    /// Nest findings under their kind
+    Kind,
 }
 
 #[derive(clap::ValueEnum, Clone, Debug, Default)]
@@ -95,8 +328,14 @@ pub enum OutputFormat {
     Pretty,
     /// JSON output for LLM consumption
     Json,
+    /// One JSON finding per line, then a summary line
+    Jsonl,
+    /// YAML, the same document as `json`
+    Yaml,
     /// Compact single-line output
     Compact,
+    /// Plain-text report for pasting into an LLM chat
+    Llm,
     /// Interactive TUI
     Tui,
 }
@@ -119,9 +358,118 @@ pub struct FixArgs {
     #[arg(long, default_value = "0.8")]
     pub confidence: f64,
 
-    /// Specific files to fix (if not specified, fixes all)
+    /// Files to fix, as paths or globs like `src/legacy/**` (default: all)
     #[arg(short, long)]
     pub files: Option<Vec<PathBuf>>,
+
+    /// Stage the fixed files and create a git commit summarizing the removals
+    #[arg(long)]
+    pub commit: bool,
+
+    /// Only fix the items confirmed in an LLM judgment response (JSON)
+    #[arg(long, value_name = "FILE")]
+    pub from_judgment: Option<PathBuf>,
+
+    /// Remove directories left empty by deleted files
+    #[arg(long)]
+    pub prune_dirs: bool,
+
+    /// Unchanged lines shown around each change in the --dry-run diff
+    #[arg(long, default_value = "3", value_name = "LINES")]
+    pub context: usize,
+}
+
+#[cfg(test)]
+impl FixArgs {
+    /// Parse `clrd fix` flags, so tests only spell out what they change
+    pub(crate) fn from_flags(flags: &[&str]) -> Self {
+        Self::parse_from(std::iter::once("fix").chain(flags.iter().copied()))
+    }
+}
+
+#[derive(Parser, Debug)]
+pub struct MapArgs {
+    /// Minimum confidence threshold (0.0 - 1.0)
+    #[arg(long, default_value = "0.5")]
+    pub confidence: f64,
+}
+
+#[derive(Parser, Debug)]
+pub struct ExplainArgs {
+    /// Finding to explain, as `<file>:<name>` (or just `<file>` for zombie files)
+    pub target: String,
+
+    /// Only match findings of this kind (e.g. unused_export)
+    #[arg(short, long)]
+    pub kind: Option<String>,
+
+    /// Include test files in analysis
+    #[arg(long)]
+    pub include_tests: bool,
+}
+
+#[derive(Parser, Debug)]
+pub struct DiffArgs {
+    /// Earlier scan output (`clrd scan --format json`)
+    pub old: PathBuf,
+
+    /// Later scan output
+    pub new: PathBuf,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "pretty")]
+    pub format: DiffFormat,
+}
+
+#[derive(Parser, Debug)]
+pub struct ApiArgs {
+    /// Entry file to list exports of (repeatable; defaults to package.json
+    /// `exports`, `module` and `main`)
+    #[arg(long, value_name = "FILE")]
+    pub entry: Vec<PathBuf>,
+
+    /// Output format
+    #[arg(short, long, value_enum, default_value = "pretty")]
+    pub format: ApiFormat,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+pub enum ApiFormat {
+    /// Exports grouped by entry point
+    #[default]
+    Pretty,
+    /// JSON array of exported symbols
+    Json,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, Default)]
+pub enum DiffFormat {
+    /// Human-readable colored output
+    #[default]
+    Pretty,
+    /// JSON with the added, removed and unchanged findings
+    Json,
+}
+
+#[derive(Parser, Debug)]
+pub struct ValidateJudgmentArgs {
+    /// Judgment file (`LlmJudgmentResponse` JSON)
+    pub file: PathBuf,
+
+    /// Scan output to check items against, instead of a fresh scan
+    #[arg(long)]
+    pub scan: Option<PathBuf>,
+}
+
+#[derive(Parser, Debug)]
+pub struct LspArgs {
+    /// Minimum confidence threshold (0.0 - 1.0)
+    #[arg(long, default_value = "0.5")]
+    pub confidence: f64,
+
+    /// Include test files in analysis
+    #[arg(long)]
+    pub include_tests: bool,
 }
 
 /// Run the CLI with given arguments
@@ -133,14 +481,115 @@ pub async fn run_cli(args: Vec<String>) -> Result<i32> {
         Cli::parse_from(std::iter::once("clrd".to_string()).chain(args))
     };
 
+    colored::control::set_override(color_enabled(
+        cli.no_color,
+        std::env::var("NO_COLOR").ok().as_deref(),
+        std::io::stdout().is_terminal(),
+    ));
+
     let root = cli
         .directory
         .unwrap_or_else(|| std::env::current_dir().unwrap_or_else(|_| PathBuf::from(".")));
 
+    // Relative to the root, like every other path argument
+    let config_file = cli.config.map(|path| root.join(path));
+
     match cli.command {
         Commands::Init(args) => commands::init::run(root, args).await,
-        Commands::Scan(args) => commands::scan::run(root, args, cli.verbose).await,
-        Commands::Fix(args) => commands::fix::run(root, args).await,
+        Commands::Scan(args) => commands::scan::run(root, *args, config_file, cli.verbose).await,
+        Commands::Fix(args) => commands::fix::run(root, args, config_file).await,
+        Commands::Undo => commands::undo::run(root).await,
+        Commands::Map(args) => commands::map::run(root, args, config_file).await,
+        Commands::Explain(args) => commands::explain::run(root, args, config_file).await,
+        Commands::Diff(args) => commands::diff::run(root, args).await,
+        Commands::ValidateJudgment(args) => {
+            commands::validate_judgment::run(root, args, config_file).await
+        }
+        Commands::Api(args) => commands::api::run(root, args, config_file).await,
+        Commands::Doctor => commands::doctor::run(root, config_file).await,
+        Commands::Lsp(args) => commands::lsp::run(root, args, config_file).await,
         Commands::Schema => commands::schema::run().await,
     }
 }
+
+/// Parse a duration such as `90s`, `30m`, `12h`, `7d` or `2w`
+fn parse_duration(value: &str) -> Result<Duration, String> {
+    let value = value.trim();
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .unwrap_or(value.len());
+    let (amount, unit) = value.split_at(split);
+    let amount: u64 = amount
+        .parse()
+        .map_err(|_| format!("invalid duration {:?}", value))?;
+    let seconds = match unit {
+        "s" => 1,
+        "m" => 60,
+        "h" => 60 * 60,
+        "d" => 24 * 60 * 60,
+        "w" => 7 * 24 * 60 * 60,
+        _ => return Err(format!("unknown unit in {:?} (use s, m, h, d or w)", value)),
+    };
+    amount
+        .checked_mul(seconds)
+        .map(Duration::from_secs)
+        .ok_or_else(|| format!("duration {:?} is too long", value))
+}
+
+/// Split comma-separated values, keeping commas inside glob braces (`{a,b}`)
+fn split_list(values: &[String]) -> Vec<String> {
+    let mut items = Vec::new();
+    for value in values {
+        let mut depth = 0usize;
+        let mut start = 0;
+        for (i, c) in value.char_indices() {
+            match c {
+                '{' => depth += 1,
+                '}' => depth = depth.saturating_sub(1),
+                ',' if depth == 0 => {
+                    items.push(&value[start..i]);
+                    start = i + 1;
+                }
+                _ => {}
+            }
+        }
+        items.push(&value[start..]);
+    }
+    items
+        .into_iter()
+        .map(str::trim)
+        .filter(|item| !item.is_empty())
+        .map(String::from)
+        .collect()
+}
+
+/// Colors are used only on a terminal, unless `--no-color` or a non-empty `NO_COLOR` is set
+fn color_enabled(no_color_flag: bool, no_color_env: Option<&str>, is_terminal: bool) -> bool {
+    !no_color_flag && no_color_env.map_or(true, str::is_empty) && is_terminal
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_color_enabled() {
+        assert!(color_enabled(false, None, true));
+        assert!(color_enabled(false, Some(""), true));
+        assert!(!color_enabled(true, None, true));
+        assert!(!color_enabled(false, Some("1"), true));
+        assert!(!color_enabled(false, None, false));
+    }
+
+    #[test]
+    fn test_parse_duration() {
+        assert_eq!(parse_duration("90s"), Ok(Duration::from_secs(90)));
+        assert_eq!(parse_duration("12h"), Ok(Duration::from_secs(12 * 3600)));
+        assert_eq!(parse_duration("7d"), Ok(Duration::from_secs(7 * 86400)));
+        assert_eq!(parse_duration("2w"), Ok(Duration::from_secs(14 * 86400)));
+        assert!(parse_duration("7").is_err());
+        assert!(parse_duration("d").is_err());
+        assert!(parse_duration("3y").is_err());
+        assert!(parse_duration("99999999999999999w").is_err());
+    }
+}
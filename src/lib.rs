@@ -23,7 +23,9 @@ pub use types::*;
 mod napi_bindings {
     use super::*;
     use napi_derive::napi;
-    use std::sync::OnceLock;
+    use std::path::Path;
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::{Arc, OnceLock};
 
     static INIT: OnceLock<()> = OnceLock::new();
 
@@ -35,6 +37,7 @@ mod napi_bindings {
                         .add_directive("clrd=info".parse().unwrap()),
                 )
                 .with_target(false)
+                .with_writer(std::io::stderr)
                 .init();
         });
     }
@@ -67,11 +70,15 @@ mod napi_bindings {
     #[napi(object)]
     #[derive(Debug, Clone)]
     pub struct ScanResultItem {
+        pub id: String,
         pub file_path: String,
         pub line_start: u32,
         pub line_end: u32,
+        pub byte_start: u32,
+        pub byte_end: u32,
         pub code_snippet: String,
         pub kind: String,
+        pub severity: String,
         pub name: String,
         pub reason: String,
         pub confidence: f64,
@@ -85,15 +92,58 @@ mod napi_bindings {
         pub scan_duration_ms: i64,
     }
 
+    /// Cancels a running `scan` it was passed to
+    #[napi]
+    #[derive(Default)]
+    pub struct CancellationToken {
+        cancelled: Arc<AtomicBool>,
+    }
+
+    #[napi]
+    impl CancellationToken {
+        #[napi(constructor)]
+        pub fn new() -> Self {
+            Self::default()
+        }
+
+        /// Stop the scan; it rejects with "Scan cancelled"
+        #[napi]
+        pub fn cancel(&self) {
+            self.cancelled.store(true, Ordering::Relaxed);
+        }
+
+        #[napi(getter)]
+        pub fn is_cancelled(&self) -> bool {
+            self.cancelled.load(Ordering::Relaxed)
+        }
+    }
+
+    /// JSON Schema of the scan output, for LLM tool definitions
+    #[napi]
+    pub fn schema() -> String {
+        crate::types::output_schema()
+    }
+
     /// Programmatic scan API for Node.js consumers
     #[napi]
-    pub async fn scan(options: ScanOptions) -> napi::Result<ScanResult> {
+    pub async fn scan(
+        options: ScanOptions,
+        token: Option<&CancellationToken>,
+    ) -> napi::Result<ScanResult> {
         init_logger();
 
-        let scanner = Scanner::new(&options.root)
+        // The root `clrd.toml` applies as it does to `clrd scan`
+        let config =
+            crate::scanner::load_root_config(Path::new(&options.root), None, ClrConfig::default())
+                .map_err(|e| napi::Error::from_reason(format!("{e:#}")))?;
+        let mut scanner = Scanner::new(&options.root)
+            .with_config(&config)
             .with_extensions(options.extensions)
             .with_ignore_patterns(options.ignore_patterns)
-            .include_tests(options.include_tests);
+            .include_tests(options.include_tests || config.include_tests);
+        if let Some(token) = token {
+            scanner = scanner.with_cancel_token(token.cancelled.clone());
+        }
 
         let result = scanner
             .scan()
@@ -105,11 +155,15 @@ mod napi_bindings {
                 .dead_code
                 .into_iter()
                 .map(|item| ScanResultItem {
+                    id: item.id,
                     file_path: item.file_path.to_string_lossy().to_string(),
                     line_start: item.span.start,
                     line_end: item.span.end,
+                    byte_start: item.span.byte_start,
+                    byte_end: item.span.byte_end,
                     code_snippet: item.code_snippet,
                     kind: item.kind.to_string(),
+                    severity: item.severity.to_string(),
                     name: item.name,
                     reason: item.reason,
                     confidence: item.confidence,
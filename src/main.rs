@@ -13,6 +13,7 @@ async fn main() {
                 .add_directive("clrd=info".parse().unwrap()),
         )
         .with_target(false)
+        .with_writer(std::io::stderr)
         .init();
 
     let args: Vec<String> = std::env::args().skip(1).collect();
@@ -4,6 +4,7 @@
 
 pub mod templates;
 
+use crate::types::{OutputConfig, ScanOutput};
 use anyhow::{Context, Result};
 use std::fs;
 use std::path::Path;
@@ -14,20 +15,62 @@ pub use templates::*;
 /// Marker to check if clrd reference already exists
 const CLRD_REFERENCE_MARKER: &str = "clrd.md";
 
+/// Delimiters around the generated report section in context files
+pub const REPORT_START_MARKER: &str = "<!-- clrd:report:start -->";
+pub const REPORT_END_MARKER: &str = "<!-- clrd:report:end -->";
+
+/// Context files that receive the dead code report on `clrd map`
+const REPORT_TARGETS: &[&str] = &["claude.md", "agent.md"];
+
+/// Context file paths relative to the root
+const COPILOT_INSTRUCTIONS_PATH: &str = ".github/copilot-instructions.md";
+const WINDSURFRULES_PATH: &str = ".windsurfrules";
+const GEMINI_MD_PATH: &str = "GEMINI.md";
+
+/// Environment variable pointing at a directory of template overrides
+pub const TEMPLATE_DIR_ENV: &str = "CLRD_TEMPLATE_DIR";
+
 /// Mapper generates AI context files
 pub struct Mapper {
     root: PathBuf,
+    output: OutputConfig,
+    template_dir: Option<PathBuf>,
 }
 
 impl Mapper {
+    /// Create a mapper for the given root
+    ///
+    /// Template overrides are read from `CLRD_TEMPLATE_DIR` when set.
     pub fn new(root: impl AsRef<Path>) -> Self {
         Self {
             root: root.as_ref().to_path_buf(),
+            output: OutputConfig::default(),
+            template_dir: std::env::var_os(TEMPLATE_DIR_ENV).map(PathBuf::from),
         }
     }
 
+    /// Load templates from this directory, falling back to the built-ins
+    /// for any file that is missing
+    ///
+    /// Recognized files: `clrd.md`, `claude_reference.md`, `agent_reference.md`,
+    /// `cursorrules_reference.txt`, `copilot_instructions.md`, `copilot_reference.md`,
+    /// `windsurfrules.txt`, `windsurfrules_reference.txt`, `gemini.md`, `gemini_reference.md`
+    pub fn with_template_dir(mut self, dir: Option<PathBuf>) -> Self {
+        if dir.is_some() {
+            self.template_dir = dir;
+        }
+        self
+    }
+
+    /// Select which context files are generated or updated
+    pub fn with_output_config(mut self, output: OutputConfig) -> Self {
+        self.output = output;
+        self
+    }
+
     /// Initialize clrd context
     /// - Always creates/updates clrd.md
+    /// - Creates .github/copilot-instructions.md, .windsurfrules, GEMINI.md if missing
     /// - Adds reference to existing claude.md, agent.md, .cursorrules
     pub fn init(&self, force: bool) -> Result<InitReport> {
         let mut report = InitReport::default();
@@ -37,16 +80,37 @@ impl Mapper {
         report.created.push("clrd.md".to_string());
 
         // Add reference to existing files
-        if self.add_reference_to_claude_md()? {
-            report.updated.push("claude.md (added clrd.md reference)".to_string());
+        if self.output.claude_md && self.add_reference_to_claude_md()? {
+            report
+                .updated
+                .push("claude.md (added clrd.md reference)".to_string());
+        }
+
+        if self.output.agent_md && self.add_reference_to_agent_md()? {
+            report
+                .updated
+                .push("agent.md (added clrd.md reference)".to_string());
+        }
+
+        if self.output.cursorrules && self.add_reference_to_cursorrules()? {
+            report
+                .updated
+                .push(".cursorrules (added clrd.md reference)".to_string());
+        }
+
+        if self.output.copilot_instructions {
+            let result = self.add_copilot_instructions()?;
+            report.record(COPILOT_INSTRUCTIONS_PATH, result);
         }
 
-        if self.add_reference_to_agent_md()? {
-            report.updated.push("agent.md (added clrd.md reference)".to_string());
+        if self.output.windsurfrules {
+            let result = self.add_reference_to_windsurfrules()?;
+            report.record(WINDSURFRULES_PATH, result);
         }
 
-        if self.add_reference_to_cursorrules()? {
-            report.updated.push(".cursorrules (added clrd.md reference)".to_string());
+        if self.output.gemini_md {
+            let result = self.add_reference_to_gemini_md()?;
+            report.record(GEMINI_MD_PATH, result);
         }
 
         Ok(report)
@@ -60,7 +124,7 @@ impl Mapper {
             // Update anyway - clrd.md is our file
         }
 
-        let content = templates::CLRD_MD_TEMPLATE;
+        let content = self.load_template("clrd.md", templates::CLRD_MD_TEMPLATE)?;
         fs::write(&path, content).context("Failed to create clrd.md")?;
         Ok(())
     }
@@ -81,7 +145,8 @@ impl Mapper {
 
         // Append reference
         let mut new_content = content;
-        new_content.push_str(templates::CLAUDE_MD_REFERENCE);
+        new_content
+            .push_str(&self.load_template("claude_reference.md", templates::CLAUDE_MD_REFERENCE)?);
         fs::write(&path, new_content)?;
 
         Ok(true)
@@ -103,7 +168,8 @@ impl Mapper {
 
         // Append reference
         let mut new_content = content;
-        new_content.push_str(templates::AGENT_MD_REFERENCE);
+        new_content
+            .push_str(&self.load_template("agent_reference.md", templates::AGENT_MD_REFERENCE)?);
         fs::write(&path, new_content)?;
 
         Ok(true)
@@ -125,11 +191,186 @@ impl Mapper {
 
         // Append reference
         let mut new_content = content;
-        new_content.push_str(templates::CURSORRULES_REFERENCE);
+        new_content.push_str(&self.load_template(
+            "cursorrules_reference.txt",
+            templates::CURSORRULES_REFERENCE,
+        )?);
         fs::write(&path, new_content)?;
 
         Ok(true)
     }
+
+    /// Create .github/copilot-instructions.md, or add a reference to an existing one
+    fn add_copilot_instructions(&self) -> Result<Option<bool>> {
+        self.create_or_reference(
+            COPILOT_INSTRUCTIONS_PATH,
+            &self.load_template(
+                "copilot_instructions.md",
+                templates::COPILOT_INSTRUCTIONS_TEMPLATE,
+            )?,
+            &self.load_template(
+                "copilot_reference.md",
+                templates::COPILOT_INSTRUCTIONS_REFERENCE,
+            )?,
+        )
+    }
+
+    /// Create .windsurfrules, or add a reference to an existing one
+    fn add_reference_to_windsurfrules(&self) -> Result<Option<bool>> {
+        self.create_or_reference(
+            WINDSURFRULES_PATH,
+            &self.load_template("windsurfrules.txt", templates::WINDSURFRULES_TEMPLATE)?,
+            &self.load_template(
+                "windsurfrules_reference.txt",
+                templates::WINDSURFRULES_REFERENCE,
+            )?,
+        )
+    }
+
+    /// Create GEMINI.md, or add a reference to an existing one
+    fn add_reference_to_gemini_md(&self) -> Result<Option<bool>> {
+        self.create_or_reference(
+            GEMINI_MD_PATH,
+            &self.load_template("gemini.md", templates::GEMINI_MD_TEMPLATE)?,
+            &self.load_template("gemini_reference.md", templates::GEMINI_MD_REFERENCE)?,
+        )
+    }
+
+    /// Create a context file from `template`, or append `reference` to an existing one
+    ///
+    /// Returns `Some(true)` if created, `Some(false)` if a reference was added,
+    /// and `None` if the file already references clrd.md.
+    fn create_or_reference(
+        &self,
+        relative_path: &str,
+        template: &str,
+        reference: &str,
+    ) -> Result<Option<bool>> {
+        let path = self.root.join(relative_path);
+        if !path.exists() {
+            if let Some(parent) = path.parent() {
+                fs::create_dir_all(parent)
+                    .with_context(|| format!("Failed to create directory for {}", relative_path))?;
+            }
+            fs::write(&path, template)
+                .with_context(|| format!("Failed to create {}", relative_path))?;
+            return Ok(Some(true));
+        }
+
+        let content = fs::read_to_string(&path)?;
+
+        // Check if reference already exists
+        if content.contains(CLRD_REFERENCE_MARKER) {
+            return Ok(None);
+        }
+
+        // Append reference
+        let mut new_content = content;
+        new_content.push_str(reference);
+        fs::write(&path, new_content)?;
+
+        Ok(Some(false))
+    }
+
+    /// Read a template override from the template directory, if any
+    fn load_template(&self, file_name: &str, builtin: &str) -> Result<String> {
+        if let Some(dir) = &self.template_dir {
+            let path = dir.join(file_name);
+            if path.is_file() {
+                return fs::read_to_string(&path)
+                    .with_context(|| format!("Failed to read template {:?}", path));
+            }
+        }
+        Ok(builtin.to_string())
+    }
+
+    /// Write the dead code report into existing context files
+    /// - Replaces the block between the report markers if present
+    /// - Appends a new block otherwise
+    pub fn update(&self, scan_output: &ScanOutput) -> Result<InitReport> {
+        let mut report = InitReport::default();
+        let section = render_report_section(scan_output);
+
+        for target in REPORT_TARGETS {
+            let path = self.root.join(target);
+            if !path.exists() {
+                continue;
+            }
+
+            let content =
+                fs::read_to_string(&path).with_context(|| format!("Failed to read {}", target))?;
+            let new_content = replace_report_section(&content, &section);
+
+            if new_content != content {
+                fs::write(&path, new_content)
+                    .with_context(|| format!("Failed to update {}", target))?;
+                report
+                    .updated
+                    .push(format!("{} (dead code report)", target));
+            }
+        }
+
+        Ok(report)
+    }
+}
+
+/// Render the delimited report block for a scan result
+fn render_report_section(scan_output: &ScanOutput) -> String {
+    let summary = &scan_output.summary;
+    let mut section = String::new();
+
+    section.push_str(REPORT_START_MARKER);
+    section.push_str("\n## Dead Code Report\n\n");
+    section.push_str("Generated by `clrd map`. Do not edit this section by hand.\n\n");
+    section.push_str(&format!(
+        "- Files scanned: {}\n",
+        scan_output.total_files_scanned
+    ));
+    section.push_str(&format!("- Total issues: {}\n", summary.total_issues));
+    section.push_str(&format!(
+        "- High confidence: {}\n",
+        summary.high_confidence_issues
+    ));
+
+    if !scan_output.dead_code.is_empty() {
+        section.push_str("\n| confidence | kind | name | location |\n");
+        section.push_str("|------------|------|------|----------|\n");
+        for item in &scan_output.dead_code {
+            section.push_str(&format!(
+                "| {:.0}% | {} | `{}` | {} |\n",
+                item.confidence * 100.0,
+                item.kind,
+                item.name,
+                item.location()
+            ));
+        }
+    }
+
+    section.push_str(REPORT_END_MARKER);
+    section
+}
+
+/// Replace the report block in `content`, or append it if absent
+fn replace_report_section(content: &str, section: &str) -> String {
+    if let Some(start) = content.find(REPORT_START_MARKER) {
+        if let Some(end_offset) = content[start..].find(REPORT_END_MARKER) {
+            let end = start + end_offset + REPORT_END_MARKER.len();
+            let mut new_content = String::with_capacity(content.len() + section.len());
+            new_content.push_str(&content[..start]);
+            new_content.push_str(section);
+            new_content.push_str(&content[end..]);
+            return new_content;
+        }
+    }
+
+    let mut new_content = content.to_string();
+    if !new_content.ends_with('\n') {
+        new_content.push('\n');
+    }
+    new_content.push('\n');
+    new_content.push_str(section);
+    new_content.push('\n');
+    new_content
 }
 
 /// Report from init operation
@@ -139,6 +380,19 @@ pub struct InitReport {
     pub updated: Vec<String>,
 }
 
+impl InitReport {
+    /// Record the outcome of `Mapper::create_or_reference`
+    fn record(&mut self, relative_path: &str, result: Option<bool>) {
+        match result {
+            Some(true) => self.created.push(relative_path.to_string()),
+            Some(false) => self
+                .updated
+                .push(format!("{} (added clrd.md reference)", relative_path)),
+            None => {}
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -151,10 +405,11 @@ mod tests {
 
         let report = mapper.init(false).unwrap();
 
-        // Only clrd.md should be created
-        assert_eq!(report.created.len(), 1);
+        // clrd.md plus the Copilot, Windsurf and Gemini files should be created
+        assert_eq!(report.created.len(), 4);
         assert!(report.created.contains(&"clrd.md".to_string()));
         assert!(dir.path().join("clrd.md").exists());
+        assert!(dir.path().join(COPILOT_INSTRUCTIONS_PATH).exists());
 
         // No updates since no existing files
         assert!(report.updated.is_empty());
@@ -186,7 +441,11 @@ mod tests {
         let dir = tempdir().unwrap();
 
         // Create claude.md with existing reference
-        fs::write(dir.path().join("claude.md"), "# My Project\n\nSee clrd.md for info").unwrap();
+        fs::write(
+            dir.path().join("claude.md"),
+            "# My Project\n\nSee clrd.md for info",
+        )
+        .unwrap();
 
         let mapper = Mapper::new(dir.path());
         let report = mapper.init(false).unwrap();
@@ -194,4 +453,180 @@ mod tests {
         // claude.md should NOT be in updated (reference already exists)
         assert!(!report.updated.iter().any(|s| s.contains("claude.md")));
     }
+
+    #[test]
+    fn test_mapper_init_copilot_fresh() {
+        let dir = tempdir().unwrap();
+        let mapper = Mapper::new(dir.path());
+
+        let report = mapper.init(false).unwrap();
+
+        assert!(report
+            .created
+            .contains(&COPILOT_INSTRUCTIONS_PATH.to_string()));
+        let content = fs::read_to_string(dir.path().join(COPILOT_INSTRUCTIONS_PATH)).unwrap();
+        assert!(content.contains("clrd.md"));
+    }
+
+    #[test]
+    fn test_mapper_init_copilot_existing() {
+        let dir = tempdir().unwrap();
+        fs::create_dir_all(dir.path().join(".github")).unwrap();
+        fs::write(
+            dir.path().join(COPILOT_INSTRUCTIONS_PATH),
+            "# Team conventions\n",
+        )
+        .unwrap();
+
+        let mapper = Mapper::new(dir.path());
+        let report = mapper.init(false).unwrap();
+
+        assert!(report
+            .updated
+            .iter()
+            .any(|s| s.contains("copilot-instructions.md")));
+        let content = fs::read_to_string(dir.path().join(COPILOT_INSTRUCTIONS_PATH)).unwrap();
+        assert!(content.starts_with("# Team conventions"));
+        assert_eq!(content.matches("## Dead Code Detection").count(), 1);
+
+        // Second run must not add another reference
+        let report = mapper.init(false).unwrap();
+        assert!(!report
+            .updated
+            .iter()
+            .any(|s| s.contains("copilot-instructions.md")));
+    }
+
+    #[test]
+    fn test_mapper_init_copilot_disabled() {
+        let dir = tempdir().unwrap();
+        let output = OutputConfig {
+            copilot_instructions: false,
+            ..OutputConfig::default()
+        };
+
+        let mapper = Mapper::new(dir.path()).with_output_config(output);
+        mapper.init(false).unwrap();
+
+        assert!(!dir.path().join(COPILOT_INSTRUCTIONS_PATH).exists());
+    }
+
+    #[test]
+    fn test_mapper_init_windsurf_and_gemini_fresh() {
+        let dir = tempdir().unwrap();
+        let mapper = Mapper::new(dir.path());
+
+        let report = mapper.init(false).unwrap();
+
+        assert!(report.created.contains(&WINDSURFRULES_PATH.to_string()));
+        assert!(report.created.contains(&GEMINI_MD_PATH.to_string()));
+        assert!(dir.path().join(".windsurfrules").exists());
+        assert!(dir.path().join("GEMINI.md").exists());
+    }
+
+    #[test]
+    fn test_mapper_init_windsurf_and_gemini_existing() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join(".windsurfrules"), "# Existing rules\n").unwrap();
+        fs::write(dir.path().join("GEMINI.md"), "# Gemini\n").unwrap();
+
+        let mapper = Mapper::new(dir.path());
+        mapper.init(false).unwrap();
+        let report = mapper.init(false).unwrap();
+
+        // The second run finds the references and leaves both files alone
+        assert!(!report.updated.iter().any(|s| s.contains(".windsurfrules")));
+        assert!(!report.updated.iter().any(|s| s.contains("GEMINI.md")));
+
+        let windsurf = fs::read_to_string(dir.path().join(".windsurfrules")).unwrap();
+        assert!(windsurf.starts_with("# Existing rules"));
+        assert_eq!(windsurf.matches("# Dead Code Detection").count(), 1);
+
+        let gemini = fs::read_to_string(dir.path().join("GEMINI.md")).unwrap();
+        assert!(gemini.starts_with("# Gemini"));
+        assert_eq!(gemini.matches("## Dead Code Detection").count(), 1);
+    }
+
+    #[test]
+    fn test_mapper_custom_template_dir() {
+        let dir = tempdir().unwrap();
+        let templates = tempdir().unwrap();
+        fs::write(
+            templates.path().join("clrd.md"),
+            "# Team clrd rules\nOnly remove imports.\n",
+        )
+        .unwrap();
+
+        let mapper =
+            Mapper::new(dir.path()).with_template_dir(Some(templates.path().to_path_buf()));
+        mapper.init(false).unwrap();
+
+        let content = fs::read_to_string(dir.path().join("clrd.md")).unwrap();
+        assert_eq!(content, "# Team clrd rules\nOnly remove imports.\n");
+
+        // Files without an override keep the built-in template
+        let gemini = fs::read_to_string(dir.path().join("GEMINI.md")).unwrap();
+        assert_eq!(gemini, GEMINI_MD_TEMPLATE);
+    }
+
+    fn sample_scan_output(issues: u32) -> ScanOutput {
+        let mut summary = crate::types::ScanSummary::new();
+        summary.total_issues = issues;
+
+        ScanOutput {
+            version: "0.0.0".to_string(),
+            root: ".".to_string(),
+            timestamp: "0".to_string(),
+            dead_code: Vec::new(),
+            total_findings: issues,
+            truncated: false,
+            total_files_scanned: 3,
+            total_lines: 0,
+            scan_duration_ms: 0,
+            summary,
+            parse_errors: Vec::new(),
+            ignore_warnings: Vec::new(),
+            unresolved_imports: Vec::new(),
+            skipped_files: Vec::new(),
+            stats: None,
+            content_hashes: Default::default(),
+        }
+    }
+
+    #[test]
+    fn test_mapper_update_inserts_report() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("claude.md"), "# My Project\n").unwrap();
+
+        let mapper = Mapper::new(dir.path());
+        let report = mapper.update(&sample_scan_output(2)).unwrap();
+
+        assert!(report.updated.iter().any(|s| s.contains("claude.md")));
+
+        let content = fs::read_to_string(dir.path().join("claude.md")).unwrap();
+        assert!(content.starts_with("# My Project\n"));
+        assert_eq!(content.matches(REPORT_START_MARKER).count(), 1);
+        assert_eq!(content.matches(REPORT_END_MARKER).count(), 1);
+        assert!(content.contains("Total issues: 2"));
+    }
+
+    #[test]
+    fn test_mapper_update_replaces_existing_report() {
+        let dir = tempdir().unwrap();
+        fs::write(dir.path().join("agent.md"), "# Agents\n\nFooter text\n").unwrap();
+
+        let mapper = Mapper::new(dir.path());
+        mapper.update(&sample_scan_output(5)).unwrap();
+        mapper.update(&sample_scan_output(1)).unwrap();
+
+        let content = fs::read_to_string(dir.path().join("agent.md")).unwrap();
+        assert_eq!(content.matches(REPORT_START_MARKER).count(), 1);
+        assert!(content.contains("Total issues: 1"));
+        assert!(!content.contains("Total issues: 5"));
+        assert!(content.contains("Footer text"));
+
+        // A third identical run leaves the file untouched
+        let report = mapper.update(&sample_scan_output(1)).unwrap();
+        assert!(report.updated.is_empty());
+    }
 }
@@ -94,82 +94,46 @@ pub const CURSORRULES_REFERENCE: &str = r#"
 # See clrd.md for dead code cleanup instructions.
 "#;
 
-/// JSON Schema for LLM communication
-pub const DEAD_CODE_JSON_SCHEMA: &str = r##"{
-  "$schema": "http://json-schema.org/draft-07/schema#",
-  "title": "ClrScanOutput",
-  "description": "Output from clrd dead code scanner",
-  "type": "object",
-  "required": ["version", "root", "timestamp", "dead_code", "summary"],
-  "properties": {
-    "version": {
-      "type": "string",
-      "description": "clrd version that generated this output"
-    },
-    "root": {
-      "type": "string",
-      "description": "Root directory that was scanned"
-    },
-    "timestamp": {
-      "type": "string",
-      "description": "ISO 8601 timestamp of the scan"
-    },
-    "dead_code": {
-      "type": "array",
-      "items": {
-        "$ref": "#/definitions/DeadCodeItem"
-      }
-    },
-    "summary": {
-      "$ref": "#/definitions/Summary"
-    }
-  },
-  "definitions": {
-    "DeadCodeItem": {
-      "type": "object",
-      "required": ["file_path", "span", "code_snippet", "kind", "name", "reason", "confidence"],
-      "properties": {
-        "file_path": { "type": "string" },
-        "relative_path": { "type": "string" },
-        "span": {
-          "type": "object",
-          "properties": {
-            "start": { "type": "integer" },
-            "end": { "type": "integer" }
-          }
-        },
-        "code_snippet": { "type": "string" },
-        "kind": {
-          "type": "string",
-          "enum": ["unused_export", "unreachable_function", "unused_variable", "unused_import", "zombie_file", "unused_type"]
-        },
-        "name": { "type": "string" },
-        "reason": { "type": "string" },
-        "confidence": {
-          "type": "number",
-          "minimum": 0,
-          "maximum": 1
-        },
-        "context": {
-          "type": "object",
-          "properties": {
-            "possibly_dynamic": { "type": "boolean" },
-            "in_test_file": { "type": "boolean" },
-            "public_api": { "type": "boolean" }
-          }
-        }
-      }
-    },
-    "Summary": {
-      "type": "object",
-      "properties": {
-        "unused_exports": { "type": "integer" },
-        "unreachable_functions": { "type": "integer" },
-        "unused_imports": { "type": "integer" },
-        "zombie_files": { "type": "integer" },
-        "total_issues": { "type": "integer" },
-        "high_confidence_issues": { "type": "integer" }
-      }
-    }
-  }
-}"##;
+/// Template for .github/copilot-instructions.md when none exists
+pub const COPILOT_INSTRUCTIONS_TEMPLATE: &str = r#"# Copilot Instructions
+
+## Dead Code Detection
+
+This project uses clrd for dead code detection.
+See [clrd.md](../clrd.md) for dead code cleanup instructions.
+"#;
+
+/// Reference line to add to .github/copilot-instructions.md
+pub const COPILOT_INSTRUCTIONS_REFERENCE: &str = r#"
+## Dead Code Detection
+
+See [clrd.md](../clrd.md) for dead code cleanup instructions.
+"#;
+
+/// Template for .windsurfrules when none exists
+pub const WINDSURFRULES_TEMPLATE: &str = r#"# Windsurf Rules
+
+# Dead Code Detection
+# See clrd.md for dead code cleanup instructions.
+"#;
+
+/// Reference line to add to .windsurfrules
+pub const WINDSURFRULES_REFERENCE: &str = r#"
+# Dead Code Detection
+# See clrd.md for dead code cleanup instructions.
+"#;
+
+/// Template for GEMINI.md when none exists
+pub const GEMINI_MD_TEMPLATE: &str = r#"# Gemini Instructions
+
+## Dead Code Detection
+
+See [clrd.md](./clrd.md) for dead code cleanup instructions.
+"#;
+
+/// Reference line to add to GEMINI.md
+pub const GEMINI_MD_REFERENCE: &str = r#"
+## Dead Code Detection
+
+See [clrd.md](./clrd.md) for dead code cleanup instructions.
+"#;
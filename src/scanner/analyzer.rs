@@ -3,13 +3,17 @@
 //! Extracts exports, imports, and internal references from source files
 //! using the ultra-fast Oxc parser.
 
+use super::sfc;
+use super::source::read_source;
 use crate::types::*;
-use anyhow::{Context, Result};
+use anyhow::Result;
 use oxc_allocator::Allocator;
 use oxc_ast::ast::*;
+use oxc_ast_visit::{walk, Visit};
 use oxc_parser::Parser;
-use oxc_span::SourceType;
-use std::fs;
+use oxc_span::{GetSpan, SourceType};
+use oxc_syntax::scope::ScopeFlags;
+use std::collections::{BTreeSet, HashMap};
 use std::path::Path;
 
 /// Analyzes a single source file and extracts reference information
@@ -18,82 +22,653 @@ pub struct AstAnalyzer;
 impl AstAnalyzer {
     /// Analyze a file and return its reference node
     pub fn analyze_file(path: &Path) -> Result<ReferenceNode> {
-        let source =
-            fs::read_to_string(path).with_context(|| format!("Failed to read file: {:?}", path))?;
+        let source = read_source(path, None)?;
 
-        Self::analyze_source(path, &source)
+        Self::analyze_source(path, source.as_str())
     }
 
     /// Analyze source code directly
     pub fn analyze_source(path: &Path, source: &str) -> Result<ReferenceNode> {
+        // Spans count from the first character an editor shows, after any BOM
+        let source = source.strip_prefix('\u{feff}').unwrap_or(source);
+
+        // Components are parsed with everything but their scripts blanked
+        let markup = source;
+        let blocks = sfc::is_sfc(path).then(|| sfc::script_blocks(source));
+        let masked = blocks
+            .as_ref()
+            .map(|blocks| sfc::mask_to_scripts(source, blocks));
+        let source = masked.as_deref().unwrap_or(source);
+
         let allocator = Allocator::default();
-        let source_type = Self::get_source_type(path);
+        let source_type = match &blocks {
+            Some(blocks) if blocks.iter().any(|block| block.typescript) => SourceType::ts(),
+            Some(_) => SourceType::mjs(),
+            None => Self::get_source_type(path),
+        };
 
         let parser = Parser::new(&allocator, source, source_type);
         let result = parser.parse();
 
-        if !result.errors.is_empty() {
-            tracing::debug!(
-                "Parse errors in {:?}: {:?}",
-                path,
-                result
-                    .errors
-                    .iter()
-                    .map(|e| e.to_string())
-                    .collect::<Vec<_>>()
+        let mut visitor = ReferenceVisitor::new(source);
+
+        let parse_errors: Vec<ParseErrorInfo> = result
+            .errors
+            .iter()
+            .map(|e| {
+                let line = e
+                    .labels
+                    .as_ref()
+                    .and_then(|labels| labels.first())
+                    .map(|label| visitor.line_index.line_col(label.offset()).0)
+                    .unwrap_or(0);
+                ParseErrorInfo {
+                    path: path.to_string_lossy().to_string(),
+                    line,
+                    message: e.to_string(),
+                }
+            })
+            .collect();
+        if !parse_errors.is_empty() {
+            tracing::warn!(
+                "{} parse error(s) in {:?}; results for this file may be incomplete",
+                parse_errors.len(),
+                path
             );
         }
 
-        let mut exports = Vec::new();
-        let mut imports = Vec::new();
-        let internal_refs = Vec::new();
+        visitor.visit_program(&result.program);
 
-        // Process statements directly
-        for stmt in &result.program.body {
-            Self::process_statement(stmt, source, &mut exports, &mut imports);
+        // Attach each export's leading JSDoc block; the nearest one wins
+        let docs: HashMap<u32, &str> = result
+            .program
+            .comments
+            .iter()
+            .filter(|comment| comment.is_leading() && comment.is_jsdoc())
+            .map(|comment| (comment.attached_to, comment.span.source_text(source)))
+            .collect();
+        for export in &mut visitor.exports {
+            export.doc_comment = docs.get(&export.span.byte_start).map(|doc| doc.to_string());
         }
 
+        let todo_lines = result
+            .program
+            .comments
+            .iter()
+            .filter(|comment| is_todo_comment(comment.span.source_text(source)))
+            .map(|comment| visitor.line_index.line_col(comment.span.start as usize).0)
+            .collect();
+
+        if let Some(blocks) = &blocks {
+            // The template reads script bindings and imported components
+            visitor
+                .reads
+                .extend(sfc::markup_identifiers(markup, blocks));
+            // `export let` in a Svelte instance script declares a prop
+            if path.extension().is_some_and(|ext| ext == "svelte") {
+                visitor.exports.retain(|export| {
+                    let offset = export.span.byte_start as usize;
+                    blocks
+                        .iter()
+                        .any(|block| block.module_context && block.content.contains(&offset))
+                });
+            }
+        }
+
+        // Imports whose every reference resolves to a local redeclaration
+        let shadowed_refs = visitor
+            .imports
+            .iter()
+            .map(|import| import.alias.as_ref().unwrap_or(&import.name))
+            .filter(|name| visitor.shadowed_reads.contains(*name) && !visitor.reads.contains(*name))
+            .cloned()
+            .collect();
+
         Ok(ReferenceNode {
             file_path: path.to_path_buf(),
-            exports,
-            imports,
-            internal_refs,
+            exports: visitor.exports,
+            imports: visitor.imports,
+            locals: visitor.locals,
+            type_refs: visitor
+                .type_reads
+                .difference(&visitor.reads)
+                .cloned()
+                .collect(),
+            internal_refs: visitor.reads.union(&visitor.type_reads).cloned().collect(),
+            write_refs: visitor.writes.into_iter().collect(),
+            member_refs: visitor.members.into_iter().collect(),
+            computed_member_refs: visitor.computed_members.into_iter().collect(),
+            const_objects: visitor.const_objects,
+            enums: visitor.enums,
+            write_only_fields: visitor.write_only_fields,
+            todo_lines,
+            shadowed_refs,
+            parse_errors,
+            line_count: source.lines().count() as u32,
         })
     }
 
-    fn process_statement(
-        stmt: &Statement,
-        source: &str,
-        exports: &mut Vec<ExportedSymbol>,
-        imports: &mut Vec<ImportedSymbol>,
-    ) {
-        match stmt {
-            Statement::ImportDeclaration(decl) => {
-                Self::process_import(decl, source, imports);
+    /// Rewrite the imports of `names` (local bindings) to type-only imports:
+    /// a declaration importing nothing else becomes `import type { .. }`,
+    /// otherwise each named specifier gets an inline `type` modifier
+    pub fn make_imports_type_only(path: &Path, source: &str, names: &[&str]) -> String {
+        let (bom, body) = match source.strip_prefix('\u{feff}') {
+            Some(body) => ("\u{feff}", body),
+            None => ("", source),
+        };
+
+        let allocator = Allocator::default();
+        let result = Parser::new(&allocator, body, Self::get_source_type(path)).parse();
+
+        let mut insertions: Vec<(usize, &str)> = Vec::new();
+        for stmt in &result.program.body {
+            let Statement::ImportDeclaration(decl) = stmt else {
+                continue;
+            };
+            let Some(specifiers) = decl
+                .specifiers
+                .as_ref()
+                .filter(|_| decl.import_kind.is_value())
+            else {
+                continue;
+            };
+
+            let is_flagged = |spec: &ImportDeclarationSpecifier| match spec {
+                ImportDeclarationSpecifier::ImportSpecifier(s) => {
+                    s.import_kind.is_value() && names.contains(&s.local.name.as_str())
+                }
+                ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => {
+                    names.contains(&s.local.name.as_str())
+                }
+                ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => {
+                    names.contains(&s.local.name.as_str())
+                }
+            };
+            if !specifiers.iter().any(is_flagged) {
+                continue;
+            }
+
+            // `import type Foo, { Bar }` is not valid TypeScript
+            let has_default = specifiers
+                .iter()
+                .any(|spec| matches!(spec, ImportDeclarationSpecifier::ImportDefaultSpecifier(_)));
+            if specifiers.iter().all(is_flagged) && !(has_default && specifiers.len() > 1) {
+                insertions.push((decl.span.start as usize + "import".len(), " type"));
+                continue;
+            }
+            // A default or namespace import shared with value imports stays as is
+            for spec in specifiers.iter().filter(|spec| is_flagged(spec)) {
+                if let ImportDeclarationSpecifier::ImportSpecifier(s) = spec {
+                    insertions.push((s.span.start as usize, "type "));
+                }
+            }
+        }
+
+        let mut rewritten = body.to_string();
+        insertions.sort_by_key(|(offset, _)| std::cmp::Reverse(*offset));
+        for (offset, text) in insertions {
+            rewritten.insert_str(offset, text);
+        }
+        format!("{}{}", bom, rewritten)
+    }
+
+    fn get_source_type(path: &Path) -> SourceType {
+        if is_declaration_file(path) {
+            return SourceType::d_ts();
+        }
+
+        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+
+        match ext {
+            "ts" | "mts" => SourceType::ts(),
+            "tsx" => SourceType::tsx(),
+            "cts" => SourceType::cjs().with_typescript(true),
+            "jsx" => SourceType::jsx(),
+            "mjs" => SourceType::mjs(),
+            "cjs" => SourceType::cjs(),
+            _ => SourceType::mjs(),
+        }
+    }
+
+    fn get_binding_name(pattern: &BindingPattern) -> Option<String> {
+        match &pattern.kind {
+            BindingPatternKind::BindingIdentifier(id) => Some(id.name.to_string()),
+            _ => None,
+        }
+    }
+}
+
+/// Walks a parsed program collecting exports, imports and identifier references
+struct ReferenceVisitor {
+    line_index: LineIndex,
+    exports: Vec<ExportedSymbol>,
+    imports: Vec<ImportedSymbol>,
+    locals: Vec<LocalSymbol>,
+    /// Identifiers whose value is read somewhere in the file
+    reads: BTreeSet<String>,
+    /// Identifiers read where an enclosing scope redeclares them
+    shadowed_reads: BTreeSet<String>,
+    /// Identifiers referenced from type annotations and declarations
+    type_reads: BTreeSet<String>,
+    /// Nesting depth of type positions being walked
+    type_depth: usize,
+    /// Identifiers that appear as a plain assignment or update target
+    writes: BTreeSet<String>,
+    /// `object.property` accesses where the object is a plain identifier
+    members: BTreeSet<(String, String)>,
+    /// Plain identifiers indexed with a key that is not a literal
+    computed_members: BTreeSet<String>,
+    /// Variables initialized with `{ ... } as const`
+    const_objects: Vec<String>,
+    enums: Vec<EnumSymbol>,
+    /// Field accesses of the classes being walked, innermost last
+    classes: Vec<ClassScope>,
+    /// Names bound by the function and block scopes being walked, innermost
+    /// last; references to them don't reach module-level bindings
+    scopes: Vec<BTreeSet<String>>,
+    write_only_fields: Vec<ClassField>,
+}
+
+/// `this.field` reads and writes seen inside one class body
+#[derive(Default)]
+struct ClassScope {
+    name: Option<String>,
+    /// First write of each field, in source order
+    writes: Vec<(String, CodeSpan)>,
+    reads: BTreeSet<String>,
+    /// Fields with a setter or decorator; assigning them has effects
+    special: BTreeSet<String>,
+    /// `this` was used other than for a field access, or the class is
+    /// decorated, so fields may be read in ways that can't be seen
+    escapes: bool,
+}
+
+impl ClassScope {
+    fn write(&mut self, field: String, span: CodeSpan) {
+        if !self.writes.iter().any(|(name, _)| *name == field) {
+            self.writes.push((field, span));
+        }
+    }
+}
+
+impl<'a> Visit<'a> for ReferenceVisitor {
+    fn visit_program(&mut self, program: &Program<'a>) {
+        for stmt in &program.body {
+            self.record_local(stmt);
+        }
+        walk::walk_program(self, program);
+    }
+
+    fn visit_import_declaration(&mut self, decl: &ImportDeclaration<'a>) {
+        self.visit_import(decl);
+    }
+
+    fn visit_export_named_declaration(&mut self, decl: &ExportNamedDeclaration<'a>) {
+        self.visit_export_named(decl);
+        walk::walk_export_named_declaration(self, decl);
+    }
+
+    fn visit_export_default_declaration(&mut self, decl: &ExportDefaultDeclaration<'a>) {
+        self.visit_export_default(decl);
+        walk::walk_export_default_declaration(self, decl);
+    }
+
+    fn visit_export_all_declaration(&mut self, decl: &ExportAllDeclaration<'a>) {
+        self.visit_export_all(decl);
+    }
+
+    fn visit_static_member_expression(&mut self, expr: &StaticMemberExpression<'a>) {
+        if let Expression::ThisExpression(_) = &expr.object {
+            if let Some(class) = self.classes.last_mut() {
+                class.reads.insert(expr.property.name.to_string());
+                return;
+            }
+        }
+        if let Expression::Identifier(object) = &expr.object {
+            self.members
+                .insert((object.name.to_string(), expr.property.name.to_string()));
+        }
+        walk::walk_static_member_expression(self, expr);
+    }
+
+    fn visit_private_field_expression(&mut self, expr: &PrivateFieldExpression<'a>) {
+        if let Expression::ThisExpression(_) = &expr.object {
+            if let Some(class) = self.classes.last_mut() {
+                class.reads.insert(format!("#{}", expr.field.name));
+                return;
+            }
+        }
+        walk::walk_private_field_expression(self, expr);
+    }
+
+    fn visit_this_expression(&mut self, _: &ThisExpression) {
+        if let Some(class) = self.classes.last_mut() {
+            class.escapes = true;
+        }
+    }
+
+    fn visit_class(&mut self, class: &Class<'a>) {
+        self.classes.push(ClassScope {
+            name: class.id.as_ref().map(|id| id.name.to_string()),
+            escapes: !class.decorators.is_empty(),
+            ..Default::default()
+        });
+        walk::walk_class(self, class);
+        let Some(scope) = self.classes.pop() else {
+            return;
+        };
+
+        let Some(class_name) = scope.name.filter(|_| !scope.escapes) else {
+            return;
+        };
+        for (field, span) in scope.writes {
+            if !scope.reads.contains(&field) && !scope.special.contains(&field) {
+                self.write_only_fields.push(ClassField {
+                    class: class_name.clone(),
+                    name: field,
+                    span,
+                });
+            }
+        }
+    }
+
+    fn visit_property_definition(&mut self, def: &PropertyDefinition<'a>) {
+        if let (Some(field), false) = (Self::field_name(&def.key), def.r#static) {
+            let span = self.span_to_code_span(def.span);
+            if let Some(class) = self.classes.last_mut() {
+                if !def.decorators.is_empty() {
+                    class.special.insert(field.clone());
+                }
+                class.write(field, span);
+            }
+        }
+        walk::walk_property_definition(self, def);
+    }
+
+    fn visit_method_definition(&mut self, def: &MethodDefinition<'a>) {
+        if let Some(field) = Self::field_name(&def.key) {
+            if let Some(class) = self.classes.last_mut() {
+                // Reading a getter's field or assigning a setter's runs code
+                class.special.insert(field);
+            }
+        }
+        walk::walk_method_definition(self, def);
+    }
+
+    fn visit_computed_member_expression(&mut self, expr: &ComputedMemberExpression<'a>) {
+        if let Expression::Identifier(object) = &expr.object {
+            let object = object.name.to_string();
+            // A literal key is as good as a static property access
+            match &expr.expression {
+                Expression::StringLiteral(key) => {
+                    self.members.insert((object, key.value.to_string()));
+                }
+                _ => {
+                    self.computed_members.insert(object);
+                }
+            }
+        }
+        walk::walk_computed_member_expression(self, expr);
+    }
+
+    fn visit_variable_declarator(&mut self, declarator: &VariableDeclarator<'a>) {
+        if let (Some(name), Some(Expression::TSAsExpression(init))) = (
+            AstAnalyzer::get_binding_name(&declarator.id),
+            &declarator.init,
+        ) {
+            let is_const = matches!(
+                &init.type_annotation,
+                TSType::TSTypeReference(ty)
+                    if matches!(&ty.type_name, TSTypeName::IdentifierReference(id) if id.name == "const")
+            );
+            if is_const && matches!(init.expression, Expression::ObjectExpression(_)) {
+                self.const_objects.push(name);
+            }
+        }
+        // `const { a, b: c } = object` reads `object.a` and `object.b`
+        if let (BindingPatternKind::ObjectPattern(pattern), Some(Expression::Identifier(object))) =
+            (&declarator.id.kind, &declarator.init)
+        {
+            for property in &pattern.properties {
+                if let Some(key) = property.key.static_name() {
+                    self.members
+                        .insert((object.name.to_string(), key.to_string()));
+                }
+            }
+        }
+        walk::walk_variable_declarator(self, declarator);
+    }
+
+    fn visit_ts_enum_declaration(&mut self, decl: &TSEnumDeclaration<'a>) {
+        let members = decl
+            .members
+            .iter()
+            .map(|member| {
+                (
+                    member.id.static_name().to_string(),
+                    self.span_to_code_span(member.span),
+                )
+            })
+            .collect();
+        self.enums.push(EnumSymbol {
+            name: decl.id.name.to_string(),
+            members,
+        });
+        walk::walk_ts_enum_declaration(self, decl);
+    }
+
+    fn visit_identifier_reference(&mut self, ident: &IdentifierReference<'a>) {
+        if self.type_depth > 0 {
+            self.type_reads.insert(ident.name.to_string());
+        } else if self.is_shadowed(&ident.name) {
+            self.shadowed_reads.insert(ident.name.to_string());
+        } else {
+            self.reads.insert(ident.name.to_string());
+        }
+    }
+
+    // Parameters and the body's own declarations shadow module-level
+    // bindings of the same name, imports included
+    fn visit_function(&mut self, func: &Function<'a>, flags: ScopeFlags) {
+        let mut scope = Self::parameter_names(&func.params);
+        if let Some(body) = &func.body {
+            scope.extend(Self::declared_names(&body.statements));
+        }
+        self.scopes.push(scope);
+        walk::walk_function(self, func, flags);
+        self.scopes.pop();
+    }
+
+    fn visit_arrow_function_expression(&mut self, arrow: &ArrowFunctionExpression<'a>) {
+        let mut scope = Self::parameter_names(&arrow.params);
+        scope.extend(Self::declared_names(&arrow.body.statements));
+        self.scopes.push(scope);
+        walk::walk_arrow_function_expression(self, arrow);
+        self.scopes.pop();
+    }
+
+    fn visit_block_statement(&mut self, block: &BlockStatement<'a>) {
+        self.scopes.push(Self::declared_names(&block.body));
+        walk::walk_block_statement(self, block);
+        self.scopes.pop();
+    }
+
+    fn visit_ts_type(&mut self, ty: &TSType<'a>) {
+        self.type_depth += 1;
+        walk::walk_ts_type(self, ty);
+        self.type_depth -= 1;
+    }
+
+    // `implements Foo` and `interface X extends Foo` only name types
+    fn visit_ts_class_implements(&mut self, it: &TSClassImplements<'a>) {
+        self.type_depth += 1;
+        walk::walk_ts_class_implements(self, it);
+        self.type_depth -= 1;
+    }
+
+    fn visit_ts_interface_heritage(&mut self, it: &TSInterfaceHeritage<'a>) {
+        self.type_depth += 1;
+        walk::walk_ts_interface_heritage(self, it);
+        self.type_depth -= 1;
+    }
+
+    // `x = ...`, `x += ...` and `x++` write to `x` without handing its value
+    // to anyone else, so they are not counted as reads.
+    fn visit_simple_assignment_target(&mut self, target: &SimpleAssignmentTarget<'a>) {
+        match target {
+            SimpleAssignmentTarget::AssignmentTargetIdentifier(ident) => {
+                if !self.is_shadowed(&ident.name) {
+                    self.writes.insert(ident.name.to_string());
+                }
+            }
+            SimpleAssignmentTarget::StaticMemberExpression(member)
+                if matches!(member.object, Expression::ThisExpression(_))
+                    && !self.classes.is_empty() =>
+            {
+                let span = self.span_to_code_span(member.span);
+                if let Some(class) = self.classes.last_mut() {
+                    class.write(member.property.name.to_string(), span);
+                }
             }
-            Statement::ExportNamedDeclaration(decl) => {
-                Self::process_export_named(decl, source, exports);
+            SimpleAssignmentTarget::PrivateFieldExpression(member)
+                if matches!(member.object, Expression::ThisExpression(_))
+                    && !self.classes.is_empty() =>
+            {
+                let span = self.span_to_code_span(member.span);
+                if let Some(class) = self.classes.last_mut() {
+                    class.write(format!("#{}", member.field.name), span);
+                }
+            }
+            _ => walk::walk_simple_assignment_target(self, target),
+        }
+    }
+}
+
+impl ReferenceVisitor {
+    fn new(source: &str) -> Self {
+        Self {
+            line_index: LineIndex::new(source),
+            exports: Vec::new(),
+            imports: Vec::new(),
+            locals: Vec::new(),
+            reads: BTreeSet::new(),
+            shadowed_reads: BTreeSet::new(),
+            type_reads: BTreeSet::new(),
+            type_depth: 0,
+            writes: BTreeSet::new(),
+            members: BTreeSet::new(),
+            computed_members: BTreeSet::new(),
+            const_objects: Vec::new(),
+            enums: Vec::new(),
+            classes: Vec::new(),
+            scopes: Vec::new(),
+            write_only_fields: Vec::new(),
+        }
+    }
+
+    /// Whether a name resolves to a binding of an enclosing non-module scope
+    fn is_shadowed(&self, name: &str) -> bool {
+        self.scopes.iter().any(|scope| scope.contains(name))
+    }
+
+    /// Names declared directly by these statements
+    fn declared_names(stmts: &[Statement]) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        for stmt in stmts {
+            match stmt {
+                Statement::VariableDeclaration(var_decl) => {
+                    for declarator in &var_decl.declarations {
+                        names.extend(
+                            declarator
+                                .id
+                                .get_binding_identifiers()
+                                .iter()
+                                .map(|id| id.name.to_string()),
+                        );
+                    }
+                }
+                Statement::FunctionDeclaration(func) => {
+                    names.extend(func.id.as_ref().map(|id| id.name.to_string()));
+                }
+                Statement::ClassDeclaration(class) => {
+                    names.extend(class.id.as_ref().map(|id| id.name.to_string()));
+                }
+                _ => {}
+            }
+        }
+        names
+    }
+
+    /// Names bound by a parameter list
+    fn parameter_names(params: &FormalParameters) -> BTreeSet<String> {
+        let rest = params.rest.iter().map(|rest| &rest.argument);
+        params
+            .items
+            .iter()
+            .map(|param| &param.pattern)
+            .chain(rest)
+            .flat_map(|pattern| pattern.get_binding_identifiers())
+            .map(|id| id.name.to_string())
+            .collect()
+    }
+
+    /// Name of a non-computed class member, with `#` for private ones
+    fn field_name(key: &PropertyKey) -> Option<String> {
+        match key {
+            PropertyKey::StaticIdentifier(id) => Some(id.name.to_string()),
+            PropertyKey::PrivateIdentifier(id) => Some(format!("#{}", id.name)),
+            _ => None,
+        }
+    }
+
+    /// Record a non-exported top-level declaration
+    fn record_local(&mut self, stmt: &Statement) {
+        let span = self.span_to_code_span(stmt.span());
+        match stmt {
+            Statement::VariableDeclaration(var_decl) => {
+                let kind = match var_decl.kind {
+                    VariableDeclarationKind::Const => SymbolKind::Const,
+                    VariableDeclarationKind::Let => SymbolKind::Let,
+                    _ => SymbolKind::Variable,
+                };
+                for declarator in &var_decl.declarations {
+                    if let Some(name) = AstAnalyzer::get_binding_name(&declarator.id) {
+                        self.locals.push(LocalSymbol { name, kind, span });
+                    }
+                }
             }
-            Statement::ExportDefaultDeclaration(decl) => {
-                Self::process_export_default(decl, source, exports);
+            Statement::FunctionDeclaration(func) => {
+                if let Some(id) = &func.id {
+                    self.locals.push(LocalSymbol {
+                        name: id.name.to_string(),
+                        kind: SymbolKind::Function,
+                        span,
+                    });
+                }
             }
-            Statement::ExportAllDeclaration(decl) => {
-                Self::process_export_all(decl, source, exports);
+            Statement::ClassDeclaration(class) => {
+                if let Some(id) = &class.id {
+                    self.locals.push(LocalSymbol {
+                        name: id.name.to_string(),
+                        kind: SymbolKind::Class,
+                        span,
+                    });
+                }
             }
             _ => {}
         }
     }
 
-    fn process_import(decl: &ImportDeclaration, source: &str, imports: &mut Vec<ImportedSymbol>) {
+    fn visit_import(&mut self, decl: &ImportDeclaration) {
         let import_source = decl.source.value.to_string();
         let is_type_only = decl.import_kind.is_type();
-        let span = Self::span_to_code_span(decl.span, source);
+        let span = self.span_to_code_span(decl.span);
 
         if let Some(specifiers) = &decl.specifiers {
             for spec in specifiers {
                 match spec {
                     ImportDeclarationSpecifier::ImportSpecifier(s) => {
-                        imports.push(ImportedSymbol {
+                        self.imports.push(ImportedSymbol {
                             name: s.imported.name().to_string(),
                             alias: if s.local.name != s.imported.name() {
                                 Some(s.local.name.to_string())
@@ -106,7 +681,7 @@ impl AstAnalyzer {
                         });
                     }
                     ImportDeclarationSpecifier::ImportDefaultSpecifier(s) => {
-                        imports.push(ImportedSymbol {
+                        self.imports.push(ImportedSymbol {
                             name: "default".to_string(),
                             alias: Some(s.local.name.to_string()),
                             source: import_source.clone(),
@@ -115,7 +690,7 @@ impl AstAnalyzer {
                         });
                     }
                     ImportDeclarationSpecifier::ImportNamespaceSpecifier(s) => {
-                        imports.push(ImportedSymbol {
+                        self.imports.push(ImportedSymbol {
                             name: "*".to_string(),
                             alias: Some(s.local.name.to_string()),
                             source: import_source.clone(),
@@ -128,22 +703,23 @@ impl AstAnalyzer {
         }
     }
 
-    fn process_export_named(
-        decl: &ExportNamedDeclaration,
-        source: &str,
-        exports: &mut Vec<ExportedSymbol>,
-    ) {
-        let span = Self::span_to_code_span(decl.span, source);
-        let is_reexport = decl.source.is_some();
+    fn visit_export_named(&mut self, decl: &ExportNamedDeclaration) {
+        let span = self.span_to_code_span(decl.span);
+        let source = decl.source.as_ref().map(|s| s.value.to_string());
+        let is_reexport = source.is_some();
 
         // Handle export specifiers: export { foo, bar }
         for spec in &decl.specifiers {
-            exports.push(ExportedSymbol {
+            self.exports.push(ExportedSymbol {
                 name: spec.exported.name().to_string(),
                 kind: SymbolKind::Variable,
                 span,
                 is_default: false,
                 is_reexport,
+                source: source.clone(),
+                local_name: is_reexport.then(|| spec.local.name().to_string()),
+                doc_comment: None,
+                is_async: false,
             });
         }
 
@@ -152,69 +728,93 @@ impl AstAnalyzer {
             match declaration {
                 Declaration::VariableDeclaration(var_decl) => {
                     for declarator in &var_decl.declarations {
-                        if let Some(name) = Self::get_binding_name(&declarator.id) {
+                        if let Some(name) = AstAnalyzer::get_binding_name(&declarator.id) {
                             let kind = match var_decl.kind {
                                 VariableDeclarationKind::Const => SymbolKind::Const,
                                 VariableDeclarationKind::Let => SymbolKind::Let,
                                 _ => SymbolKind::Variable,
                             };
-                            exports.push(ExportedSymbol {
+                            self.exports.push(ExportedSymbol {
                                 name,
                                 kind,
                                 span,
                                 is_default: false,
                                 is_reexport: false,
+                                source: None,
+                                local_name: None,
+                                doc_comment: None,
+                                is_async: declarator.init.as_ref().is_some_and(is_async_function),
                             });
                         }
                     }
                 }
                 Declaration::FunctionDeclaration(func) => {
                     if let Some(id) = &func.id {
-                        exports.push(ExportedSymbol {
+                        self.exports.push(ExportedSymbol {
                             name: id.name.to_string(),
                             kind: SymbolKind::Function,
                             span,
                             is_default: false,
                             is_reexport: false,
+                            source: None,
+                            local_name: None,
+                            doc_comment: None,
+                            is_async: func.r#async,
                         });
                     }
                 }
                 Declaration::ClassDeclaration(class) => {
                     if let Some(id) = &class.id {
-                        exports.push(ExportedSymbol {
+                        self.exports.push(ExportedSymbol {
                             name: id.name.to_string(),
                             kind: SymbolKind::Class,
                             span,
                             is_default: false,
                             is_reexport: false,
+                            source: None,
+                            local_name: None,
+                            doc_comment: None,
+                            is_async: false,
                         });
                     }
                 }
                 Declaration::TSTypeAliasDeclaration(type_alias) => {
-                    exports.push(ExportedSymbol {
+                    self.exports.push(ExportedSymbol {
                         name: type_alias.id.name.to_string(),
                         kind: SymbolKind::Type,
                         span,
                         is_default: false,
                         is_reexport: false,
+                        source: None,
+                        local_name: None,
+                        doc_comment: None,
+                        is_async: false,
                     });
                 }
                 Declaration::TSInterfaceDeclaration(interface) => {
-                    exports.push(ExportedSymbol {
+                    self.exports.push(ExportedSymbol {
                         name: interface.id.name.to_string(),
                         kind: SymbolKind::Interface,
                         span,
                         is_default: false,
                         is_reexport: false,
+                        source: None,
+                        local_name: None,
+                        doc_comment: None,
+                        is_async: false,
                     });
                 }
                 Declaration::TSEnumDeclaration(enum_decl) => {
-                    exports.push(ExportedSymbol {
+                    self.exports.push(ExportedSymbol {
                         name: enum_decl.id.name.to_string(),
                         kind: SymbolKind::Enum,
                         span,
                         is_default: false,
                         is_reexport: false,
+                        source: None,
+                        local_name: None,
+                        doc_comment: None,
+                        is_async: false,
                     });
                 }
                 _ => {}
@@ -222,12 +822,8 @@ impl AstAnalyzer {
         }
     }
 
-    fn process_export_default(
-        decl: &ExportDefaultDeclaration,
-        source: &str,
-        exports: &mut Vec<ExportedSymbol>,
-    ) {
-        let span = Self::span_to_code_span(decl.span, source);
+    fn visit_export_default(&mut self, decl: &ExportDefaultDeclaration) {
+        let span = self.span_to_code_span(decl.span);
 
         let (name, kind) = match &decl.declaration {
             ExportDefaultDeclarationKind::FunctionDeclaration(func) => (
@@ -247,79 +843,143 @@ impl AstAnalyzer {
             ),
             _ => ("default".to_string(), SymbolKind::Variable),
         };
+        let is_async = match &decl.declaration {
+            ExportDefaultDeclarationKind::FunctionDeclaration(func) => func.r#async,
+            declaration => declaration.as_expression().is_some_and(is_async_function),
+        };
+        // `export default config` names the binding it exports
+        let local_name = match &decl.declaration {
+            ExportDefaultDeclarationKind::Identifier(ident) => Some(ident.name.to_string()),
+            _ => None,
+        };
 
-        exports.push(ExportedSymbol {
+        self.exports.push(ExportedSymbol {
             name,
             kind,
             span,
             is_default: true,
             is_reexport: false,
+            source: None,
+            local_name,
+            doc_comment: None,
+            is_async,
         });
     }
 
-    fn process_export_all(
-        decl: &ExportAllDeclaration,
-        source: &str,
-        exports: &mut Vec<ExportedSymbol>,
-    ) {
-        let span = Self::span_to_code_span(decl.span, source);
+    fn visit_export_all(&mut self, decl: &ExportAllDeclaration) {
+        let span = self.span_to_code_span(decl.span);
+
+        // `export * as ns from './mod'` exposes the module as a single binding
+        let (name, kind) = match &decl.exported {
+            Some(exported) => (exported.name().to_string(), SymbolKind::Namespace),
+            None => ("*".to_string(), SymbolKind::Variable),
+        };
 
-        exports.push(ExportedSymbol {
-            name: "*".to_string(),
-            kind: SymbolKind::Variable,
+        self.exports.push(ExportedSymbol {
+            name,
+            kind,
             span,
             is_default: false,
             is_reexport: true,
+            source: Some(decl.source.value.to_string()),
+            local_name: None,
+            doc_comment: None,
+            is_async: false,
         });
     }
 
-    fn span_to_code_span(span: oxc_span::Span, source: &str) -> CodeSpan {
-        let start_line = source[..span.start as usize]
-            .chars()
-            .filter(|&c| c == '\n')
-            .count() as u32
-            + 1;
-        let end_line = source[..span.end as usize]
-            .chars()
-            .filter(|&c| c == '\n')
-            .count() as u32
-            + 1;
+    fn span_to_code_span(&self, span: oxc_span::Span) -> CodeSpan {
+        let (start, col_start) = self.line_index.line_col(span.start as usize);
+        let (end, col_end) = self.line_index.line_col(span.end as usize);
 
         CodeSpan {
-            start: start_line,
-            end: end_line,
-            col_start: 0,
-            col_end: 0,
+            start,
+            end,
+            col_start,
+            col_end,
+            byte_start: span.start,
+            byte_end: span.end,
         }
     }
+}
 
-    fn get_source_type(path: &Path) -> SourceType {
-        let ext = path.extension().and_then(|e| e.to_str()).unwrap_or("");
+/// Byte offsets of every newline in a file, for O(log n) line lookups
+struct LineIndex {
+    newlines: Vec<usize>,
+}
 
-        match ext {
-            "ts" | "mts" => SourceType::ts(),
-            "tsx" => SourceType::tsx(),
-            "cts" => SourceType::cjs().with_typescript(true),
-            "jsx" => SourceType::jsx(),
-            "mjs" => SourceType::mjs(),
-            "cjs" => SourceType::cjs(),
-            _ => SourceType::mjs(),
-        }
+impl LineIndex {
+    fn new(source: &str) -> Self {
+        let newlines = source
+            .bytes()
+            .enumerate()
+            .filter(|&(_, b)| b == b'\n')
+            .map(|(i, _)| i)
+            .collect();
+        Self { newlines }
     }
 
-    fn get_binding_name(pattern: &BindingPattern) -> Option<String> {
-        match &pattern.kind {
-            BindingPatternKind::BindingIdentifier(id) => Some(id.name.to_string()),
-            _ => None,
-        }
+    /// Convert a byte offset to a 1-indexed line and 0-indexed column
+    fn line_col(&self, offset: usize) -> (u32, u32) {
+        let line = self.newlines.partition_point(|&nl| nl < offset);
+        let line_start = if line == 0 {
+            0
+        } else {
+            self.newlines[line - 1] + 1
+        };
+        ((line + 1) as u32, (offset - line_start) as u32)
     }
 }
 
+/// Comment tags marking work in progress
+const TODO_TAGS: [&str; 3] = ["TODO", "FIXME", "HACK"];
+
+/// Whether a comment carries a `TODO`, `FIXME` or `HACK` tag
+fn is_todo_comment(text: &str) -> bool {
+    text.split(|c: char| !c.is_ascii_alphanumeric())
+        .any(|word| TODO_TAGS.contains(&word))
+}
+
+/// Whether an expression is an `async` arrow function or function expression
+fn is_async_function(expr: &Expression) -> bool {
+    match expr {
+        Expression::ArrowFunctionExpression(arrow) => arrow.r#async,
+        Expression::FunctionExpression(func) => func.r#async,
+        Expression::ParenthesizedExpression(paren) => is_async_function(&paren.expression),
+        _ => false,
+    }
+}
+
+/// Check if a file is a TypeScript declaration file (`.d.ts`, `.d.mts`, `.d.cts`)
+pub fn is_declaration_file(path: &Path) -> bool {
+    let file_name = path.file_name().and_then(|n| n.to_str()).unwrap_or("");
+    file_name.ends_with(".d.ts") || file_name.ends_with(".d.mts") || file_name.ends_with(".d.cts")
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use std::path::PathBuf;
 
+    #[test]
+    fn test_bom_is_stripped_before_parsing() {
+        let source = "export const foo = 1;\nexport function bar() {}\n";
+        let with_bom = format!("\u{feff}{}", source);
+
+        let plain = AstAnalyzer::analyze_source(&PathBuf::from("test.ts"), source).unwrap();
+        let node = AstAnalyzer::analyze_source(&PathBuf::from("test.ts"), &with_bom).unwrap();
+
+        assert_eq!(node.line_count, 2);
+        assert!(node.parse_errors.is_empty());
+        for (export, expected) in node.exports.iter().zip(&plain.exports) {
+            assert_eq!(export.name, expected.name);
+            assert_eq!(export.span, expected.span);
+        }
+        assert_eq!(node.exports[0].span.col_start, 0);
+        assert_eq!(node.exports[0].span.byte_start, 0);
+        assert_eq!(node.exports[1].span.start, 2);
+    }
+
     #[test]
     fn test_analyze_simple_export() {
         let source = r#"
@@ -337,6 +997,19 @@ mod tests {
         assert!(result.exports.iter().any(|e| e.name == "Baz"));
     }
 
+    #[test]
+    fn test_span_byte_offsets() {
+        let source = "import { a } from './a';\n\nexport function helper() {\n  return a;\n}\n";
+        let result = AstAnalyzer::analyze_source(Path::new("test.ts"), source).unwrap();
+
+        let span = &result.exports[0].span;
+        let text = &source[span.byte_start as usize..span.byte_end as usize];
+        assert!(text.starts_with("export function helper()"));
+        assert!(text.ends_with('}'));
+        assert_eq!(span.byte_start, 26);
+        assert_eq!(span.start, 3);
+    }
+
     #[test]
     fn test_analyze_imports() {
         let source = r#"
@@ -350,4 +1023,145 @@ mod tests {
 
         assert_eq!(result.imports.len(), 4);
     }
+
+    #[test]
+    fn test_declaration_file_source_type() {
+        assert!(is_declaration_file(Path::new("types/global.d.ts")));
+        assert!(!is_declaration_file(Path::new("src/d.ts")));
+
+        let source_type = AstAnalyzer::get_source_type(Path::new("global.d.ts"));
+        assert!(source_type.is_typescript_definition());
+
+        let source = "export declare function helper(): void;\nexport interface Options {}";
+        let result = AstAnalyzer::analyze_source(Path::new("global.d.ts"), source).unwrap();
+        assert_eq!(result.exports.len(), 2);
+    }
+
+    #[test]
+    fn test_line_index_matches_newline_count() {
+        let source = "a\nbb\n\nccc";
+        let index = LineIndex::new(source);
+
+        for offset in 0..=source.len() {
+            let expected = source[..offset].matches('\n').count() as u32 + 1;
+            assert_eq!(index.line_col(offset).0, expected);
+        }
+        assert_eq!(index.line_col(0), (1, 0));
+        assert_eq!(index.line_col(4), (2, 2));
+        assert_eq!(index.line_col(8), (4, 2));
+    }
+
+    #[test]
+    fn test_analyze_large_file_spans() {
+        let mut source = String::new();
+        for i in 0..5000 {
+            source.push_str(&format!("export const value{} = {};\n", i, i));
+        }
+
+        let path = PathBuf::from("large.ts");
+        let result = AstAnalyzer::analyze_source(&path, &source).unwrap();
+
+        assert_eq!(result.exports.len(), 5000);
+        for (i, export) in result.exports.iter().enumerate() {
+            assert_eq!(export.name, format!("value{}", i));
+            assert_eq!(export.span.start, i as u32 + 1);
+            assert_eq!(export.span.end, i as u32 + 1);
+            assert_eq!(export.span.col_start, 0);
+        }
+    }
+
+    #[test]
+    fn test_analyze_reports_parse_errors() {
+        let source = "export const ok = 1;\nexport function broken( {\n";
+        let result = AstAnalyzer::analyze_source(Path::new("broken.ts"), source).unwrap();
+
+        assert!(!result.parse_errors.is_empty());
+        assert_eq!(result.parse_errors[0].path, "broken.ts");
+        assert!(result.parse_errors[0].line >= 2);
+    }
+
+    #[test]
+    fn test_analyze_vue_script_block() {
+        let source = r#"<template>
+  <my-button :label="title" />
+</template>
+
+<script lang="ts">
+import MyButton from './MyButton.vue';
+export const title: string = 'Hi';
+export default { components: { MyButton } };
+</script>
+"#;
+        let result = AstAnalyzer::analyze_source(Path::new("Card.vue"), source).unwrap();
+
+        let names: Vec<&str> = result.exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["title", "default"]);
+        // Spans point into the file, not the extracted block
+        assert_eq!(result.exports[0].span.start, 7);
+        let offset = source.find("export const").unwrap() as u32;
+        assert_eq!(result.exports[0].span.byte_start, offset);
+        assert_eq!(result.imports[0].source, "./MyButton.vue");
+        assert!(result.internal_refs.iter().any(|name| name == "MyButton"));
+        assert!(result.parse_errors.is_empty());
+    }
+
+    #[test]
+    fn test_analyze_svelte_props_are_not_exports() {
+        let source = r#"<script context="module">
+export const preload = () => {};
+</script>
+<script>
+export let name;
+</script>
+<h1>{name}</h1>
+"#;
+        let result = AstAnalyzer::analyze_source(Path::new("Page.svelte"), source).unwrap();
+
+        let names: Vec<&str> = result.exports.iter().map(|e| e.name.as_str()).collect();
+        assert_eq!(names, vec!["preload"]);
+    }
+
+    #[test]
+    fn test_import_shadowed_by_local_function_is_unused() {
+        let source = r#"import { format, parse } from './fmt';
+
+export function render(value) {
+  function format(x) {
+    return String(x);
+  }
+  return format(parse(value));
+}
+
+export const pad = (format) => format.padStart(2);
+"#;
+        let path = Path::new("view.ts");
+        let result = AstAnalyzer::analyze_source(path, source).unwrap();
+
+        assert!(!result.internal_refs.iter().any(|name| name == "format"));
+        assert_eq!(result.shadowed_refs, vec!["format"]);
+
+        let unused =
+            crate::scanner::find_unused_imports(path, "view.ts", &result, &|_| Ok(String::new()))
+                .unwrap();
+        let names: Vec<&str> = unused.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, vec!["format"]);
+        assert!(unused[0].reason.contains("shadowed by a local declaration"));
+    }
+
+    #[test]
+    fn test_make_imports_type_only() {
+        let path = Path::new("app.ts");
+        let source = "import { Config } from './config';\n\
+                      import { Options, load } from './options';\n\
+                      import Logger, { level } from './logger';\n";
+
+        let rewritten =
+            AstAnalyzer::make_imports_type_only(path, source, &["Config", "Options", "Logger"]);
+        assert_eq!(
+            rewritten,
+            "import type { Config } from './config';\n\
+             import { type Options, load } from './options';\n\
+             import Logger, { level } from './logger';\n"
+        );
+    }
 }
@@ -0,0 +1,90 @@
+//! Graph Cache - Persisted reference nodes for incremental scans
+//!
+//! Stores every analyzed file's node together with a hash of its source,
+//! so the next scan only re-parses files whose content changed.
+
+use crate::types::ReferenceNode;
+use anyhow::Result;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::fs;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Cache location, relative to the project root
+pub const CACHE_FILE: &str = ".clrd/graph.bin";
+
+/// How a scan uses the graph cache
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CacheMode {
+    /// Parse everything and leave the cache alone
+    #[default]
+    Disabled,
+    /// Reuse cached nodes for unchanged files, then update the cache
+    Incremental,
+    /// Parse everything and overwrite the cache
+    Rebuild,
+}
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GraphCache {
+    /// clrd version that wrote the cache; other versions start over
+    version: String,
+    entries: HashMap<PathBuf, CachedNode>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct CachedNode {
+    hash: u64,
+    node: ReferenceNode,
+}
+
+impl GraphCache {
+    pub fn new() -> Self {
+        Self {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            entries: HashMap::new(),
+        }
+    }
+
+    /// Load the cache under `root`, or an empty one if it is missing or stale
+    pub fn load(root: &Path) -> Self {
+        let cache = fs::read(root.join(CACHE_FILE))
+            .ok()
+            .and_then(|bytes| bincode::deserialize::<GraphCache>(&bytes).ok());
+
+        match cache {
+            Some(cache) if cache.version == env!("CARGO_PKG_VERSION") => cache,
+            _ => Self::new(),
+        }
+    }
+
+    pub fn save(&self, root: &Path) -> Result<()> {
+        let path = root.join(CACHE_FILE);
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, bincode::serialize(self)?)?;
+        Ok(())
+    }
+
+    /// The cached node for `path` if its source still hashes to `hash`
+    pub fn get(&self, path: &Path, hash: u64) -> Option<&ReferenceNode> {
+        self.entries
+            .get(path)
+            .filter(|entry| entry.hash == hash)
+            .map(|entry| &entry.node)
+    }
+
+    pub fn insert(&mut self, hash: u64, node: ReferenceNode) {
+        self.entries
+            .insert(node.file_path.clone(), CachedNode { hash, node });
+    }
+}
+
+/// Hash of a file's source; a different toolchain only causes a re-parse
+pub fn content_hash(source: &str) -> u64 {
+    let mut hasher = std::collections::hash_map::DefaultHasher::new();
+    source.hash(&mut hasher);
+    hasher.finish()
+}
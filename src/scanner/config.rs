@@ -0,0 +1,251 @@
+//! Config files - `clrd.toml` at the root and in any subdirectory
+//!
+//! A file's settings are the scan's own settings with every `clrd.toml`
+//! from the root down to the file's directory applied in turn, so the
+//! nearest file wins. Keys a file leaves out are inherited.
+
+use crate::types::ClrConfig;
+use anyhow::{bail, Context, Result};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Config file name, honored in every directory
+pub const CONFIG_FILE: &str = "clrd.toml";
+
+/// Settings read from one `clrd.toml`
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct ConfigFile {
+    pub extensions: Option<Vec<String>>,
+    pub include_tests: Option<bool>,
+    pub confidence_threshold: Option<f64>,
+    /// Relative to the directory holding the `clrd.toml`
+    pub entry_point_globs: Option<Vec<String>>,
+    pub test_file_globs: Option<Vec<String>>,
+    /// Relative to the directory holding the `clrd.toml`
+    pub public_api_globs: Option<Vec<String>>,
+    pub always_used_exports: Option<Vec<String>>,
+    pub generated_markers: Option<Vec<String>>,
+    /// Only read from the root `clrd.toml`
+    pub snippet_context: Option<usize>,
+    /// Only read from the root `clrd.toml`
+    pub snippet_max_lines: Option<usize>,
+    /// Only read from the root `clrd.toml`
+    pub max_file_bytes: Option<u64>,
+}
+
+impl ConfigFile {
+    /// Read a config file; `.json`, `.yaml` and `.yml` files are parsed as
+    /// such, anything else as TOML
+    pub fn load(path: &Path) -> Result<Self> {
+        let text = fs::read_to_string(path)
+            .with_context(|| format!("Failed to read {}", path.display()))?;
+        let parsed = match path.extension().and_then(|ext| ext.to_str()) {
+            Some("json") => serde_json::from_str(&text).map_err(anyhow::Error::from),
+            Some("yaml" | "yml") => serde_yaml::from_str(&text).map_err(anyhow::Error::from),
+            _ => toml::from_str(&text).map_err(anyhow::Error::from),
+        };
+        parsed.with_context(|| format!("Invalid config file {}", path.display()))
+    }
+
+    /// Override the settings this file sets
+    pub fn apply(&self, config: &mut ClrConfig) {
+        if let Some(extensions) = &self.extensions {
+            config.extensions = extensions.clone();
+        }
+        if let Some(include_tests) = self.include_tests {
+            config.include_tests = include_tests;
+        }
+        if let Some(threshold) = self.confidence_threshold {
+            config.confidence_threshold = threshold;
+        }
+        if let Some(globs) = &self.entry_point_globs {
+            config.entry_point_globs = globs.clone();
+        }
+        if let Some(globs) = &self.test_file_globs {
+            config.test_file_globs = globs.clone();
+        }
+        if let Some(globs) = &self.public_api_globs {
+            config.public_api_globs = globs.clone();
+        }
+        if let Some(names) = &self.always_used_exports {
+            config.always_used_exports = names.clone();
+        }
+        if let Some(markers) = &self.generated_markers {
+            config.generated_markers = markers.clone();
+        }
+        if let Some(lines) = self.snippet_context {
+            config.snippet_context = lines;
+        }
+        if let Some(lines) = self.snippet_max_lines {
+            config.snippet_max_lines = lines;
+        }
+        if let Some(bytes) = self.max_file_bytes {
+            config.max_file_bytes = bytes;
+        }
+    }
+}
+
+/// Apply `dir/clrd.toml` on top of `config`, if the file exists
+pub fn load_config(dir: &Path, mut config: ClrConfig) -> Result<ClrConfig> {
+    let path = dir.join(CONFIG_FILE);
+    if path.is_file() {
+        ConfigFile::load(&path)?.apply(&mut config);
+    }
+    Ok(config)
+}
+
+/// Apply the config file at `path` on top of `config`, which must exist
+pub fn load_config_file(path: &Path, mut config: ClrConfig) -> Result<ClrConfig> {
+    if !path.is_file() {
+        bail!("Config file {} not found", path.display());
+    }
+    ConfigFile::load(path)?.apply(&mut config);
+    Ok(config)
+}
+
+/// Settings for a scan of `root`: `config` under the root `clrd.toml`, or
+/// under `config_file` in its place
+///
+/// Scanners only read the `clrd.toml` files below the root themselves, so
+/// every caller starts from this and applies its own flags on top.
+pub fn load_root_config(
+    root: &Path,
+    config_file: Option<&Path>,
+    config: ClrConfig,
+) -> Result<ClrConfig> {
+    match config_file {
+        Some(path) => load_config_file(path, config),
+        None => load_config(root, config),
+    }
+}
+
+/// Effective settings of every directory holding a `clrd.toml`
+#[derive(Debug, Clone, Default)]
+pub struct DirectoryConfigs {
+    dirs: BTreeMap<PathBuf, ClrConfig>,
+}
+
+impl DirectoryConfigs {
+    /// Merge the given `clrd.toml` files onto `base`, outermost directory first
+    pub fn from_files(base: &ClrConfig, mut files: Vec<PathBuf>) -> Result<Self> {
+        files.sort_by_key(|file| file.components().count());
+        let mut configs = Self::default();
+        for file in files {
+            let Some(dir) = file.parent() else {
+                continue;
+            };
+            let mut config = configs
+                .nearest(dir.parent())
+                .map_or_else(|| base.clone(), |(_, config)| config.clone());
+            ConfigFile::load(&file)?.apply(&mut config);
+            configs.dirs.insert(dir.to_path_buf(), config);
+        }
+        Ok(configs)
+    }
+
+    /// The settings for `path` and the directory they come from, unless no
+    /// `clrd.toml` applies to it
+    pub fn for_path(&self, path: &Path) -> Option<(&Path, &ClrConfig)> {
+        self.nearest(Some(path))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.dirs.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&Path, &ClrConfig)> {
+        self.dirs
+            .iter()
+            .map(|(dir, config)| (dir.as_path(), config))
+    }
+
+    fn nearest(&self, path: Option<&Path>) -> Option<(&Path, &ClrConfig)> {
+        path?.ancestors().find_map(|dir| {
+            self.dirs
+                .get_key_value(dir)
+                .map(|(dir, config)| (dir.as_path(), config))
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_nearest_config_wins() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("packages/a/legacy")).unwrap();
+        fs::write(
+            root.join("packages/a/clrd.toml"),
+            "confidence_threshold = 0.7\nextensions = [\"ts\"]\n",
+        )
+        .unwrap();
+        fs::write(
+            root.join("packages/a/legacy/clrd.toml"),
+            "confidence_threshold = 0.9\n",
+        )
+        .unwrap();
+
+        let configs = DirectoryConfigs::from_files(
+            &ClrConfig::default(),
+            vec![
+                root.join("packages/a/legacy/clrd.toml"),
+                root.join("packages/a/clrd.toml"),
+            ],
+        )
+        .unwrap();
+
+        let (dir, config) = configs
+            .for_path(&root.join("packages/a/legacy/old.ts"))
+            .unwrap();
+        assert_eq!(dir, root.join("packages/a/legacy"));
+        assert_eq!(config.confidence_threshold, 0.9);
+        // Inherited from packages/a
+        assert_eq!(config.extensions, vec!["ts".to_string()]);
+
+        let (_, config) = configs.for_path(&root.join("packages/a/index.ts")).unwrap();
+        assert_eq!(config.confidence_threshold, 0.7);
+        assert!(configs
+            .for_path(&root.join("packages/b/index.ts"))
+            .is_none());
+    }
+
+    #[test]
+    fn test_unknown_key_is_rejected() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join(CONFIG_FILE);
+        fs::write(&path, "confidence = 0.9\n").unwrap();
+
+        assert!(ConfigFile::load(&path).is_err());
+    }
+
+    #[test]
+    fn test_load_config_file_from_other_location() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::create_dir_all(dir.path().join("ci")).unwrap();
+        let path = dir.path().join("ci/clrd.yaml");
+        fs::write(&path, "confidence_threshold: 0.9\nextensions: [ts]\n").unwrap();
+
+        let config = load_config_file(&path, ClrConfig::default()).unwrap();
+        assert_eq!(config.confidence_threshold, 0.9);
+        assert_eq!(config.extensions, vec!["ts".to_string()]);
+
+        let json = dir.path().join("ci/clrd.json");
+        fs::write(&json, "{\"include_tests\": true}").unwrap();
+        assert!(
+            load_config_file(&json, ClrConfig::default())
+                .unwrap()
+                .include_tests
+        );
+
+        let missing = load_config_file(&dir.path().join("nope.toml"), ClrConfig::default());
+        assert!(missing.unwrap_err().to_string().contains("not found"));
+        fs::write(&json, "{\"confidence\": 0.9}").unwrap();
+        assert!(load_config_file(&json, ClrConfig::default()).is_err());
+    }
+}
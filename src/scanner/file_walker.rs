@@ -1,18 +1,45 @@
 //! File Walker - Fast parallel file system traversal
 //!
 //! Uses the `ignore` crate for .gitignore-aware walking
-//! with additional custom ignore patterns.
+//! with additional custom ignore patterns and `.clrdignore` files.
 
+use super::config::CONFIG_FILE;
+use crate::types::default_test_file_globs;
 use anyhow::Result;
-use globset::{Glob, GlobSet, GlobSetBuilder};
-use ignore::WalkBuilder;
+use globset::{Glob, GlobBuilder, GlobSet, GlobSetBuilder};
+use ignore::{Walk, WalkBuilder};
 use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+
+/// Project-local ignore file using `.gitignore` syntax, honored in every directory
+pub const CLRD_IGNORE_FILE: &str = ".clrdignore";
+
+/// Compile heuristic patterns into a case-insensitive glob set, skipping invalid ones
+pub(crate) fn compile_globs(patterns: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for pattern in patterns {
+        match GlobBuilder::new(pattern).case_insensitive(true).build() {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(e) => tracing::warn!("Invalid glob pattern {:?}: {}", pattern, e),
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
 
 /// Walks the file system collecting relevant source files
 pub struct FileWalker {
     root: PathBuf,
     extensions: Vec<String>,
     ignore_patterns: GlobSet,
+    /// Compiled ignore patterns, parallel to the glob set's indices
+    ignore_sources: Vec<String>,
+    /// Ignore patterns that failed to compile, with the error
+    invalid_ignore_patterns: Vec<(String, String)>,
+    /// Whether each ignore pattern has matched a file checked so far
+    ignore_hits: Vec<AtomicBool>,
+    test_patterns: GlobSet,
     include_tests: bool,
 }
 
@@ -22,6 +49,10 @@ impl FileWalker {
             root: root.as_ref().to_path_buf(),
             extensions: Vec::new(),
             ignore_patterns: GlobSet::empty(),
+            ignore_sources: Vec::new(),
+            invalid_ignore_patterns: Vec::new(),
+            ignore_hits: Vec::new(),
+            test_patterns: compile_globs(&default_test_file_globs()),
             include_tests: false,
         }
     }
@@ -31,14 +62,49 @@ impl FileWalker {
         self
     }
 
+    /// Skip files matching these globs; invalid ones are kept aside for
+    /// `ignore_pattern_warnings`
     pub fn with_ignore_patterns(mut self, patterns: &[String]) -> Self {
         let mut builder = GlobSetBuilder::new();
+        self.ignore_sources.clear();
+        self.invalid_ignore_patterns.clear();
         for pattern in patterns {
-            if let Ok(glob) = Glob::new(pattern) {
-                builder.add(glob);
+            match Glob::new(pattern) {
+                Ok(glob) => {
+                    builder.add(glob);
+                    self.ignore_sources.push(pattern.clone());
+                }
+                Err(e) => self
+                    .invalid_ignore_patterns
+                    .push((pattern.clone(), e.kind().to_string())),
             }
         }
         self.ignore_patterns = builder.build().unwrap_or_else(|_| GlobSet::empty());
+        self.ignore_hits = self
+            .ignore_sources
+            .iter()
+            .map(|_| AtomicBool::new(false))
+            .collect();
+        self
+    }
+
+    /// Ignore patterns that failed to compile
+    pub fn invalid_ignore_patterns(&self) -> &[(String, String)] {
+        &self.invalid_ignore_patterns
+    }
+
+    /// Valid ignore patterns that have not matched any file checked so far
+    pub fn unmatched_ignore_patterns(&self) -> Vec<&str> {
+        self.ignore_sources
+            .iter()
+            .zip(&self.ignore_hits)
+            .filter(|(_, hit)| !hit.load(Ordering::Relaxed))
+            .map(|(pattern, _)| pattern.as_str())
+            .collect()
+    }
+
+    pub fn with_test_patterns(mut self, patterns: &[String]) -> Self {
+        self.test_patterns = compile_globs(patterns);
         self
     }
 
@@ -51,16 +117,7 @@ impl FileWalker {
     pub fn collect_files(&self) -> Result<Vec<PathBuf>> {
         let mut files = Vec::new();
 
-        let walker = WalkBuilder::new(&self.root)
-            .hidden(false)
-            .git_ignore(true)
-            .git_global(true)
-            .git_exclude(true)
-            .parents(true)
-            .threads(num_cpus::get())
-            .build();
-
-        for entry in walker.flatten() {
+        for entry in self.walk().flatten() {
             let path = entry.path();
 
             // Skip directories
@@ -68,28 +125,47 @@ impl FileWalker {
                 continue;
             }
 
-            // Check extension
-            if !self.has_valid_extension(path) {
-                continue;
+            if self.accepts(path) {
+                files.push(path.to_path_buf());
             }
+        }
 
-            // Check ignore patterns
-            if self.should_ignore(path) {
-                continue;
-            }
+        Ok(files)
+    }
 
-            // Check if test file (if not including tests)
-            if !self.include_tests && self.is_test_file(path) {
-                continue;
-            }
+    /// Collect the `clrd.toml` files below the root, honoring the same ignores
+    pub fn collect_config_files(&self) -> Vec<PathBuf> {
+        self.walk()
+            .flatten()
+            .map(|entry| entry.into_path())
+            .filter(|path| {
+                path.file_name().is_some_and(|name| name == CONFIG_FILE)
+                    && path.is_file()
+                    && !self.should_ignore(path)
+            })
+            .collect()
+    }
 
-            files.push(path.to_path_buf());
-        }
+    fn walk(&self) -> Walk {
+        WalkBuilder::new(&self.root)
+            .hidden(false)
+            .git_ignore(true)
+            .git_global(true)
+            .git_exclude(true)
+            .parents(true)
+            .add_custom_ignore_filename(CLRD_IGNORE_FILE)
+            .threads(num_cpus::get())
+            .build()
+    }
 
-        Ok(files)
+    /// Whether a file passes the extension, ignore and test filters
+    pub(crate) fn accepts(&self, path: &Path) -> bool {
+        self.has_valid_extension(path)
+            && !self.should_ignore(path)
+            && (self.include_tests || !self.is_test_file(path))
     }
 
-    fn has_valid_extension(&self, path: &Path) -> bool {
+    pub(crate) fn has_valid_extension(&self, path: &Path) -> bool {
         if self.extensions.is_empty() {
             return true;
         }
@@ -102,21 +178,15 @@ impl FileWalker {
 
     fn should_ignore(&self, path: &Path) -> bool {
         let path_str = path.to_string_lossy();
-        self.ignore_patterns.is_match(path_str.as_ref())
+        let matches = self.ignore_patterns.matches(path_str.as_ref());
+        for &index in &matches {
+            self.ignore_hits[index].store(true, Ordering::Relaxed);
+        }
+        !matches.is_empty()
     }
 
-    fn is_test_file(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy().to_lowercase();
-
-        // Common test file patterns
-        path_str.contains(".test.")
-            || path_str.contains(".spec.")
-            || path_str.contains("__tests__")
-            || path_str.contains("__mocks__")
-            || path_str.ends_with("_test.ts")
-            || path_str.ends_with("_test.js")
-            || path_str.ends_with("_spec.ts")
-            || path_str.ends_with("_spec.js")
+    pub(crate) fn is_test_file(&self, path: &Path) -> bool {
+        self.test_patterns.is_match(path)
     }
 }
 
@@ -142,4 +212,66 @@ mod tests {
         assert!(walker.is_test_file(Path::new("__tests__/baz.ts")));
         assert!(!walker.is_test_file(Path::new("utils.ts")));
     }
+
+    #[test]
+    fn test_custom_test_patterns() {
+        let walker = FileWalker::new("/tmp").with_test_patterns(&["**/*.e2e.ts".into()]);
+
+        assert!(walker.is_test_file(Path::new("login.e2e.ts")));
+        assert!(!walker.is_test_file(Path::new("foo.test.ts")));
+    }
+
+    #[test]
+    fn test_clrdignore_excludes_files() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("generated")).unwrap();
+        std::fs::create_dir_all(root.join("src/nested")).unwrap();
+        std::fs::write(root.join(".clrdignore"), "generated/\n").unwrap();
+        std::fs::write(root.join("src/nested/.clrdignore"), "*.gen.ts\n").unwrap();
+        for file in [
+            "generated/api.ts",
+            "src/app.ts",
+            "src/nested/kept.ts",
+            "src/nested/schema.gen.ts",
+        ] {
+            std::fs::write(root.join(file), "export const x = 1;\n").unwrap();
+        }
+
+        let mut files = FileWalker::new(root)
+            .with_extensions(&["ts".into()])
+            .collect_files()
+            .unwrap();
+        files.sort();
+
+        assert_eq!(
+            files,
+            vec![root.join("src/app.ts"), root.join("src/nested/kept.ts")]
+        );
+    }
+
+    #[test]
+    fn test_ignore_pattern_problems_are_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("src/legacy")).unwrap();
+        for file in ["src/app.ts", "src/legacy/old.ts"] {
+            std::fs::write(root.join(file), "export const x = 1;\n").unwrap();
+        }
+
+        let walker = FileWalker::new(root)
+            .with_extensions(&["ts".into()])
+            .with_ignore_patterns(&[
+                "**/legacy/**".into(),
+                "**/lgeacy/**".into(),
+                "src/[bad".into(),
+            ]);
+        let files = walker.collect_files().unwrap();
+
+        assert_eq!(files, vec![root.join("src/app.ts")]);
+        assert_eq!(walker.unmatched_ignore_patterns(), vec!["**/lgeacy/**"]);
+        let invalid = walker.invalid_ignore_patterns();
+        assert_eq!(invalid.len(), 1);
+        assert_eq!(invalid[0].0, "src/[bad");
+    }
 }
@@ -0,0 +1,217 @@
+//! File-local detectors - Findings decided by a single file
+//!
+//! Unused imports, type-only imports and write-only locals need nothing
+//! but the file's `ReferenceNode`, so they also run without a project
+//! graph, as in `Scanner::scan_source`.
+
+use super::analyzer::is_declaration_file;
+use crate::types::*;
+use anyhow::Result;
+use std::path::Path;
+
+/// Lines around a finding searched for `TODO` / `FIXME` / `HACK` comments
+const TODO_PROXIMITY_LINES: u32 = 3;
+
+/// Confidence removed from findings next to a `TODO`-style comment
+pub(super) const TODO_CONFIDENCE_PENALTY: f64 = 0.2;
+
+/// Source lines of a finding for its `code_snippet`, shortened when long
+pub type SnippetFn<'a> = dyn Fn(&CodeSpan) -> Result<String> + 'a;
+
+/// The lines `span` covers in `content` with `context_lines` on either side,
+/// cut after `max_lines` (0 for no limit)
+pub fn code_snippet(
+    content: &str,
+    span: &CodeSpan,
+    context_lines: usize,
+    max_lines: usize,
+) -> String {
+    let lines: Vec<&str> = content.lines().collect();
+
+    let start = (span.start as usize)
+        .saturating_sub(1)
+        .saturating_sub(context_lines);
+    let end = (span.end as usize + context_lines).min(lines.len());
+    let snippet = lines.get(start..end).unwrap_or_default();
+
+    // Limit snippet size
+    if max_lines > 0 && snippet.len() > max_lines {
+        format!(
+            "{}\n... ({} more lines)",
+            snippet[..max_lines].join("\n"),
+            snippet.len() - max_lines
+        )
+    } else {
+        snippet.join("\n")
+    }
+}
+
+/// Find imports that are never used in their file
+pub fn find_unused_imports(
+    file_path: &Path,
+    relative_path: &str,
+    node: &ReferenceNode,
+    snippet: &SnippetFn,
+) -> Result<Vec<DeadCodeItem>> {
+    let mut dead_code = Vec::new();
+
+    for import in &node.imports {
+        // Check if the imported name is used in the file
+        let name_to_check = import.alias.as_ref().unwrap_or(&import.name);
+
+        if !node.internal_refs.contains(name_to_check) && name_to_check != "*" {
+            // Type-only imports have lower confidence (might be used for type annotations)
+            let confidence = if import.is_type_only { 0.6 } else { 0.9 };
+
+            dead_code.push(DeadCodeItem {
+                id: finding_id(relative_path, DeadCodeKind::UnusedImport, name_to_check),
+                file_path: file_path.to_path_buf(),
+                relative_path: relative_path.to_string(),
+                span: import.span,
+                code_snippet: snippet(&import.span)?,
+                kind: DeadCodeKind::UnusedImport,
+                severity: DeadCodeKind::UnusedImport.severity(),
+                name: name_to_check.clone(),
+                reason: if node.shadowed_refs.contains(name_to_check) {
+                    format!(
+                        "Import '{}' from '{}' is shadowed by a local declaration wherever it is referenced",
+                        name_to_check, import.source
+                    )
+                } else {
+                    format!(
+                        "Import '{}' from '{}' is never used in this file",
+                        name_to_check, import.source
+                    )
+                },
+                confidence,
+                context: None,
+            });
+        }
+    }
+
+    Ok(dead_code)
+}
+
+/// Find value imports whose name only appears in type positions
+pub fn find_type_only_imports(
+    file_path: &Path,
+    relative_path: &str,
+    node: &ReferenceNode,
+    snippet: &SnippetFn,
+) -> Result<Vec<DeadCodeItem>> {
+    let mut dead_code = Vec::new();
+
+    // `import type` only exists in TypeScript
+    let is_typescript = file_path
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| matches!(ext, "ts" | "tsx" | "mts" | "cts"));
+    if !is_typescript || is_declaration_file(file_path) {
+        return Ok(dead_code);
+    }
+
+    for import in &node.imports {
+        let local = import.alias.as_ref().unwrap_or(&import.name);
+        if import.is_type_only || !node.type_refs.contains(local) {
+            continue;
+        }
+
+        // Not dead, so kept below the high-confidence bar
+        dead_code.push(DeadCodeItem {
+            id: finding_id(relative_path, DeadCodeKind::TypeOnlyImport, local),
+            file_path: file_path.to_path_buf(),
+            relative_path: relative_path.to_string(),
+            span: import.span,
+            code_snippet: snippet(&import.span)?,
+            kind: DeadCodeKind::TypeOnlyImport,
+            severity: DeadCodeKind::TypeOnlyImport.severity(),
+            name: local.clone(),
+            reason: format!(
+                "Import '{}' from '{}' is only used as a type and can be `import type`",
+                local, import.source
+            ),
+            confidence: 0.5,
+            context: None,
+        });
+    }
+
+    Ok(dead_code)
+}
+
+/// Find non-exported `let`/`var` bindings that are only ever assigned to
+pub fn find_write_only_locals(
+    file_path: &Path,
+    relative_path: &str,
+    node: &ReferenceNode,
+    snippet: &SnippetFn,
+) -> Result<Vec<DeadCodeItem>> {
+    let locals = node.locals.iter().map(|l| (&l.name, l.kind, &l.span));
+    write_only_symbols(file_path, relative_path, node, locals, snippet)
+}
+
+/// Report the given bindings that are `let`/`var` and only ever assigned to
+pub(super) fn write_only_symbols<'a>(
+    file_path: &Path,
+    relative_path: &str,
+    node: &ReferenceNode,
+    symbols: impl Iterator<Item = (&'a String, SymbolKind, &'a CodeSpan)>,
+    snippet: &SnippetFn,
+) -> Result<Vec<DeadCodeItem>> {
+    let mut dead_code = Vec::new();
+
+    for (name, kind, span) in symbols {
+        if !matches!(kind, SymbolKind::Let | SymbolKind::Variable) || !is_write_only(node, name) {
+            continue;
+        }
+
+        // Assignments may be kept for side effects (getters, proxies)
+        dead_code.push(DeadCodeItem {
+            id: finding_id(relative_path, DeadCodeKind::UnusedVariable, name),
+            file_path: file_path.to_path_buf(),
+            relative_path: relative_path.to_string(),
+            span: *span,
+            code_snippet: snippet(span)?,
+            kind: DeadCodeKind::UnusedVariable,
+            severity: DeadCodeKind::UnusedVariable.severity(),
+            name: name.clone(),
+            reason: format!("'{}' is assigned but its value is never read", name),
+            confidence: 0.6,
+            context: None,
+        });
+    }
+
+    Ok(dead_code)
+}
+
+/// Whether a name is assigned in its file but never read there
+pub(super) fn is_write_only(node: &ReferenceNode, name: &str) -> bool {
+    node.write_refs.iter().any(|n| n == name) && !node.internal_refs.iter().any(|n| n == name)
+}
+
+/// Flag findings next to a `TODO`, `FIXME` or `HACK` comment, which
+/// often mark deliberate work in progress, and lower their confidence
+pub fn mark_work_in_progress(node: &ReferenceNode, dead_code: &mut [DeadCodeItem]) {
+    for item in dead_code {
+        if matches!(
+            item.kind,
+            DeadCodeKind::ZombieFile | DeadCodeKind::CircularImport
+        ) || !has_todo_near(node, &item.span)
+        {
+            continue;
+        }
+        item.context.get_or_insert_with(Default::default).has_todo = true;
+        // Unused exports already count it among their confidence factors
+        if item.kind != DeadCodeKind::UnusedExport {
+            item.confidence = (item.confidence - TODO_CONFIDENCE_PENALTY).max(0.1);
+        }
+    }
+}
+
+/// Whether a `TODO`-style comment is within a few lines of `span`
+pub(super) fn has_todo_near(node: &ReferenceNode, span: &CodeSpan) -> bool {
+    let first = span.start.saturating_sub(TODO_PROXIMITY_LINES);
+    let last = span.end + TODO_PROXIMITY_LINES;
+    node.todo_lines
+        .iter()
+        .any(|line| (first..=last).contains(line))
+}
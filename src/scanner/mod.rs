@@ -4,27 +4,90 @@
 //! maximum performance scanning JavaScript/TypeScript codebases.
 
 mod analyzer;
+mod cache;
+mod config;
 mod file_walker;
+mod local_detectors;
+mod progress;
 mod reference_graph;
+mod sfc;
+mod source;
+mod tsconfig;
 
-pub use analyzer::AstAnalyzer;
+pub use analyzer::{is_declaration_file, AstAnalyzer};
+pub use cache::{content_hash, CacheMode, GraphCache, CACHE_FILE};
+pub use config::{
+    load_config, load_config_file, load_root_config, ConfigFile, DirectoryConfigs, CONFIG_FILE,
+};
 pub use file_walker::FileWalker;
-pub use reference_graph::ReferenceGraph;
+pub use local_detectors::{
+    code_snippet, find_type_only_imports, find_unused_imports, find_write_only_locals,
+    mark_work_in_progress, SnippetFn,
+};
+pub use progress::{ProgressSink, ScanPhase};
+use reference_graph::{owning_root, relative_to};
+pub use reference_graph::{package_entries, ReferenceGraph, EXPORT_BASE_CONFIDENCE};
+pub use source::{read_source, SourceText};
+pub use tsconfig::{PathMappings, TSCONFIG_FILE};
 
 use crate::types::*;
-use anyhow::Result;
+use anyhow::{Context, Result};
 use rayon::prelude::*;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::{Arc, Mutex};
-use std::time::Instant;
+use std::process::Command;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime, UNIX_EPOCH};
+
+/// Number of slowest files listed in scan statistics
+const SLOWEST_FILES_REPORTED: usize = 10;
+
+/// Patterns a scanner ignores unless given its own
+pub fn default_ignore_patterns() -> Vec<String> {
+    vec![
+        "**/node_modules/**".into(),
+        "**/dist/**".into(),
+        "**/build/**".into(),
+        "**/.git/**".into(),
+    ]
+}
 
 /// The main scanner that orchestrates dead code detection
+#[derive(Clone)]
 pub struct Scanner {
     root: PathBuf,
+    additional_roots: Vec<PathBuf>,
     extensions: Vec<String>,
     ignore_patterns: Vec<String>,
     include_tests: bool,
+    consider_tests_usage: bool,
     confidence_threshold: f64,
+    entry_point_globs: Vec<String>,
+    test_file_globs: Vec<String>,
+    public_api_globs: Vec<String>,
+    always_used_exports: Vec<String>,
+    explicit_files: Option<Vec<PathBuf>>,
+    max_results: Option<usize>,
+    max_per_file: Option<usize>,
+    cache_mode: CacheMode,
+    read_threads: usize,
+    mmap_threshold: Option<u64>,
+    max_file_bytes: u64,
+    snippets: bool,
+    snippet_context: usize,
+    snippet_max_lines: usize,
+    context: bool,
+    entry_files: Vec<PathBuf>,
+    detect_cycles: bool,
+    excluded_kinds: Vec<DeadCodeKind>,
+    exclude_possibly_dynamic: bool,
+    generated_markers: Vec<String>,
+    min_file_age: Option<Duration>,
+    framework_naming: bool,
+    cancel: Option<Arc<AtomicBool>>,
+    progress: Option<Arc<dyn ProgressSink>>,
+    collect_stats: bool,
 }
 
 impl Scanner {
@@ -32,25 +95,46 @@ impl Scanner {
     pub fn new(root: impl AsRef<Path>) -> Self {
         Self {
             root: root.as_ref().to_path_buf(),
-            extensions: vec![
-                "ts".into(),
-                "tsx".into(),
-                "js".into(),
-                "jsx".into(),
-                "mjs".into(),
-                "cjs".into(),
-            ],
-            ignore_patterns: vec![
-                "**/node_modules/**".into(),
-                "**/dist/**".into(),
-                "**/build/**".into(),
-                "**/.git/**".into(),
-            ],
+            additional_roots: Vec::new(),
+            extensions: default_extensions(),
+            ignore_patterns: default_ignore_patterns(),
             include_tests: false,
+            consider_tests_usage: false,
             confidence_threshold: 0.5,
+            entry_point_globs: default_entry_point_globs(),
+            test_file_globs: default_test_file_globs(),
+            public_api_globs: default_public_api_globs(),
+            always_used_exports: Vec::new(),
+            explicit_files: None,
+            max_results: None,
+            max_per_file: None,
+            cache_mode: CacheMode::Disabled,
+            read_threads: num_cpus::get(),
+            mmap_threshold: None,
+            max_file_bytes: default_max_file_bytes(),
+            snippets: true,
+            snippet_context: 0,
+            snippet_max_lines: default_snippet_max_lines(),
+            context: true,
+            entry_files: Vec::new(),
+            detect_cycles: false,
+            excluded_kinds: Vec::new(),
+            exclude_possibly_dynamic: false,
+            generated_markers: default_generated_markers(),
+            min_file_age: None,
+            framework_naming: true,
+            cancel: None,
+            progress: None,
+            collect_stats: false,
         }
     }
 
+    /// Also scan these directories, sharing one reference graph with the root
+    pub fn with_additional_roots(mut self, roots: Vec<PathBuf>) -> Self {
+        self.additional_roots = roots;
+        self
+    }
+
     /// Set file extensions to scan
     pub fn with_extensions(mut self, extensions: Vec<String>) -> Self {
         if !extensions.is_empty() {
@@ -73,75 +157,835 @@ impl Scanner {
         self
     }
 
+    /// Parse excluded test files so their imports count as usage, without
+    /// reporting anything in them
+    pub fn consider_tests_usage(mut self, consider: bool) -> Self {
+        self.consider_tests_usage = consider;
+        self
+    }
+
     /// Set minimum confidence threshold
     pub fn with_confidence_threshold(mut self, threshold: f64) -> Self {
         self.confidence_threshold = threshold;
         self
     }
 
-    /// Execute the scan and return results
-    pub async fn scan(&self) -> Result<ScanOutput> {
-        let start = Instant::now();
+    /// Set patterns for files that are never reported as zombies
+    pub fn with_entry_point_globs(mut self, patterns: Vec<String>) -> Self {
+        if !patterns.is_empty() {
+            self.entry_point_globs = patterns;
+        }
+        self
+    }
 
-        // Phase 1: Walk the file system and collect files
-        tracing::info!("Phase 1: Collecting files from {:?}", self.root);
-        let walker = FileWalker::new(&self.root)
-            .with_extensions(&self.extensions)
-            .with_ignore_patterns(&self.ignore_patterns)
-            .include_tests(self.include_tests);
+    /// Set patterns for files treated as tests
+    pub fn with_test_file_globs(mut self, patterns: Vec<String>) -> Self {
+        if !patterns.is_empty() {
+            self.test_file_globs = patterns;
+        }
+        self
+    }
 
-        let files = walker.collect_files()?;
-        let total_files = files.len() as u32;
-        tracing::info!("Found {} files to analyze", total_files);
+    /// Set patterns for files whose exports form the public API
+    pub fn with_public_api_globs(mut self, patterns: Vec<String>) -> Self {
+        if !patterns.is_empty() {
+            self.public_api_globs = patterns;
+        }
+        self
+    }
 
-        // Phase 2: Parse all files in parallel and build reference graph
-        tracing::info!("Phase 2: Building reference graph");
-        let graph = Arc::new(Mutex::new(ReferenceGraph::new()));
-        let total_lines = Arc::new(Mutex::new(0u64));
+    /// Set export names (exact or glob) that are never reported as unused
+    pub fn with_always_used_exports(mut self, names: Vec<String>) -> Self {
+        self.always_used_exports = names;
+        self
+    }
 
-        files
-            .par_iter()
-            .for_each(|file_path| match AstAnalyzer::analyze_file(file_path) {
-                Ok(node) => {
-                    let lines = node.exports.len() + node.imports.len();
-                    *total_lines.lock().unwrap() += lines as u64;
-                    graph.lock().unwrap().add_node(node);
-                }
-                Err(e) => {
-                    tracing::warn!("Failed to analyze {:?}: {}", file_path, e);
-                }
-            });
+    /// Analyze exactly these files instead of walking the root
+    ///
+    /// Only the listed files are parsed, so exports used solely from
+    /// unlisted files are reported as unused.
+    pub fn with_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.explicit_files = Some(files);
+        self
+    }
+
+    /// Take every setting `config` holds, usually from [`load_root_config`]
+    /// with a command's flags applied
+    ///
+    /// Ignore patterns are left alone: they are not a `clrd.toml` setting.
+    pub fn with_config(self, config: &ClrConfig) -> Self {
+        self.with_extensions(config.extensions.clone())
+            .include_tests(config.include_tests)
+            .consider_tests_usage(config.consider_tests_usage)
+            .with_confidence_threshold(config.confidence_threshold)
+            .with_entry_point_globs(config.entry_point_globs.clone())
+            .with_test_file_globs(config.test_file_globs.clone())
+            .with_public_api_globs(config.public_api_globs.clone())
+            .with_always_used_exports(config.always_used_exports.clone())
+            .with_generated_markers(config.generated_markers.clone())
+            .with_cycle_detection(config.detect_cycles)
+            .with_excluded_kinds(config.excluded_kinds.clone())
+            .exclude_possibly_dynamic(config.exclude_possibly_dynamic)
+            .with_max_file_bytes(config.max_file_bytes)
+            .with_snippet_context(config.snippet_context)
+            .with_snippet_max_lines(config.snippet_max_lines)
+    }
+
+    /// Emit at most this many findings, most confident first
+    pub fn with_max_results(mut self, max_results: Option<usize>) -> Self {
+        self.max_results = max_results;
+        self
+    }
+
+    /// Emit at most this many findings per file, most confident first
+    pub fn with_max_per_file(mut self, max_per_file: Option<usize>) -> Self {
+        self.max_per_file = max_per_file;
+        self
+    }
+
+    /// Choose whether parsed files are reused from and saved to the graph cache
+    pub fn with_cache_mode(mut self, mode: CacheMode) -> Self {
+        self.cache_mode = mode;
+        self
+    }
+
+    /// Number of threads reading files, separate from the parsing threads
+    pub fn with_read_threads(mut self, threads: Option<usize>) -> Self {
+        if let Some(threads) = threads.filter(|&threads| threads > 0) {
+            self.read_threads = threads;
+        }
+        self
+    }
+
+    /// Memory-map files of at least this many bytes; `None` always buffers
+    pub fn with_mmap_threshold(mut self, threshold: Option<u64>) -> Self {
+        self.mmap_threshold = threshold;
+        self
+    }
+
+    /// Skip files larger than this many bytes; 0 reads every file
+    pub fn with_max_file_bytes(mut self, bytes: u64) -> Self {
+        self.max_file_bytes = bytes;
+        self
+    }
+
+    /// Report files not reachable from these entries (relative to the root)
+    pub fn with_entry_files(mut self, files: Vec<PathBuf>) -> Self {
+        self.entry_files = files.into_iter().map(|file| self.root.join(file)).collect();
+        self
+    }
+
+    /// Also report import cycles as informational `circular_import` findings
+    pub fn with_cycle_detection(mut self, detect: bool) -> Self {
+        self.detect_cycles = detect;
+        self
+    }
+
+    /// Never report findings of these kinds
+    pub fn with_excluded_kinds(mut self, kinds: Vec<DeadCodeKind>) -> Self {
+        self.excluded_kinds = kinds;
+        self
+    }
+
+    /// Drop findings whose context marks them as possibly used dynamically
+    pub fn exclude_possibly_dynamic(mut self, exclude: bool) -> Self {
+        self.exclude_possibly_dynamic = exclude;
+        self
+    }
+
+    /// Treat files whose first lines contain one of these markers as generated
+    ///
+    /// Imports in generated files count as usage, but nothing in them is reported.
+    pub fn with_generated_markers(mut self, markers: Vec<String>) -> Self {
+        self.generated_markers = markers;
+        self
+    }
+
+    /// Skip findings in files last changed more recently than `age`
+    ///
+    /// A file's age comes from its last git commit, or its modification
+    /// time when git has no history for it.
+    pub fn with_min_file_age(mut self, age: Option<Duration>) -> Self {
+        self.min_file_age = age;
+        self
+    }
+
+    /// Lower confidence for exports named like React hooks or HOCs (on by default)
+    pub fn with_framework_naming(mut self, enabled: bool) -> Self {
+        self.framework_naming = enabled;
+        self
+    }
+
+    /// Whether findings include a code snippet
+    pub fn with_snippets(mut self, snippets: bool) -> Self {
+        self.snippets = snippets;
+        self
+    }
+
+    /// Include this many lines before and after each code snippet
+    pub fn with_snippet_context(mut self, lines: usize) -> Self {
+        self.snippet_context = lines;
+        self
+    }
+
+    /// Cut code snippets after this many lines (0 for no limit)
+    pub fn with_snippet_max_lines(mut self, lines: usize) -> Self {
+        self.snippet_max_lines = lines;
+        self
+    }
+
+    /// Whether findings include their `context` hints
+    pub fn with_context(mut self, context: bool) -> Self {
+        self.context = context;
+        self
+    }
+
+    /// Stop the scan with an error once `token` is set
+    ///
+    /// The token is checked between phases and before each file is read or
+    /// parsed, so a cancel takes effect without waiting for the whole phase.
+    pub fn with_cancel_token(mut self, token: Arc<AtomicBool>) -> Self {
+        self.cancel = Some(token);
+        self
+    }
+
+    /// Report phase changes and per-file progress to `sink`
+    pub fn with_progress(mut self, sink: Arc<dyn ProgressSink>) -> Self {
+        self.progress = Some(sink);
+        self
+    }
+
+    /// Collect per-phase timing statistics
+    pub fn with_stats(mut self, collect: bool) -> Self {
+        self.collect_stats = collect;
+        self
+    }
+
+    /// Execute the scan on a blocking thread and return results
+    pub async fn scan(&self) -> Result<ScanOutput> {
+        let scanner = self.clone();
+        tokio::task::spawn_blocking(move || scanner.scan_blocking())
+            .await
+            .context("Scan task failed")?
+    }
+
+    /// Execute the scan on the calling thread and return results; the work
+    /// is CPU-bound and parallelized with Rayon, so no async runtime is needed
+    pub fn scan_blocking(&self) -> Result<ScanOutput> {
+        let start = Instant::now();
+
+        let BuildOutput {
+            graph,
+            total_files,
+            total_lines,
+            mut stats,
+            parse_errors,
+            ignore_warnings,
+            skipped_files,
+            content_hashes,
+            directory_configs,
+        } = self.build()?;
+        self.check_cancelled()?;
 
         // Phase 3: Detect dead code
         tracing::info!("Phase 3: Detecting dead code");
-        let graph = Arc::try_unwrap(graph)
-            .map_err(|_| anyhow::anyhow!("Failed to unwrap graph"))?
-            .into_inner()?;
-
-        let dead_code = graph.find_dead_code(&self.root, self.confidence_threshold)?;
+        self.start_phase(ScanPhase::Detect, None);
+        let detect_start = Instant::now();
+        // A nested `clrd.toml` may lower the threshold below the scan's own
+        let lowest_threshold = directory_configs
+            .iter()
+            .map(|(_, config)| config.confidence_threshold)
+            .fold(self.confidence_threshold, f64::min);
+        let mut dead_code = graph.find_dead_code(&self.root, lowest_threshold)?;
+        dead_code.retain(|item| {
+            let threshold = directory_configs
+                .for_path(&item.file_path)
+                .map_or(self.confidence_threshold, |(_, config)| {
+                    config.confidence_threshold
+                });
+            // Cycles are informational and never filtered by confidence
+            if item.kind != DeadCodeKind::CircularImport && item.confidence < threshold {
+                return false;
+            }
+            let excluded_dynamic = self.exclude_possibly_dynamic
+                && item.context.as_ref().is_some_and(|c| c.possibly_dynamic);
+            !excluded_dynamic && !self.excluded_kinds.contains(&item.kind)
+        });
+        if let Some(min_age) = self.min_file_age {
+            let files: HashSet<&PathBuf> = dead_code.iter().map(|item| &item.file_path).collect();
+            let now = SystemTime::now();
+            let young: HashSet<PathBuf> = files
+                .into_par_iter()
+                .filter(|path| {
+                    last_changed(path)
+                        .and_then(|changed| now.duration_since(changed).ok())
+                        .map_or(true, |age| age < min_age)
+                })
+                .cloned()
+                .collect();
+            dead_code.retain(|item| !young.contains(&item.file_path));
+        }
+        if !self.context {
+            for item in &mut dead_code {
+                item.context = None;
+            }
+        }
+        stats.detect = detect_start.elapsed();
 
         // Build summary
         let mut summary = ScanSummary::new();
         for item in &dead_code {
             summary.add(item);
         }
+        summary.parse_errors = parse_errors
+            .iter()
+            .map(|e| e.path.as_str())
+            .collect::<HashSet<_>>()
+            .len() as u32;
+        summary.skipped_files = skipped_files.len() as u32;
+        summary.files_with_issues = dead_code
+            .iter()
+            .map(|item| &item.file_path)
+            .collect::<HashSet<_>>()
+            .len() as u32;
+        if total_lines > 0 {
+            let dead_lines = RemovalEstimate::of(&dead_code, |path| graph.line_count(path)).lines;
+            summary.dead_code_ratio = dead_lines as f64 / total_lines as f64;
+        }
+        summary.estimated_removal = RemovalEstimate::of(
+            dead_code
+                .iter()
+                .filter(|item| item.confidence >= 0.8 && item.kind.is_removable()),
+            |path| graph.line_count(path),
+        );
+        summary.compute_health(total_lines);
+
+        // The summary above still counts every finding
+        let total_findings = dead_code.len() as u32;
+        if let Some(max_per_file) = self.max_per_file {
+            summary.suppressed_per_file = cap_per_file(&mut dead_code, max_per_file, |path| {
+                relative_to(path, &self.root)
+            });
+        }
+        let truncated = !summary.suppressed_per_file.is_empty()
+            || self
+                .max_results
+                .is_some_and(|max_results| dead_code.len() > max_results);
+        if let Some(max_results) = self.max_results {
+            dead_code.sort_by(|a, b| b.confidence.total_cmp(&a.confidence));
+            dead_code.truncate(max_results);
+        }
 
         let scan_duration_ms = start.elapsed().as_millis() as u64;
-        let total_lines = Arc::try_unwrap(total_lines)
-            .map_err(|_| anyhow::anyhow!("Failed to unwrap lines counter"))?
-            .into_inner()?;
 
         Ok(ScanOutput {
             version: env!("CARGO_PKG_VERSION").to_string(),
             root: self.root.to_string_lossy().to_string(),
             timestamp: chrono_lite_now(),
             dead_code,
+            total_findings,
+            truncated,
             total_files_scanned: total_files,
             total_lines,
             scan_duration_ms,
             summary,
+            parse_errors,
+            ignore_warnings,
+            unresolved_imports: graph.unresolved_imports(&self.root),
+            skipped_files,
+            stats: self.collect_stats.then_some(stats),
+            content_hashes,
         })
     }
+
+    /// Collect and parse files into a reference graph without detecting dead code
+    pub fn build_graph(&self) -> Result<ReferenceGraph> {
+        Ok(self.build()?.graph)
+    }
+
+    /// Check one in-memory source with the file-local detectors only
+    ///
+    /// Nothing is read from disk, so findings that need other files
+    /// (unused exports, zombie files, cycles) are never reported.
+    /// `path` may be relative to the scanner root and needn't exist.
+    pub fn scan_source(&self, path: impl AsRef<Path>, source: &str) -> Result<Vec<DeadCodeItem>> {
+        let file_path = self.root.join(path);
+        let node = AstAnalyzer::analyze_source(&file_path, source)?;
+        let relative_path = self.relative_path(&file_path);
+        let snippet = |span: &CodeSpan| {
+            Ok(if self.snippets {
+                code_snippet(source, span, self.snippet_context, self.snippet_max_lines)
+            } else {
+                String::new()
+            })
+        };
+
+        let mut dead_code = find_unused_imports(&file_path, &relative_path, &node, &snippet)?;
+        dead_code.extend(find_type_only_imports(
+            &file_path,
+            &relative_path,
+            &node,
+            &snippet,
+        )?);
+        dead_code.extend(find_write_only_locals(
+            &file_path,
+            &relative_path,
+            &node,
+            &snippet,
+        )?);
+        mark_work_in_progress(&node, &mut dead_code);
+
+        dead_code.retain(|item| {
+            item.confidence >= self.confidence_threshold
+                && !self.excluded_kinds.contains(&item.kind)
+        });
+        if !self.context {
+            for item in &mut dead_code {
+                item.context = None;
+            }
+        }
+        dead_code.sort_by_key(|item| item.span.start);
+        Ok(dead_code)
+    }
+
+    /// Phases 1 and 2: walk the file system and build the reference graph
+    fn build(&self) -> Result<BuildOutput> {
+        let mut stats = ScanStats::default();
+
+        // Phase 1: Walk the file system and collect files
+        tracing::info!("Phase 1: Collecting files from {:?}", self.root);
+        self.start_phase(ScanPhase::Collect, None);
+        let walk_start = Instant::now();
+        let directory_configs = self.directory_configs()?;
+        let walker_with = |root: &Path, config: &ClrConfig| {
+            FileWalker::new(root)
+                .with_extensions(&config.extensions)
+                .with_ignore_patterns(&self.ignore_patterns)
+                .with_test_patterns(&config.test_file_globs)
+                .include_tests(config.include_tests || self.consider_tests_usage)
+        };
+        let base_config = self.base_config();
+        let walker = walker_with(&self.root, &base_config);
+        // Each file is held to the settings of the nearest `clrd.toml`
+        let directory_walkers: Vec<(&Path, FileWalker, bool)> = directory_configs
+            .iter()
+            .map(|(dir, config)| (dir, walker_with(dir, config), config.include_tests))
+            .collect();
+        let walker_at = |path: &Path| {
+            directory_configs
+                .for_path(path)
+                .and_then(|(dir, _)| directory_walkers.iter().find(|(d, ..)| *d == dir))
+                .map_or(
+                    (&walker, self.include_tests),
+                    |(_, walker, include_tests)| (walker, *include_tests),
+                )
+        };
+
+        let mut ignore_warnings: Vec<String> = walker
+            .invalid_ignore_patterns()
+            .iter()
+            .map(|(pattern, error)| format!("Invalid ignore pattern `{}`: {}", pattern, error))
+            .collect();
+        let mut files = match &self.explicit_files {
+            Some(files) => files
+                .iter()
+                .filter(|path| {
+                    let valid = walker_at(path).0.has_valid_extension(path);
+                    if !valid {
+                        tracing::warn!("Skipping {:?}: extension is not scanned", path);
+                    }
+                    valid
+                })
+                .cloned()
+                .collect(),
+            None => {
+                // Walk with every extension and test setting some `clrd.toml`
+                // allows, then filter each file by its own settings
+                let mut widened = base_config.clone();
+                for (_, config) in directory_configs.iter() {
+                    for extension in &config.extensions {
+                        if !widened.extensions.contains(extension) {
+                            widened.extensions.push(extension.clone());
+                        }
+                    }
+                    widened.include_tests = true;
+                }
+                let mut files = Vec::new();
+                let mut unmatched: Option<Vec<String>> = None;
+                for root in std::iter::once(&self.root).chain(&self.additional_roots) {
+                    let root_walker = walker_with(root, &widened);
+                    files.extend(root_walker.collect_files()?);
+                    let here = root_walker.unmatched_ignore_patterns();
+                    let still = unmatched.get_or_insert_with(|| {
+                        here.iter().map(|pattern| pattern.to_string()).collect()
+                    });
+                    still.retain(|pattern| here.contains(&pattern.as_str()));
+                }
+                // Built-in patterns often match nothing because .gitignore hides those files
+                let defaults = default_ignore_patterns();
+                ignore_warnings.extend(
+                    unmatched
+                        .unwrap_or_default()
+                        .into_iter()
+                        .filter(|pattern| !defaults.contains(pattern))
+                        .map(|pattern| format!("Ignore pattern `{}` matched no files", pattern)),
+                );
+                if !directory_configs.is_empty() {
+                    files.retain(|path| walker_at(path).0.accepts(path));
+                }
+                // Overlapping roots would otherwise parse a file twice
+                files.sort();
+                files.dedup();
+                files
+            }
+        };
+        // Oversized files are usually bundles that slipped past the ignores
+        let mut skipped_files = Vec::new();
+        if self.max_file_bytes > 0 {
+            files.retain(|path| {
+                let bytes = std::fs::metadata(path).map_or(0, |meta| meta.len());
+                if bytes <= self.max_file_bytes {
+                    return true;
+                }
+                tracing::warn!(
+                    "Skipping {:?}: {} bytes exceeds the size limit",
+                    path,
+                    bytes
+                );
+                skipped_files.push(SkippedFile {
+                    path: self.relative_path(path),
+                    bytes,
+                });
+                false
+            });
+            skipped_files.sort_by(|a, b| a.path.cmp(&b.path));
+        }
+
+        // Test files collected only so their imports count as usage
+        let mut usage_only: HashSet<PathBuf> =
+            if self.explicit_files.is_none() && self.consider_tests_usage {
+                files
+                    .iter()
+                    .filter(|path| {
+                        let (walker, include_tests) = walker_at(path);
+                        !include_tests && walker.is_test_file(path)
+                    })
+                    .cloned()
+                    .collect()
+            } else {
+                HashSet::new()
+            };
+        stats.walk = walk_start.elapsed();
+        tracing::info!("Found {} files", files.len());
+        self.check_cancelled()?;
+
+        // Phase 2: Read all files on a bounded IO pool, parse them in parallel,
+        // then build the reference graph
+        tracing::info!("Phase 2: Building reference graph");
+        self.start_phase(ScanPhase::Read, Some(files.len() as u64));
+        let read_start = Instant::now();
+        let read_pool = rayon::ThreadPoolBuilder::new()
+            .num_threads(self.read_threads)
+            .build()
+            .context("Failed to start file reading threads")?;
+        let sources: Vec<Result<SourceText>> = read_pool.install(|| {
+            files
+                .par_iter()
+                .map(|file_path| {
+                    self.check_cancelled()?;
+                    let source = read_source(file_path, self.mmap_threshold);
+                    self.file_done();
+                    source
+                })
+                .collect()
+        });
+        stats.read = read_start.elapsed();
+        self.check_cancelled()?;
+
+        // Generated files still count as usage but are never reported on
+        let generated: Vec<&PathBuf> = files
+            .par_iter()
+            .zip(&sources)
+            .filter(|(path, source)| {
+                let markers = directory_configs
+                    .for_path(path)
+                    .map_or(&self.generated_markers, |(_, config)| {
+                        &config.generated_markers
+                    });
+                source
+                    .as_ref()
+                    .is_ok_and(|source| is_generated(source.as_str(), markers))
+            })
+            .map(|(path, _)| path)
+            .collect();
+        usage_only.extend(generated.into_iter().cloned());
+        let total_files = (files.len() - usage_only.len()) as u32;
+        tracing::info!("Analyzing {} files", total_files);
+
+        self.start_phase(ScanPhase::Parse, Some(files.len() as u64));
+        let parse_start = Instant::now();
+        let cache = match self.cache_mode {
+            CacheMode::Incremental => GraphCache::load(&self.root),
+            CacheMode::Disabled | CacheMode::Rebuild => GraphCache::new(),
+        };
+        let parsed: Vec<_> = files
+            .par_iter()
+            .zip(sources)
+            .map(|(file_path, source)| {
+                let file_start = Instant::now();
+                let result = source.and_then(|source| {
+                    self.check_cancelled()?;
+                    let source = source.as_str();
+                    let hash = cache::content_hash(source);
+                    match cache.get(file_path, hash) {
+                        Some(node) => Ok((node.clone(), hash, true)),
+                        None => AstAnalyzer::analyze_source(file_path, source)
+                            .map(|node| (node, hash, false)),
+                    }
+                });
+                self.file_done();
+                (file_path, result, file_start.elapsed())
+            })
+            .collect();
+        stats.parse = parse_start.elapsed();
+        self.check_cancelled()?;
+        let mut updated_cache = GraphCache::new();
+
+        let graph_start = Instant::now();
+        let mut graph = ReferenceGraph::new()
+            .with_entry_point_globs(&self.entry_point_globs)
+            .with_test_file_globs(&self.test_file_globs)
+            .with_public_api_globs(&self.public_api_globs)
+            .with_always_used_exports(&self.always_used_exports)
+            .with_additional_roots(&self.additional_roots)
+            .with_resolve_extensions(&self.extensions)
+            .with_snippets(self.snippets)
+            .with_snippet_context(self.snippet_context)
+            .with_snippet_max_lines(self.snippet_max_lines)
+            .with_entry_files(&self.entry_files)
+            .with_usage_only_files(usage_only.clone())
+            .with_directory_configs(&directory_configs)
+            .with_cycle_detection(self.detect_cycles)
+            .with_framework_naming(self.framework_naming)
+            .with_path_mappings(self.path_mappings());
+        let mut total_lines = 0u64;
+        let mut timings = Vec::with_capacity(parsed.len());
+        let mut parse_errors = Vec::new();
+        let mut content_hashes = HashMap::with_capacity(parsed.len());
+
+        for (file_path, result, duration) in parsed {
+            timings.push(FileTiming {
+                path: file_path.to_string_lossy().to_string(),
+                duration,
+            });
+
+            match result {
+                Ok((node, hash, cached)) => {
+                    content_hashes.insert(file_path.clone(), hash);
+                    if cached {
+                        stats.files_cached += 1;
+                    } else {
+                        stats.files_parsed += 1;
+                    }
+                    if self.cache_mode != CacheMode::Disabled {
+                        updated_cache.insert(hash, node.clone());
+                    }
+                    stats.parse_errors += node.parse_errors.len() as u32;
+                    parse_errors.extend(node.parse_errors.iter().map(|e| ParseErrorInfo {
+                        path: self.relative_path(file_path),
+                        line: e.line,
+                        message: e.message.clone(),
+                    }));
+                    if !usage_only.contains(file_path) {
+                        total_lines += node.line_count as u64;
+                    }
+                    graph.add_node(node);
+                }
+                Err(e) => {
+                    stats.files_failed += 1;
+                    tracing::warn!("Failed to analyze {:?}: {}", file_path, e);
+                    parse_errors.push(ParseErrorInfo {
+                        path: self.relative_path(file_path),
+                        line: 0,
+                        message: e.to_string(),
+                    });
+                }
+            }
+        }
+        stats.graph = graph_start.elapsed();
+
+        if self.cache_mode != CacheMode::Disabled {
+            if let Err(e) = updated_cache.save(&self.root) {
+                tracing::warn!("Failed to save graph cache: {}", e);
+            }
+        }
+
+        timings.sort_by_key(|timing| std::cmp::Reverse(timing.duration));
+        timings.truncate(SLOWEST_FILES_REPORTED);
+        stats.slowest_files = timings;
+
+        parse_errors.sort_by(|a, b| a.path.cmp(&b.path).then(a.line.cmp(&b.line)));
+
+        Ok(BuildOutput {
+            graph,
+            total_files,
+            total_lines,
+            stats,
+            parse_errors,
+            ignore_warnings,
+            skipped_files,
+            content_hashes,
+            directory_configs,
+        })
+    }
+
+    /// This scanner's settings, the base that `clrd.toml` files override
+    fn base_config(&self) -> ClrConfig {
+        ClrConfig {
+            extensions: self.extensions.clone(),
+            ignore_patterns: self.ignore_patterns.clone(),
+            include_tests: self.include_tests,
+            confidence_threshold: self.confidence_threshold,
+            entry_point_globs: self.entry_point_globs.clone(),
+            test_file_globs: self.test_file_globs.clone(),
+            public_api_globs: self.public_api_globs.clone(),
+            always_used_exports: self.always_used_exports.clone(),
+            generated_markers: self.generated_markers.clone(),
+            ..ClrConfig::default()
+        }
+    }
+
+    /// Settings of every directory below the roots that holds a `clrd.toml`
+    ///
+    /// The main root's own `clrd.toml` is not read here: callers merge it
+    /// into the settings they give the scanner, under any explicit flags.
+    fn directory_configs(&self) -> Result<DirectoryConfigs> {
+        let root_config = self.root.join(CONFIG_FILE);
+        let mut files = Vec::new();
+        for root in std::iter::once(&self.root).chain(&self.additional_roots) {
+            files.extend(
+                FileWalker::new(root)
+                    .with_ignore_patterns(&self.ignore_patterns)
+                    .collect_config_files()
+                    .into_iter()
+                    .filter(|file| *file != root_config),
+            );
+        }
+        files.sort();
+        files.dedup();
+        DirectoryConfigs::from_files(&self.base_config(), files)
+    }
+
+    /// tsconfig import mappings of every root, following project references
+    fn path_mappings(&self) -> PathMappings {
+        let mut mappings = PathMappings::load(&self.root);
+        for root in &self.additional_roots {
+            mappings.extend(PathMappings::load(root));
+        }
+        mappings
+    }
+
+    /// Fail once the cancel token has been set
+    fn check_cancelled(&self) -> Result<()> {
+        if self
+            .cancel
+            .as_ref()
+            .is_some_and(|token| token.load(Ordering::Relaxed))
+        {
+            anyhow::bail!("Scan cancelled");
+        }
+        Ok(())
+    }
+
+    fn start_phase(&self, phase: ScanPhase, total: Option<u64>) {
+        if let Some(progress) = &self.progress {
+            progress.start_phase(phase, total);
+        }
+    }
+
+    fn file_done(&self) {
+        if let Some(progress) = &self.progress {
+            progress.file_done();
+        }
+    }
+
+    fn relative_path(&self, path: &Path) -> String {
+        relative_to(path, owning_root(path, &self.root, &self.additional_roots))
+    }
+}
+
+/// Result of the collection and parsing phases
+struct BuildOutput {
+    graph: ReferenceGraph,
+    total_files: u32,
+    total_lines: u64,
+    stats: ScanStats,
+    parse_errors: Vec<ParseErrorInfo>,
+    ignore_warnings: Vec<String>,
+    skipped_files: Vec<SkippedFile>,
+    content_hashes: HashMap<PathBuf, u64>,
+    directory_configs: DirectoryConfigs,
+}
+
+/// Lines at the top of a file searched for a generated marker
+const GENERATED_HEADER_LINES: usize = 5;
+
+/// Whether a generated marker appears in the file's first lines
+fn is_generated(source: &str, markers: &[String]) -> bool {
+    source
+        .lines()
+        .take(GENERATED_HEADER_LINES)
+        .any(|line| markers.iter().any(|marker| line.contains(marker.as_str())))
+}
+
+/// Keep the `max_per_file` most confident findings of each file, in their
+/// original order, and count the rest per file under its `label`
+///
+/// Files are told apart by absolute path, since two roots can hold the same
+/// relative path; `label` should keep them apart too.
+fn cap_per_file(
+    dead_code: &mut Vec<DeadCodeItem>,
+    max_per_file: usize,
+    label: impl Fn(&Path) -> String,
+) -> BTreeMap<String, u32> {
+    let mut order: Vec<usize> = (0..dead_code.len()).collect();
+    order.sort_by(|&a, &b| dead_code[b].confidence.total_cmp(&dead_code[a].confidence));
+
+    let mut kept: HashMap<&Path, usize> = HashMap::new();
+    let mut keep = vec![false; dead_code.len()];
+    let mut suppressed = BTreeMap::new();
+    for i in order {
+        let path = dead_code[i].file_path.as_path();
+        let count = kept.entry(path).or_default();
+        if *count < max_per_file {
+            *count += 1;
+            keep[i] = true;
+        } else {
+            *suppressed.entry(label(path)).or_default() += 1;
+        }
+    }
+
+    let mut keep = keep.into_iter();
+    dead_code.retain(|_| keep.next().unwrap_or(false));
+    suppressed
+}
+
+/// When a file last changed: its last git commit, else its modification time
+fn last_changed(path: &Path) -> Option<SystemTime> {
+    let committed = Command::new("git")
+        .args(["log", "-1", "--format=%ct", "--"])
+        .arg(path)
+        .current_dir(path.parent()?)
+        .output()
+        .ok()
+        .filter(|output| output.status.success())
+        .and_then(|output| String::from_utf8(output.stdout).ok()?.trim().parse().ok())
+        .map(|seconds| UNIX_EPOCH + Duration::from_secs(seconds));
+    committed.or_else(|| {
+        std::fs::metadata(path)
+            .and_then(|meta| meta.modified())
+            .ok()
+    })
 }
 
 /// Simple timestamp without chrono dependency
@@ -166,4 +1010,842 @@ mod tests {
         assert!(scanner.include_tests);
         assert_eq!(scanner.extensions, vec!["ts".to_string()]);
     }
+
+    #[test]
+    fn test_scan_blocking_without_runtime() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("utils.ts"), "export const unused = 1;\n").unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_confidence_threshold(0.0)
+            .scan_blocking()
+            .unwrap();
+
+        assert_eq!(output.total_files_scanned, 1);
+        assert!(output.dead_code.iter().any(|item| item.name == "unused"));
+    }
+
+    #[tokio::test]
+    async fn test_scan_stats() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.ts"), "export const a = 1;\n").unwrap();
+        std::fs::write(
+            dir.path().join("b.ts"),
+            "import { a } from './a';\nexport const b = a;\n",
+        )
+        .unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_stats(true)
+            .scan()
+            .await
+            .unwrap();
+        let stats = output.stats.unwrap();
+
+        assert!(stats.walk > std::time::Duration::ZERO);
+        assert!(stats.parse > std::time::Duration::ZERO);
+        assert!(stats.graph > std::time::Duration::ZERO);
+        assert!(stats.detect > std::time::Duration::ZERO);
+        assert_eq!(stats.files_parsed, 2);
+        assert_eq!(stats.files_failed, 0);
+        assert_eq!(stats.slowest_files.len(), 2);
+    }
+
+    #[tokio::test]
+    async fn test_scan_collects_parse_errors() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("good.ts"), "export const a = 1;\n").unwrap();
+        std::fs::write(
+            dir.path().join("bad.ts"),
+            "export const b = 1;\nexport function oops( {\n",
+        )
+        .unwrap();
+
+        let output = Scanner::new(dir.path()).scan().await.unwrap();
+
+        assert!(!output.parse_errors.is_empty());
+        assert!(output.parse_errors.iter().all(|e| e.path == "bad.ts"));
+        assert_eq!(output.summary.parse_errors, 1);
+    }
+
+    #[tokio::test]
+    async fn test_custom_entry_point_glob_prevents_zombie() {
+        let dir = tempfile::tempdir().unwrap();
+        let routes = dir.path().join("src/routes/blog");
+        std::fs::create_dir_all(&routes).unwrap();
+        std::fs::write(routes.join("+page.ts"), "export const load = () => ({});\n").unwrap();
+
+        let is_zombie = |output: &ScanOutput| {
+            output
+                .dead_code
+                .iter()
+                .any(|item| item.kind == DeadCodeKind::ZombieFile)
+        };
+
+        let output = Scanner::new(dir.path())
+            .with_entry_point_globs(vec!["**/index.*".into()])
+            .scan()
+            .await
+            .unwrap();
+        assert!(is_zombie(&output));
+
+        let output = Scanner::new(dir.path())
+            .with_entry_point_globs(vec!["**/+page.ts".into()])
+            .scan()
+            .await
+            .unwrap();
+        assert!(!is_zombie(&output));
+    }
+
+    #[tokio::test]
+    async fn test_finding_ids_survive_line_shifts() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("utils.ts");
+        std::fs::write(&file, "export const unused = 1;\n").unwrap();
+
+        let ids = |output: ScanOutput| {
+            let mut ids: Vec<_> = output.dead_code.into_iter().map(|item| item.id).collect();
+            ids.sort();
+            ids
+        };
+
+        let before = ids(Scanner::new(dir.path()).scan().await.unwrap());
+        std::fs::write(&file, "// header\n\nexport const unused = 1;\n").unwrap();
+        let after = ids(Scanner::new(dir.path()).scan().await.unwrap());
+
+        assert!(!before.is_empty());
+        assert_eq!(before, after);
+    }
+
+    #[tokio::test]
+    async fn test_confidence_threshold_filters_findings() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("orphan.ts"), "export const orphan = 1;\n").unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_confidence_threshold(0.8)
+            .scan()
+            .await
+            .unwrap();
+
+        assert!(!output.dead_code.is_empty());
+        assert!(output.dead_code.iter().all(|item| item.confidence >= 0.8));
+        assert!(!output
+            .dead_code
+            .iter()
+            .any(|item| item.kind == DeadCodeKind::ZombieFile));
+        assert_eq!(output.summary.zombie_files, 0);
+        assert_eq!(output.summary.total_issues, output.dead_code.len() as u32);
+    }
+
+    #[tokio::test]
+    async fn test_dead_code_ratio() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.ts"),
+            "export const used = 1;\nexport const unused = 2;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("b.ts"),
+            "import { used } from './a';\nconsole.log(used);\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("orphan.ts"),
+            "export const orphan = 1;\nexport const other = 2;\nconst local = 3;\n",
+        )
+        .unwrap();
+
+        let output = Scanner::new(dir.path()).scan().await.unwrap();
+
+        // a.ts line 2 plus all three lines of the zombie orphan.ts
+        assert_eq!(output.total_lines, 7);
+        assert_eq!(output.summary.files_with_issues, 2);
+        assert!((output.summary.dead_code_ratio - 4.0 / 7.0).abs() < 1e-9);
+    }
+
+    #[tokio::test]
+    async fn test_scan_explicit_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("a.ts"), "export const a = 1;\n").unwrap();
+        std::fs::write(dir.path().join("b.ts"), "export const b = 1;\n").unwrap();
+        std::fs::write(dir.path().join("notes.md"), "# notes\n").unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_files(vec![dir.path().join("a.ts"), dir.path().join("notes.md")])
+            .scan()
+            .await
+            .unwrap();
+
+        assert_eq!(output.total_files_scanned, 1);
+        assert!(output
+            .dead_code
+            .iter()
+            .all(|item| item.relative_path == "a.ts"));
+    }
+
+    #[tokio::test]
+    async fn test_max_results_truncates_by_confidence() {
+        let dir = tempfile::tempdir().unwrap();
+        // Unused exports (0.9, or 0.7 for handler-like names) and two zombies (0.7)
+        std::fs::write(
+            dir.path().join("orphan.ts"),
+            "export const a = 1;\nexport const b = 2;\nexport const c = 3;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("handlers.ts"),
+            "export const routeHandler = 1;\nexport const pluginHandler = 2;\nexport const d = 3;\n",
+        )
+        .unwrap();
+
+        let full = Scanner::new(dir.path()).scan().await.unwrap();
+        let output = Scanner::new(dir.path())
+            .with_max_results(Some(5))
+            .scan()
+            .await
+            .unwrap();
+
+        assert!(full.dead_code.len() > 5);
+        assert_eq!(output.dead_code.len(), 5);
+        assert!(output.truncated);
+        assert_eq!(output.total_findings, full.dead_code.len() as u32);
+        assert_eq!(output.summary.total_issues, full.summary.total_issues);
+        assert!(output
+            .dead_code
+            .windows(2)
+            .all(|pair| pair[0].confidence >= pair[1].confidence));
+        assert!(!full.truncated);
+    }
+
+    #[tokio::test]
+    async fn test_incremental_scan_reparses_changed_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("a.ts"),
+            "export const a = 1;\nexport const b = 2;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("b.ts"),
+            "import { a } from './a';\nexport const c = a;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("c.ts"),
+            "import { c } from './b';\nconsole.log(c);\n",
+        )
+        .unwrap();
+
+        let scanner = Scanner::new(dir.path())
+            .with_cache_mode(CacheMode::Incremental)
+            .with_stats(true);
+        let unused_exports = |output: &ScanOutput| {
+            let mut names: Vec<_> = output
+                .dead_code
+                .iter()
+                .filter(|item| item.kind == DeadCodeKind::UnusedExport)
+                .map(|item| item.name.clone())
+                .collect();
+            names.sort();
+            names
+        };
+
+        let first = scanner.scan().await.unwrap();
+        let stats = first.stats.as_ref().unwrap();
+        assert_eq!((stats.files_parsed, stats.files_cached), (3, 0));
+        assert_eq!(unused_exports(&first), vec!["b"]);
+        assert!(dir.path().join(CACHE_FILE).exists());
+
+        // b.ts now imports `b` as well
+        std::fs::write(
+            dir.path().join("b.ts"),
+            "import { a, b } from './a';\nexport const c = a + b;\n",
+        )
+        .unwrap();
+
+        let second = scanner.scan().await.unwrap();
+        let stats = second.stats.as_ref().unwrap();
+        assert_eq!((stats.files_parsed, stats.files_cached), (1, 2));
+        assert!(unused_exports(&second).is_empty());
+    }
+
+    #[tokio::test]
+    async fn test_scan_multiple_roots() {
+        let dir = tempfile::tempdir().unwrap();
+        let web = dir.path().join("web");
+        let api = dir.path().join("api");
+        std::fs::create_dir_all(&web).unwrap();
+        std::fs::create_dir_all(&api).unwrap();
+        std::fs::write(
+            web.join("shared.ts"),
+            "export const used = 1;\nexport const stale = 2;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            api.join("handler.ts"),
+            "import { used } from '../web/shared';\nexport const orphan = used;\n",
+        )
+        .unwrap();
+        // The same relative path in both roots
+        for root in [&web, &api] {
+            std::fs::write(root.join("util.ts"), "export const x = 1;\n").unwrap();
+        }
+
+        let output = Scanner::new(&web)
+            .with_additional_roots(vec![api.clone()])
+            .scan()
+            .await
+            .unwrap();
+
+        let mut exports: Vec<_> = output
+            .dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedExport)
+            .map(|item| (item.relative_path.as_str(), item.name.as_str()))
+            .collect();
+        exports.sort();
+
+        // `used` is imported across roots
+        assert_eq!(output.total_files_scanned, 4);
+        assert_eq!(
+            exports,
+            vec![
+                ("handler.ts", "orphan"),
+                ("shared.ts", "stale"),
+                ("util.ts", "x"),
+                ("util.ts", "x")
+            ]
+        );
+
+        let utils: Vec<_> = output
+            .dead_code
+            .iter()
+            .filter(|item| item.relative_path == "util.ts")
+            .collect();
+        assert!(utils.iter().any(|item| item.file_path.starts_with(&web)));
+        assert!(utils.iter().any(|item| item.file_path.starts_with(&api)));
+        let ids: HashSet<&str> = output
+            .dead_code
+            .iter()
+            .map(|item| item.id.as_str())
+            .collect();
+        assert_eq!(ids.len(), output.dead_code.len());
+    }
+
+    #[tokio::test]
+    async fn test_health_score() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "import { add } from './math';\nconsole.log(add(1, 2));\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("math.ts"),
+            "export function add(a: number, b: number) {\n  return a + b;\n}\n",
+        )
+        .unwrap();
+
+        let clean = Scanner::new(dir.path()).scan().await.unwrap();
+        assert_eq!(clean.summary.total_issues, 0);
+        assert_eq!(clean.summary.health_score, 100);
+        assert_eq!(clean.summary.health_grade, "A");
+
+        // A dozen unused exports in a tiny file
+        let messy: String = (0..12)
+            .map(|i| format!("export const unused{} = {};\n", i, i))
+            .collect();
+        std::fs::write(dir.path().join("messy.ts"), messy).unwrap();
+        std::fs::write(
+            dir.path().join("index.ts"),
+            "import { add } from './math';\nimport { unused0 } from './messy';\nconsole.log(add(1, 2), unused0);\n",
+        )
+        .unwrap();
+
+        let messy = Scanner::new(dir.path()).scan().await.unwrap();
+        assert!(messy.summary.health_score < 10);
+        assert_eq!(messy.summary.health_grade, "F");
+    }
+
+    #[test]
+    fn test_health_grade_boundaries() {
+        let grades: Vec<_> = [100, 90, 89, 80, 79, 70, 69, 60, 59, 0]
+            .into_iter()
+            .map(health_grade)
+            .collect();
+        assert_eq!(grades, ["A", "A", "B", "B", "C", "C", "D", "D", "F", "F"]);
+    }
+
+    #[tokio::test]
+    async fn test_scan_without_snippets() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("utils.ts"), "export const unused = 1;\n").unwrap();
+
+        let full = Scanner::new(dir.path()).scan().await.unwrap();
+        assert!(full
+            .dead_code
+            .iter()
+            .all(|item| !item.code_snippet.is_empty()));
+        assert!(serde_json::to_string(&full)
+            .unwrap()
+            .contains("code_snippet"));
+
+        let slim = Scanner::new(dir.path())
+            .with_snippets(false)
+            .with_context(false)
+            .scan()
+            .await
+            .unwrap();
+        assert_eq!(slim.dead_code.len(), full.dead_code.len());
+        assert!(slim
+            .dead_code
+            .iter()
+            .all(|item| item.code_snippet.is_empty() && item.context.is_none()));
+        let json = serde_json::to_string(&slim).unwrap();
+        assert!(!json.contains("code_snippet"));
+        assert!(!json.contains("\"context\""));
+    }
+
+    #[tokio::test]
+    async fn test_cancelled_scan_returns_early() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..500 {
+            std::fs::write(
+                dir.path().join(format!("module{}.ts", i)),
+                format!(
+                    "import {{ value }} from './module{}';\nexport const value = 1;\n",
+                    i + 1
+                ),
+            )
+            .unwrap();
+        }
+
+        let token = Arc::new(AtomicBool::new(true));
+        let error = Scanner::new(dir.path())
+            .with_cancel_token(token.clone())
+            .scan()
+            .await
+            .unwrap_err();
+        assert_eq!(error.to_string(), "Scan cancelled");
+
+        token.store(false, Ordering::Relaxed);
+        let output = Scanner::new(dir.path())
+            .with_cancel_token(token)
+            .scan()
+            .await
+            .unwrap();
+        assert_eq!(output.total_files_scanned, 500);
+    }
+
+    #[tokio::test]
+    async fn test_test_file_imports_count_as_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("math.ts"),
+            "export function add(a: number, b: number) { return a + b; }\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("math.test.ts"),
+            "import { add } from './math';\nexport const unusedFixture = 1;\nadd(1, 2);\n",
+        )
+        .unwrap();
+
+        let scanner = Scanner::new(dir.path()).with_confidence_threshold(0.0);
+        let without = scanner.scan().await.unwrap();
+        assert!(without.dead_code.iter().any(|item| item.name == "add"));
+
+        let with = scanner.consider_tests_usage(true).scan().await.unwrap();
+        assert!(with.dead_code.is_empty(), "{:?}", with.dead_code);
+        assert_eq!(with.total_files_scanned, 1);
+    }
+
+    #[tokio::test]
+    async fn test_nested_config_raises_threshold_beneath_it() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::create_dir_all(dir.path().join("legacy")).unwrap();
+        std::fs::write(dir.path().join("util.ts"), "export const unusedA = 1;\n").unwrap();
+        std::fs::write(
+            dir.path().join("legacy/util.ts"),
+            "export const unusedB = 1;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("legacy/clrd.toml"),
+            "confidence_threshold = 0.99\n",
+        )
+        .unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_confidence_threshold(0.5)
+            .scan()
+            .await
+            .unwrap();
+
+        assert!(output.dead_code.iter().any(|item| item.name == "unusedA"));
+        assert!(
+            output
+                .dead_code
+                .iter()
+                .all(|item| !item.relative_path.starts_with("legacy")),
+            "{:?}",
+            output.dead_code
+        );
+    }
+
+    #[tokio::test]
+    async fn test_min_file_age_skips_recent_files() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("old.ts"), "export const stale = 1;\n").unwrap();
+        std::fs::write(dir.path().join("new.ts"), "export const fresh = 1;\n").unwrap();
+        let month_ago = SystemTime::now() - Duration::from_secs(30 * 24 * 60 * 60);
+        std::fs::File::options()
+            .write(true)
+            .open(dir.path().join("old.ts"))
+            .unwrap()
+            .set_modified(month_ago)
+            .unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_confidence_threshold(0.0)
+            .with_min_file_age(Some(Duration::from_secs(7 * 24 * 60 * 60)))
+            .scan()
+            .await
+            .unwrap();
+
+        assert!(output.dead_code.iter().any(|item| item.name == "stale"));
+        assert!(output.dead_code.iter().all(|item| item.name != "fresh"));
+    }
+
+    #[tokio::test]
+    async fn test_generated_files_are_not_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("api.gen.ts"),
+            "// @generated by openapi-codegen\nimport { helper } from './helper';\nexport const unusedClient = helper;\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("helper.ts"), "export const helper = 1;\n").unwrap();
+        std::fs::write(
+            dir.path().join("other.ts"),
+            "export const unusedOther = 1;\n",
+        )
+        .unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_confidence_threshold(0.0)
+            .scan()
+            .await
+            .unwrap();
+
+        let names: Vec<_> = output
+            .dead_code
+            .iter()
+            .map(|item| item.name.as_str())
+            .collect();
+        assert!(names.contains(&"unusedOther"), "{:?}", names);
+        assert!(!names.contains(&"unusedClient"), "{:?}", names);
+        assert!(!names.contains(&"helper"), "{:?}", names);
+        assert!(output
+            .dead_code
+            .iter()
+            .all(|item| item.relative_path != "api.gen.ts"));
+        assert_eq!(output.total_files_scanned, 2);
+    }
+
+    #[derive(Default)]
+    struct CountingSink {
+        phases: std::sync::Mutex<Vec<(ScanPhase, Option<u64>, u64)>>,
+    }
+
+    impl ProgressSink for CountingSink {
+        fn start_phase(&self, phase: ScanPhase, total: Option<u64>) {
+            self.phases.lock().unwrap().push((phase, total, 0));
+        }
+
+        fn file_done(&self) {
+            if let Some((_, _, done)) = self.phases.lock().unwrap().last_mut() {
+                *done += 1;
+            }
+        }
+    }
+
+    #[tokio::test]
+    async fn test_progress_counts_every_file() {
+        let dir = tempfile::tempdir().unwrap();
+        for i in 0..5 {
+            std::fs::write(dir.path().join(format!("f{i}.ts")), "export const x = 1;\n").unwrap();
+        }
+        let sink = Arc::new(CountingSink::default());
+
+        Scanner::new(dir.path())
+            .with_progress(sink.clone())
+            .scan()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            *sink.phases.lock().unwrap(),
+            vec![
+                (ScanPhase::Collect, None, 0),
+                (ScanPhase::Read, Some(5), 5),
+                (ScanPhase::Parse, Some(5), 5),
+                (ScanPhase::Detect, None, 0),
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_resolves_alias_from_referenced_project() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        std::fs::create_dir_all(root.join("packages/shared/src")).unwrap();
+        std::fs::create_dir_all(root.join("packages/app/src")).unwrap();
+        std::fs::write(
+            root.join("tsconfig.json"),
+            r#"{ "files": [], "references": [{ "path": "./packages/shared" }] }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("packages/shared/tsconfig.json"),
+            r#"{ "compilerOptions": { "paths": { "@shared/*": ["./src/*"] } } }"#,
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("packages/shared/src/format.ts"),
+            "export const formatDate = 1;\nexport const unusedFormat = 2;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            root.join("packages/app/src/main.ts"),
+            "import { formatDate } from '@shared/format';\nconsole.log(formatDate);\n",
+        )
+        .unwrap();
+
+        let output = Scanner::new(root)
+            .with_confidence_threshold(0.0)
+            .scan()
+            .await
+            .unwrap();
+
+        let names: Vec<_> = output
+            .dead_code
+            .iter()
+            .map(|item| item.name.as_str())
+            .collect();
+        assert!(names.contains(&"unusedFormat"), "{:?}", names);
+        assert!(!names.contains(&"formatDate"), "{:?}", names);
+        assert!(
+            output
+                .dead_code
+                .iter()
+                .all(|item| item.kind != DeadCodeKind::ZombieFile),
+            "{:?}",
+            output.dead_code
+        );
+    }
+
+    #[test]
+    fn test_scan_source_runs_local_detectors() {
+        let source = "import { a, b } from './lib';\n\
+                      let last = 0;\n\
+                      export function run() {\n\
+                      \x20 last = a();\n\
+                      }\n";
+        let scanner = Scanner::new("/project").with_confidence_threshold(0.0);
+        let dead_code = scanner.scan_source("src/app.ts", source).unwrap();
+
+        let found: Vec<(DeadCodeKind, &str)> = dead_code
+            .iter()
+            .map(|item| (item.kind, item.name.as_str()))
+            .collect();
+        assert_eq!(
+            found,
+            vec![
+                (DeadCodeKind::UnusedImport, "b"),
+                (DeadCodeKind::UnusedVariable, "last"),
+            ]
+        );
+        assert_eq!(dead_code[0].relative_path, "src/app.ts");
+        assert_eq!(dead_code[1].code_snippet, "let last = 0;");
+    }
+
+    #[tokio::test]
+    async fn test_scan_reports_ignore_pattern_warnings() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.ts"), "export const x = 1;\n").unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_ignore_patterns(vec!["**/vendor/**".into(), "{bad".into()])
+            .scan()
+            .await
+            .unwrap();
+
+        assert_eq!(output.ignore_warnings.len(), 2);
+        assert!(output.ignore_warnings[0].starts_with("Invalid ignore pattern `{bad`"));
+        assert_eq!(
+            output.ignore_warnings[1],
+            "Ignore pattern `**/vendor/**` matched no files"
+        );
+    }
+
+    #[tokio::test]
+    async fn test_estimated_removal_counts_overlapping_spans_once() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("lib.ts"),
+            "export const used = 1;\n\
+             export function dead() {\n\
+             \x20 return 2;\n\
+             }\n\
+             export const a = 1, b = 2;\n\
+             let stale = 0;\n\
+             stale = 1;\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("main.ts"),
+            "import { used } from './lib';\nimport { noop } from 'pkg';\nconsole.log(used);\n",
+        )
+        .unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_confidence_threshold(0.0)
+            .scan()
+            .await
+            .unwrap();
+
+        // `dead` (2-4), `a` and `b` (both 5) and the `noop` import; the
+        // write-only `stale` is below 80%
+        assert_eq!(
+            output.summary.estimated_removal,
+            RemovalEstimate { lines: 5, files: 2 }
+        );
+    }
+
+    #[tokio::test]
+    async fn test_import_of_unscanned_extension_is_unresolved() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(
+            dir.path().join("app.ts"),
+            "import { helper } from './helper';\n\
+             import './styles.css';\n\
+             import { used } from './used';\n\
+             helper(used);\n",
+        )
+        .unwrap();
+        std::fs::write(
+            dir.path().join("helper.js"),
+            "export function helper() {}\n",
+        )
+        .unwrap();
+        std::fs::write(dir.path().join("used.ts"), "export const used = 1;\n").unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_extensions(vec!["ts".into()])
+            .scan()
+            .await
+            .unwrap();
+
+        assert_eq!(
+            output.unresolved_imports,
+            vec![UnresolvedImport {
+                path: "app.ts".into(),
+                line: 1,
+                source: "./helper".into(),
+            }]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_oversized_file_is_skipped() {
+        let dir = tempfile::tempdir().unwrap();
+        std::fs::write(dir.path().join("app.ts"), "export const x = 1;\n").unwrap();
+        let bundle = "export const big = 1;\n".repeat(100);
+        std::fs::write(dir.path().join("bundle.js"), &bundle).unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_confidence_threshold(0.0)
+            .with_max_file_bytes(1024)
+            .scan()
+            .await
+            .unwrap();
+
+        assert_eq!(output.total_files_scanned, 1);
+        assert_eq!(output.summary.skipped_files, 1);
+        assert_eq!(
+            output.skipped_files,
+            vec![SkippedFile {
+                path: "bundle.js".into(),
+                bytes: bundle.len() as u64,
+            }]
+        );
+        assert!(output
+            .dead_code
+            .iter()
+            .all(|item| item.relative_path != "bundle.js"));
+    }
+
+    #[tokio::test]
+    async fn test_max_per_file_caps_noisy_file() {
+        let dir = tempfile::tempdir().unwrap();
+        let noisy: String = (0..50)
+            .map(|i| format!("export const value{} = {};\n", i, i))
+            .collect();
+        std::fs::write(dir.path().join("noisy.ts"), noisy).unwrap();
+        std::fs::write(dir.path().join("quiet.ts"), "export const lonely = 1;\n").unwrap();
+
+        let output = Scanner::new(dir.path())
+            .with_max_per_file(Some(10))
+            .scan()
+            .await
+            .unwrap();
+
+        let count = |path: &str| {
+            output
+                .dead_code
+                .iter()
+                .filter(|item| item.relative_path == path)
+                .count()
+        };
+        assert_eq!(count("noisy.ts"), 10);
+        assert!(count("quiet.ts") > 0);
+        // 50 unused exports and the zombie file itself
+        assert_eq!(output.summary.suppressed_per_file["noisy.ts"], 41);
+        assert!(!output.summary.suppressed_per_file.contains_key("quiet.ts"));
+        assert!(output.truncated);
+        assert_eq!(output.total_findings as usize, output.dead_code.len() + 41);
+    }
+
+    #[tokio::test]
+    async fn test_max_per_file_counts_each_root_separately() {
+        let dir = tempfile::tempdir().unwrap();
+        let (web, api) = (dir.path().join("web"), dir.path().join("api"));
+        for root in [&web, &api] {
+            std::fs::create_dir_all(root).unwrap();
+            std::fs::write(
+                root.join("util.ts"),
+                "export const a = 1;\nexport const b = 2;\nexport const c = 3;\n",
+            )
+            .unwrap();
+        }
+
+        let output = Scanner::new(&web)
+            .with_additional_roots(vec![api.clone()])
+            .with_max_per_file(Some(1))
+            .scan()
+            .await
+            .unwrap();
+
+        // Three unused exports and the zombie file, one kept, in each root
+        assert_eq!(output.dead_code.len(), 2);
+        let suppressed = &output.summary.suppressed_per_file;
+        assert_eq!(suppressed["util.ts"], 3);
+        assert_eq!(
+            suppressed[&format!("..{0}api{0}util.ts", std::path::MAIN_SEPARATOR)],
+            3
+        );
+    }
 }
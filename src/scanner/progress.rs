@@ -0,0 +1,37 @@
+//! Progress reporting - Phase and per-file events for long scans
+//!
+//! The scanner only emits events; rendering (a progress bar, a log line)
+//! is up to the sink, so nothing here depends on a terminal.
+
+/// A stage of the scan pipeline
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ScanPhase {
+    /// Walking the file system
+    Collect,
+    /// Reading file contents
+    Read,
+    /// Parsing files into the reference graph
+    Parse,
+    /// Detecting dead code in the graph
+    Detect,
+}
+
+impl ScanPhase {
+    pub fn label(self) -> &'static str {
+        match self {
+            ScanPhase::Collect => "Collecting files",
+            ScanPhase::Read => "Reading files",
+            ScanPhase::Parse => "Parsing files",
+            ScanPhase::Detect => "Detecting dead code",
+        }
+    }
+}
+
+/// Receives scan progress; `file_done` is called from worker threads
+pub trait ProgressSink: Send + Sync {
+    /// A phase started; `total` is the number of files it processes, when known
+    fn start_phase(&self, phase: ScanPhase, total: Option<u64>);
+
+    /// One more file of the current phase is done
+    fn file_done(&self);
+}
@@ -3,11 +3,78 @@
 //! Builds a graph of all exports and imports across the codebase
 //! to identify unused exports and zombie files.
 
+use super::analyzer::is_declaration_file;
+use super::config::DirectoryConfigs;
+use super::file_walker::compile_globs;
+use super::local_detectors::{
+    self, code_snippet, find_type_only_imports, find_unused_imports, has_todo_near, is_write_only,
+    mark_work_in_progress, TODO_CONFIDENCE_PENALTY,
+};
+use super::source::read_source;
+use super::tsconfig::PathMappings;
 use crate::types::*;
 use anyhow::Result;
-use std::collections::{HashMap, HashSet};
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use rayon::prelude::*;
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fs;
-use std::path::{Path, PathBuf};
+use std::path::{Component, Path, PathBuf};
+
+/// Confidence of an unused export before context adjustments
+pub const EXPORT_BASE_CONFIDENCE: f64 = 0.9;
+
+/// Analyzed files from which a project counts as large: the more code, the
+/// more likely something reaches a symbol in a way imports don't show
+const LARGE_PROJECT_FILES: usize = 1000;
+
+/// Patterns classifying files and export names, compiled from one configuration
+#[derive(Debug, Clone)]
+struct FilePatterns {
+    /// Files never reported as zombies
+    entry_points: GlobSet,
+    /// Files treated as tests
+    test_files: GlobSet,
+    /// Files whose exports form the public API
+    public_api: GlobSet,
+    /// Export names that are never reported as unused
+    always_used_exports: GlobSet,
+}
+
+impl FilePatterns {
+    fn from_config(config: &ClrConfig) -> Self {
+        Self {
+            entry_points: compile_globs(&config.entry_point_globs),
+            test_files: compile_globs(&config.test_file_globs),
+            public_api: compile_globs(&config.public_api_globs),
+            always_used_exports: compile_name_globs(&config.always_used_exports),
+        }
+    }
+}
+
+impl Default for FilePatterns {
+    fn default() -> Self {
+        Self {
+            entry_points: compile_globs(&default_entry_point_globs()),
+            test_files: compile_globs(&default_test_file_globs()),
+            public_api: compile_globs(&default_public_api_globs()),
+            always_used_exports: GlobSet::empty(),
+        }
+    }
+}
+
+/// Compile export name patterns (exact or glob), skipping invalid ones
+fn compile_name_globs(names: &[String]) -> GlobSet {
+    let mut builder = GlobSetBuilder::new();
+    for name in names {
+        match Glob::new(name) {
+            Ok(glob) => {
+                builder.add(glob);
+            }
+            Err(e) => tracing::warn!("Invalid export name pattern {:?}: {}", name, e),
+        }
+    }
+    builder.build().unwrap_or_else(|_| GlobSet::empty())
+}
 
 /// Graph of all file references in the project
 pub struct ReferenceGraph {
@@ -17,6 +84,30 @@ pub struct ReferenceGraph {
     export_index: HashMap<String, Vec<PathBuf>>,
     /// Map from import source to files that import it
     import_index: HashMap<String, Vec<PathBuf>>,
+    /// File and export patterns from the scan's own settings
+    patterns: FilePatterns,
+    /// Patterns from nested `clrd.toml` files, deepest directory first
+    directory_patterns: Vec<(PathBuf, FilePatterns)>,
+    /// Other scanned roots; files under them are reported relative to them
+    additional_roots: Vec<PathBuf>,
+    /// Whether findings carry a code snippet
+    snippets: bool,
+    /// Lines shown around each snippet
+    snippet_context: usize,
+    /// Longest snippet before it is cut (0 for no limit)
+    snippet_max_lines: usize,
+    /// Explicit entry files; when set, only files reachable from them are used
+    entry_files: Vec<PathBuf>,
+    /// Files whose imports count as usage but which are never reported on
+    usage_only_files: HashSet<PathBuf>,
+    /// Extensions tried, in order, for imports that omit one
+    resolve_extensions: Vec<String>,
+    /// Whether import cycles are reported
+    detect_cycles: bool,
+    /// Whether hook- and HOC-like names lower export confidence
+    framework_naming: bool,
+    /// `paths` / `baseUrl` mappings for bare import specifiers
+    path_mappings: PathMappings,
 }
 
 impl ReferenceGraph {
@@ -25,7 +116,119 @@ impl ReferenceGraph {
             nodes: HashMap::new(),
             export_index: HashMap::new(),
             import_index: HashMap::new(),
+            patterns: FilePatterns::default(),
+            directory_patterns: Vec::new(),
+            additional_roots: Vec::new(),
+            snippets: true,
+            snippet_context: 0,
+            snippet_max_lines: default_snippet_max_lines(),
+            entry_files: Vec::new(),
+            usage_only_files: HashSet::new(),
+            resolve_extensions: default_extensions(),
+            detect_cycles: false,
+            framework_naming: true,
+            path_mappings: PathMappings::default(),
+        }
+    }
+
+    /// Set the patterns that mark entry-point files
+    pub fn with_entry_point_globs(mut self, patterns: &[String]) -> Self {
+        self.patterns.entry_points = compile_globs(patterns);
+        self
+    }
+
+    /// Set the patterns that mark test files
+    pub fn with_test_file_globs(mut self, patterns: &[String]) -> Self {
+        self.patterns.test_files = compile_globs(patterns);
+        self
+    }
+
+    /// Set the patterns that mark public API files
+    pub fn with_public_api_globs(mut self, patterns: &[String]) -> Self {
+        self.patterns.public_api = compile_globs(patterns);
+        self
+    }
+
+    /// Set export names (exact or glob) that are consumed outside the project
+    pub fn with_always_used_exports(mut self, names: &[String]) -> Self {
+        self.patterns.always_used_exports = compile_name_globs(names);
+        self
+    }
+
+    /// Classify files under each directory with its `clrd.toml` settings
+    ///
+    /// Entry point and public API patterns match paths relative to the
+    /// directory holding the `clrd.toml`.
+    pub fn with_directory_configs(mut self, configs: &DirectoryConfigs) -> Self {
+        self.directory_patterns = configs
+            .iter()
+            .map(|(dir, config)| (dir.to_path_buf(), FilePatterns::from_config(config)))
+            .collect();
+        self.directory_patterns
+            .sort_by_key(|(dir, _)| std::cmp::Reverse(dir.components().count()));
+        self
+    }
+
+    /// Set the other roots scanned alongside the main one
+    pub fn with_additional_roots(mut self, roots: &[PathBuf]) -> Self {
+        self.additional_roots = roots.to_vec();
+        self
+    }
+
+    /// Seed reachability analysis with these files
+    pub fn with_entry_files(mut self, files: &[PathBuf]) -> Self {
+        self.entry_files = files.to_vec();
+        self
+    }
+
+    /// Resolve extensionless imports with the scanned extensions, in order
+    pub fn with_resolve_extensions(mut self, extensions: &[String]) -> Self {
+        if !extensions.is_empty() {
+            self.resolve_extensions = extensions.to_vec();
         }
+        self
+    }
+
+    /// Report import cycles as `circular_import` findings
+    pub fn with_cycle_detection(mut self, detect: bool) -> Self {
+        self.detect_cycles = detect;
+        self
+    }
+
+    /// Lower confidence for exports named like React hooks (`useX`) or HOCs (`withX`)
+    pub fn with_framework_naming(mut self, enabled: bool) -> Self {
+        self.framework_naming = enabled;
+        self
+    }
+
+    /// Resolve bare imports through tsconfig `paths` and `baseUrl`
+    pub fn with_path_mappings(mut self, mappings: PathMappings) -> Self {
+        self.path_mappings = mappings;
+        self
+    }
+
+    /// Use these files' imports as usage without reporting findings in them
+    pub fn with_usage_only_files(mut self, files: HashSet<PathBuf>) -> Self {
+        self.usage_only_files = files;
+        self
+    }
+
+    /// Whether to read code snippets for findings
+    pub fn with_snippets(mut self, snippets: bool) -> Self {
+        self.snippets = snippets;
+        self
+    }
+
+    /// Lines of surrounding code included in each snippet
+    pub fn with_snippet_context(mut self, lines: usize) -> Self {
+        self.snippet_context = lines;
+        self
+    }
+
+    /// Cut snippets after this many lines (0 for no limit)
+    pub fn with_snippet_max_lines(mut self, lines: usize) -> Self {
+        self.snippet_max_lines = lines;
+        self
     }
 
     /// Add a file node to the graph
@@ -51,157 +254,232 @@ impl ReferenceGraph {
         self.nodes.insert(file_path, node);
     }
 
-    /// Find all dead code in the graph
+    /// Number of lines in an analyzed file
+    pub fn line_count(&self, file_path: &Path) -> u32 {
+        self.nodes.get(file_path).map_or(0, |node| node.line_count)
+    }
+
+    /// Find all dead code in the graph with at least the given confidence
     pub fn find_dead_code(
         &self,
         root: &Path,
         confidence_threshold: f64,
     ) -> Result<Vec<DeadCodeItem>> {
-        let mut dead_code = Vec::new();
+        let usage = UsageIndex::build(self);
+        // With explicit entries, imports from unreachable files already don't count
+        let live = usage
+            .reachable
+            .is_none()
+            .then(|| self.live_files(root, &usage));
+
+        // Detectors only read the graph, so each file is checked independently
+        let per_file = self
+            .nodes
+            .par_iter()
+            .filter(|(file_path, _)| !self.usage_only_files.contains(*file_path))
+            .map(|(file_path, node)| {
+                let root = owning_root(file_path, root, &self.additional_roots);
+                let mut dead_code = Vec::new();
+
+                // Find unused exports
+                dead_code.extend(self.find_unused_exports(file_path, node, root, &usage)?);
+
+                // Find exported functions only imported by unreachable files
+                if let Some(live) = &live {
+                    dead_code.extend(
+                        self.find_unreachable_functions(file_path, node, root, &usage, live)?,
+                    );
+                }
 
-        // Find unused exports
-        dead_code.extend(self.find_unused_exports(root, confidence_threshold)?);
+                // Find zombie files
+                dead_code.extend(self.find_zombie_file(file_path, node, root, &usage));
+
+                let relative_path = relative_to(file_path, root);
+                let snippet = |span: &CodeSpan| self.get_code_snippet(file_path, span);
+
+                // Find unused imports
+                dead_code.extend(find_unused_imports(
+                    file_path,
+                    &relative_path,
+                    node,
+                    &snippet,
+                )?);
+
+                // Find value imports that could be `import type`
+                dead_code.extend(find_type_only_imports(
+                    file_path,
+                    &relative_path,
+                    node,
+                    &snippet,
+                )?);
+
+                // Find variables that are assigned but never read
+                dead_code.extend(self.find_write_only_symbols(
+                    file_path,
+                    node,
+                    &relative_path,
+                    &usage,
+                )?);
+
+                // Find members of used enums that are never accessed
+                dead_code.extend(self.find_unused_enum_members(file_path, node, root, &usage)?);
+
+                // Find class fields that are assigned but never read
+                dead_code.extend(self.find_write_only_fields(file_path, node, root, &usage)?);
+
+                mark_work_in_progress(node, &mut dead_code);
+
+                dead_code.retain(|item| item.confidence >= confidence_threshold);
+                Ok(dead_code)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let mut dead_code = Self::dedup_findings(per_file.into_iter().flatten());
+
+        // Cycles are informational, so they skip the confidence threshold
+        if self.detect_cycles {
+            dead_code.extend(self.find_import_cycles(root));
+        }
 
-        // Find zombie files
-        dead_code.extend(self.find_zombie_files(root, confidence_threshold)?);
+        // Ids hash the relative path, which repeats across roots; findings
+        // under an additional root also hash that root, relative to `root`
+        for item in &mut dead_code {
+            let owner = owning_root(&item.file_path, root, &self.additional_roots);
+            if owner != root {
+                item.id = finding_id(&relative_to(owner, root), item.kind, &item.id);
+            }
+        }
 
-        // Find unused imports
-        dead_code.extend(self.find_unused_imports(root, confidence_threshold)?);
+        // Hash map iteration order varies between runs
+        dead_code.sort_by(|a, b| {
+            a.relative_path
+                .cmp(&b.relative_path)
+                .then(a.span.start.cmp(&b.span.start))
+                .then(a.id.cmp(&b.id))
+        });
 
         Ok(dead_code)
     }
 
-    /// Find exports that are never imported
-    fn find_unused_exports(
-        &self,
-        root: &Path,
-        _confidence_threshold: f64,
-    ) -> Result<Vec<DeadCodeItem>> {
-        let mut dead_code = Vec::new();
-
-        // Collect all imported names across the project
-        let mut imported_names: HashSet<String> = HashSet::new();
-        for node in self.nodes.values() {
-            for import in &node.imports {
-                imported_names.insert(import.name.clone());
-                if let Some(alias) = &import.alias {
-                    imported_names.insert(alias.clone());
-                }
-            }
-        }
-
-        // Check each export
-        for (file_path, node) in &self.nodes {
-            for export in &node.exports {
-                // Skip re-exports and wildcard exports
-                if export.is_reexport || export.name == "*" {
-                    continue;
-                }
-
-                // Skip default exports with higher scrutiny (they're often entry points)
-                if export.is_default {
-                    continue;
-                }
+    /// Merge findings reported more than once for the same symbol and location,
+    /// keeping the highest confidence and the union of their context
+    fn dedup_findings(items: impl IntoIterator<Item = DeadCodeItem>) -> Vec<DeadCodeItem> {
+        let mut unique: Vec<DeadCodeItem> = Vec::new();
+        // Relative paths repeat across roots; absolute ones don't
+        let mut seen: HashMap<(PathBuf, DeadCodeKind, String, u32), usize> = HashMap::new();
+
+        for item in items {
+            let key = (
+                item.file_path.clone(),
+                item.kind,
+                item.name.clone(),
+                item.span.start,
+            );
+            let Some(&index) = seen.get(&key) else {
+                seen.insert(key, unique.len());
+                unique.push(item);
+                continue;
+            };
 
-                // Check if this export is imported anywhere
-                let is_imported = self.is_export_used(file_path, &export.name);
-
-                if !is_imported {
-                    let code_snippet = self.get_code_snippet(file_path, &export.span)?;
-                    let relative_path = pathdiff::diff_paths(file_path, root)
-                        .unwrap_or_else(|| file_path.clone())
-                        .to_string_lossy()
-                        .to_string();
-
-                    // Determine confidence based on context
-                    let confidence = self.calculate_export_confidence(file_path, &export.name);
-
-                    dead_code.push(DeadCodeItem {
-                        file_path: file_path.clone(),
-                        relative_path,
-                        span: export.span,
-                        code_snippet,
-                        kind: DeadCodeKind::UnusedExport,
-                        name: export.name.clone(),
-                        reason: format!(
-                            "Export '{}' has 0 references in the codebase",
-                            export.name
-                        ),
-                        confidence,
-                        context: Some(DeadCodeContext {
-                            possibly_dynamic: self.might_be_dynamic_import(&export.name),
-                            in_test_file: self.is_test_file(file_path),
-                            public_api: self.is_public_api(file_path, root),
-                            partial_references: Vec::new(),
-                            doc_comment: None,
-                        }),
-                    });
+            let existing = &mut unique[index];
+            let context = match (existing.context.take(), item.context.clone()) {
+                (Some(mut a), Some(b)) => {
+                    a.merge(b);
+                    Some(a)
                 }
+                (a, b) => a.or(b),
+            };
+            if item.confidence > existing.confidence {
+                *existing = item;
             }
+            existing.context = context;
         }
 
-        Ok(dead_code)
+        unique
     }
 
-    /// Find files that are never imported
-    fn find_zombie_files(
+    /// Find exports of a file that are never imported
+    fn find_unused_exports(
         &self,
+        file_path: &Path,
+        node: &ReferenceNode,
         root: &Path,
-        _confidence_threshold: f64,
+        usage: &UsageIndex,
     ) -> Result<Vec<DeadCodeItem>> {
         let mut dead_code = Vec::new();
 
-        // Collect all imported file paths
-        let mut imported_files: HashSet<PathBuf> = HashSet::new();
-        for node in self.nodes.values() {
-            for import in &node.imports {
-                // Resolve the import source to a file path
-                if let Some(resolved) = self.resolve_import(&node.file_path, &import.source) {
-                    imported_files.insert(resolved);
-                }
+        for export in &node.exports {
+            // Skip re-exports, wildcard exports and allowlisted names
+            if export.is_reexport
+                || export.name == "*"
+                || self.is_always_used(file_path, &export.name)
+            {
+                continue;
             }
-        }
 
-        // Check each file
-        for (file_path, node) in &self.nodes {
-            // Skip entry points and config files
-            if self.is_likely_entry_point(file_path, root) {
+            // Check if this export is imported anywhere; default imports pick
+            // their own local name, so defaults match on `default` alone
+            let imported_name = if export.is_default {
+                "default"
+            } else {
+                export.name.as_str()
+            };
+            let is_imported = usage.is_export_used(file_path, imported_name);
+
+            // Reported as a write-only variable instead
+            if !is_imported && is_write_only(node, &export.name) {
                 continue;
             }
 
-            // Check if this file is imported
-            if !imported_files.contains(file_path) && !node.exports.is_empty() {
-                let relative_path = pathdiff::diff_paths(file_path, root)
-                    .unwrap_or_else(|| file_path.clone())
-                    .to_string_lossy()
-                    .to_string();
-
-                let confidence = if self.is_test_file(file_path) {
-                    0.3 // Lower confidence for test files
+            if !is_imported {
+                let code_snippet = self.get_code_snippet(file_path, &export.span)?;
+                let relative_path = relative_to(file_path, root);
+
+                // Determine confidence based on context
+                let confidence = self.calculate_export_confidence(file_path, &export.name);
+                let name = finding_name(file_path, export);
+
+                // Used in its own file, so only the `export` keyword is dead
+                let (kind, reason) = if node.internal_refs.contains(&export.name) {
+                    (
+                        DeadCodeKind::RedundantExport,
+                        format!(
+                            "Export '{}' is exported but only used internally; consider making it local",
+                            name
+                        ),
+                    )
                 } else {
-                    0.7
+                    let label = if export.is_async {
+                        "Async export"
+                    } else {
+                        "Export"
+                    };
+                    (
+                        DeadCodeKind::UnusedExport,
+                        format!("{} '{}' has 0 references in the codebase", label, name),
+                    )
                 };
 
                 dead_code.push(DeadCodeItem {
-                    file_path: file_path.clone(),
-                    relative_path: relative_path.clone(),
-                    span: CodeSpan {
-                        start: 1,
-                        end: 1,
-                        col_start: 0,
-                        col_end: 0,
-                    },
-                    code_snippet: format!("// Entire file: {}", relative_path),
-                    kind: DeadCodeKind::ZombieFile,
-                    name: relative_path,
-                    reason: "File is never imported by any other file in the project".to_string(),
+                    id: finding_id(&relative_path, kind, &name),
+                    file_path: file_path.to_path_buf(),
+                    relative_path,
+                    span: export.span,
+                    code_snippet,
+                    kind,
+                    severity: kind.severity(),
+                    name,
+                    reason,
                     confidence,
                     context: Some(DeadCodeContext {
-                        possibly_dynamic: true,
+                        possibly_dynamic: self.might_be_dynamic_import(&export.name)
+                            || self.is_dynamic_lookup(file_path, &export.name),
                         in_test_file: self.is_test_file(file_path),
                         public_api: self.is_public_api(file_path, root),
-                        partial_references: Vec::new(),
-                        doc_comment: None,
+                        partial_references: usage.importers_of(file_path, root),
+                        doc_comment: export.doc_comment.clone(),
+                        exports: Vec::new(),
+                        has_todo: false,
                     }),
                 });
             }
@@ -210,99 +488,344 @@ impl ReferenceGraph {
         Ok(dead_code)
     }
 
-    /// Find imports that are declared but never used
-    fn find_unused_imports(
+    /// Find exported functions whose every importer is unreachable from the
+    /// program's roots, so nothing live can call them
+    fn find_unreachable_functions(
         &self,
+        file_path: &PathBuf,
+        node: &ReferenceNode,
         root: &Path,
-        _confidence_threshold: f64,
+        usage: &UsageIndex,
+        live: &HashSet<PathBuf>,
     ) -> Result<Vec<DeadCodeItem>> {
         let mut dead_code = Vec::new();
+        if self.is_public_api(file_path, root) {
+            return Ok(dead_code);
+        }
 
-        for (file_path, node) in &self.nodes {
-            for import in &node.imports {
-                // Check if the imported name is used in the file
-                let name_to_check = import.alias.as_ref().unwrap_or(&import.name);
-
-                if !node.internal_refs.contains(name_to_check) && name_to_check != "*" {
-                    let code_snippet = self.get_code_snippet(file_path, &import.span)?;
-                    let relative_path = pathdiff::diff_paths(file_path, root)
-                        .unwrap_or_else(|| file_path.clone())
-                        .to_string_lossy()
-                        .to_string();
-
-                    // Type-only imports have lower confidence (might be used for type annotations)
-                    let confidence = if import.is_type_only { 0.6 } else { 0.9 };
-
-                    dead_code.push(DeadCodeItem {
-                        file_path: file_path.clone(),
-                        relative_path,
-                        span: import.span,
-                        code_snippet,
-                        kind: DeadCodeKind::UnusedImport,
-                        name: name_to_check.clone(),
-                        reason: format!(
-                            "Import '{}' from '{}' is never used in this file",
-                            name_to_check, import.source
-                        ),
-                        confidence,
-                        context: None,
-                    });
-                }
+        for export in &node.exports {
+            if export.kind != SymbolKind::Function
+                || export.is_reexport
+                || self.is_always_used(file_path, &export.name)
+            {
+                continue;
+            }
+            // Called from its own file, which is live
+            if live.contains(file_path) && node.internal_refs.contains(&export.name) {
+                continue;
+            }
+
+            let imported_name = if export.is_default {
+                "default"
+            } else {
+                export.name.as_str()
+            };
+            // Unimported exports are reported as unused exports instead
+            let Some(importers) = usage.importers_of_export(file_path, imported_name) else {
+                continue;
+            };
+            if importers.iter().any(|importer| live.contains(importer)) {
+                continue;
             }
+
+            let relative_path = relative_to(file_path, root);
+            let chain = importers
+                .iter()
+                .next()
+                .map(|importer| usage.import_chain(importer, root))
+                .unwrap_or_default();
+
+            let name = finding_name(file_path, export);
+            dead_code.push(DeadCodeItem {
+                id: finding_id(&relative_path, DeadCodeKind::UnreachableFunction, &name),
+                file_path: file_path.clone(),
+                relative_path,
+                span: export.span,
+                code_snippet: self.get_code_snippet(file_path, &export.span)?,
+                kind: DeadCodeKind::UnreachableFunction,
+                severity: DeadCodeKind::UnreachableFunction.severity(),
+                reason: format!(
+                    "Function '{}' is only imported by code no entry point reaches: {} ← {}",
+                    name,
+                    name,
+                    chain.join(" ← ")
+                ),
+                name,
+                confidence: 0.6,
+                context: Some(DeadCodeContext {
+                    possibly_dynamic: self.might_be_dynamic_import(&export.name),
+                    in_test_file: self.is_test_file(file_path),
+                    public_api: false,
+                    partial_references: usage.importers_of(file_path, root),
+                    doc_comment: export.doc_comment.clone(),
+                    exports: Vec::new(),
+                    has_todo: false,
+                }),
+            });
         }
 
         Ok(dead_code)
     }
 
-    /// Check if an export is used anywhere in the codebase
-    fn is_export_used(&self, export_file: &Path, export_name: &str) -> bool {
-        for (file_path, node) in &self.nodes {
-            if file_path == export_file {
+    /// Report a file that is never imported
+    fn find_zombie_file(
+        &self,
+        file_path: &PathBuf,
+        node: &ReferenceNode,
+        root: &Path,
+        usage: &UsageIndex,
+    ) -> Option<DeadCodeItem> {
+        // Skip entry points, config files and ambient declarations
+        if self.is_likely_entry_point(file_path, root) || is_declaration_file(file_path) {
+            return None;
+        }
+
+        // Check if this file is imported, or reached from an explicit entry
+        let used = match &usage.reachable {
+            Some(reachable) => reachable.contains(file_path),
+            None => usage.imported_files.contains(file_path),
+        };
+        if used || node.exports.is_empty() {
+            return None;
+        }
+
+        let relative_path = relative_to(file_path, root);
+
+        let base = if self.is_test_file(file_path) {
+            0.3 // Lower confidence for test files
+        } else {
+            0.7
+        };
+        let confidence = base + self.project_size_factor().map_or(0.0, |(_, delta)| delta);
+
+        Some(DeadCodeItem {
+            id: finding_id(&relative_path, DeadCodeKind::ZombieFile, &relative_path),
+            file_path: file_path.clone(),
+            relative_path: relative_path.clone(),
+            span: CodeSpan {
+                start: 1,
+                end: 1,
+                col_start: 0,
+                col_end: 0,
+                byte_start: 0,
+                byte_end: 0,
+            },
+            code_snippet: if self.snippets {
+                format!("// Entire file: {}", relative_path)
+            } else {
+                String::new()
+            },
+            kind: DeadCodeKind::ZombieFile,
+            severity: DeadCodeKind::ZombieFile.severity(),
+            name: relative_path,
+            reason: if usage.reachable.is_some() {
+                "File is not reachable from any entry point".to_string()
+            } else {
+                "File is never imported by any other file in the project".to_string()
+            },
+            confidence,
+            context: Some(DeadCodeContext {
+                possibly_dynamic: true,
+                in_test_file: self.is_test_file(file_path),
+                public_api: self.is_public_api(file_path, root),
+                partial_references: Vec::new(),
+                doc_comment: None,
+                exports: node.exports.iter().map(|e| e.name.clone()).collect(),
+                has_todo: false,
+            }),
+        })
+    }
+
+    /// Find `let`/`var` bindings of a file that are only ever assigned to
+    fn find_write_only_symbols(
+        &self,
+        file_path: &Path,
+        node: &ReferenceNode,
+        relative_path: &str,
+        usage: &UsageIndex,
+    ) -> Result<Vec<DeadCodeItem>> {
+        let exported = node
+            .exports
+            .iter()
+            .filter(|e| {
+                !e.is_reexport
+                    && !self.is_always_used(file_path, &e.name)
+                    && !usage.is_export_used(file_path, &e.name)
+            })
+            .map(|e| (&e.name, e.kind, &e.span));
+        let locals = node.locals.iter().map(|l| (&l.name, l.kind, &l.span));
+
+        local_detectors::write_only_symbols(
+            file_path,
+            relative_path,
+            node,
+            exported.chain(locals),
+            &|span| self.get_code_snippet(file_path, span),
+        )
+    }
+
+    /// Find instance fields only ever assigned through `this`
+    fn find_write_only_fields(
+        &self,
+        file_path: &Path,
+        node: &ReferenceNode,
+        root: &Path,
+        usage: &UsageIndex,
+    ) -> Result<Vec<DeadCodeItem>> {
+        let mut dead_code = Vec::new();
+
+        for field in &node.write_only_fields {
+            // `instance.field` elsewhere may read it; instance types aren't tracked
+            if usage.accessed_properties.contains(&field.name) {
                 continue;
             }
 
-            for import in &node.imports {
-                // Check if this import comes from the export file
-                if let Some(resolved) = self.resolve_import(file_path, &import.source) {
-                    if resolved == export_file && import.name == export_name {
-                        return true;
-                    }
+            let name = format!("{}.{}", field.class, field.name);
+            let code_snippet = self.get_code_snippet(file_path, &field.span)?;
+            let relative_path = relative_to(file_path, root);
+
+            // Serializers and reflection can read fields without naming them
+            dead_code.push(DeadCodeItem {
+                id: finding_id(&relative_path, DeadCodeKind::UnusedVariable, &name),
+                file_path: file_path.to_path_buf(),
+                relative_path,
+                span: field.span,
+                code_snippet,
+                kind: DeadCodeKind::UnusedVariable,
+                severity: DeadCodeKind::UnusedVariable.severity(),
+                reason: format!("Field '{}' is assigned but never read", name),
+                name,
+                confidence: 0.6,
+                context: None,
+            });
+        }
+
+        Ok(dead_code)
+    }
+
+    /// Find members of an enum in use that are never accessed as `Enum.Member`
+    fn find_unused_enum_members(
+        &self,
+        file_path: &Path,
+        node: &ReferenceNode,
+        root: &Path,
+        usage: &UsageIndex,
+    ) -> Result<Vec<DeadCodeItem>> {
+        let mut dead_code = Vec::new();
+
+        // Ambient enums describe values owned by someone else
+        if is_declaration_file(file_path) {
+            return Ok(dead_code);
+        }
+
+        for enum_symbol in &node.enums {
+            let name = &enum_symbol.name;
+            if self.is_always_used(file_path, name) {
+                continue;
+            }
+
+            // An enum nobody uses is reported whole, not member by member
+            let used_locally = node.internal_refs.contains(name);
+            if !used_locally && !usage.is_export_used(file_path, name) {
+                continue;
+            }
+
+            for (member, span) in &enum_symbol.members {
+                let accessed_locally = node
+                    .member_refs
+                    .iter()
+                    .any(|(object, property)| object == name && property == member);
+                if accessed_locally || usage.is_enum_member_used(file_path, name, member) {
+                    continue;
                 }
+
+                let qualified = format!("{}.{}", name, member);
+                let code_snippet = self.get_code_snippet(file_path, span)?;
+                let relative_path = relative_to(file_path, root);
+
+                // Enums are sometimes iterated with Object.values and friends
+                dead_code.push(DeadCodeItem {
+                    id: finding_id(&relative_path, DeadCodeKind::UnusedEnum, &qualified),
+                    file_path: file_path.to_path_buf(),
+                    relative_path,
+                    span: *span,
+                    code_snippet,
+                    kind: DeadCodeKind::UnusedEnum,
+                    severity: DeadCodeKind::UnusedEnum.severity(),
+                    reason: format!("Enum member '{}' is never referenced", qualified),
+                    name: qualified,
+                    confidence: 0.6,
+                    context: None,
+                });
             }
         }
 
-        false
+        Ok(dead_code)
     }
 
     /// Resolve an import source to a file path
     fn resolve_import(&self, from_file: &Path, source: &str) -> Option<PathBuf> {
-        // Skip node_modules
+        // Bare specifiers are packages unless a tsconfig mapping claims them
         if !source.starts_with('.') && !source.starts_with('/') {
-            return None;
+            return self
+                .path_mappings
+                .candidates(source)
+                .iter()
+                .find_map(|candidate| self.resolve_path(&normalize_path(candidate), true));
         }
 
         let dir = from_file.parent()?;
-        let resolved = dir.join(source);
-
-        // Try different extensions
-        let extensions = [
-            "",
-            ".ts",
-            ".tsx",
-            ".js",
-            ".jsx",
-            "/index.ts",
-            "/index.tsx",
-            "/index.js",
-        ];
+        self.resolve_path(&normalize_path(&dir.join(source)), true)
+    }
 
-        for ext in extensions {
-            let candidate = if ext.is_empty() {
-                resolved.clone()
-            } else {
-                PathBuf::from(format!("{}{}", resolved.display(), ext))
-            };
+    /// Find the analyzed file an import path refers to; directories resolve
+    /// through a local `package.json` (when `follow_package`), then `index.*`
+    fn resolve_path(&self, resolved: &Path, follow_package: bool) -> Option<PathBuf> {
+        // Try the path as written, then each scanned extension
+        if self.nodes.contains_key(resolved) {
+            return Some(resolved.to_path_buf());
+        }
+        let extensions = self
+            .resolve_extensions
+            .iter()
+            .map(String::as_str)
+            .chain(["d.ts"]);
+        for ext in extensions.clone() {
+            let candidate = PathBuf::from(format!("{}.{}", resolved.display(), ext));
+            if self.nodes.contains_key(&candidate) {
+                return Some(candidate);
+            }
+        }
+
+        // ESM TypeScript imports name the emitted file: './foo.js' -> 'foo.ts'
+        let source_ext = resolved.extension().and_then(|e| e.to_str()).unwrap_or("");
+        let ts_extensions: &[&str] = match source_ext {
+            "js" => &["ts", "tsx"],
+            "jsx" => &["tsx"],
+            "mjs" => &["mts"],
+            "cjs" => &["cts"],
+            _ => &[],
+        };
+
+        for ts_ext in ts_extensions {
+            let candidate = resolved.with_extension(ts_ext);
+            if self.nodes.contains_key(&candidate) {
+                return Some(candidate);
+            }
+        }
+
+        // A local package names its entry in package.json
+        if follow_package {
+            if let Some(entry) = package_entry(resolved) {
+                let target = normalize_path(&resolved.join(entry));
+                if let Some(found) = self.resolve_path(&target, false) {
+                    return Some(found);
+                }
+            }
+        }
 
+        for ext in extensions {
+            let candidate = resolved.join(format!("index.{}", ext));
             if self.nodes.contains_key(&candidate) {
                 return Some(candidate);
             }
@@ -311,104 +834,949 @@ impl ReferenceGraph {
         None
     }
 
-    /// Get code snippet from file
-    fn get_code_snippet(&self, file_path: &Path, span: &CodeSpan) -> Result<String> {
-        let content = fs::read_to_string(file_path)?;
-        let lines: Vec<&str> = content.lines().collect();
+    /// Relative imports and re-exports that resolve to no analyzed file,
+    /// leaving out assets such as stylesheets, images and JSON
+    pub fn unresolved_imports(&self, root: &Path) -> Vec<UnresolvedImport> {
+        let mut unresolved = Vec::new();
+        for (file_path, node) in &self.nodes {
+            let imports = node
+                .imports
+                .iter()
+                .map(|import| (import.source.as_str(), import.span.start));
+            let reexports = node
+                .exports
+                .iter()
+                .filter_map(|e| Some((e.source.as_deref()?, e.span.start)));
+            for (source, line) in imports.chain(reexports) {
+                let relative = source.starts_with('.') || source.starts_with('/');
+                if !relative
+                    || is_asset_import(source)
+                    || self.resolve_import(file_path, source).is_some()
+                {
+                    continue;
+                }
+                let root = owning_root(file_path, root, &self.additional_roots);
+                unresolved.push(UnresolvedImport {
+                    path: relative_to(file_path, root),
+                    line,
+                    source: source.to_string(),
+                });
+            }
+        }
+
+        // One statement may import several names from the same source
+        unresolved.sort_by(|a, b| {
+            a.path
+                .cmp(&b.path)
+                .then(a.line.cmp(&b.line))
+                .then(a.source.cmp(&b.source))
+        });
+        unresolved.dedup();
+        unresolved
+    }
 
-        let start = (span.start as usize).saturating_sub(1);
-        let end = (span.end as usize).min(lines.len());
+    /// Report each group of files that import each other, directly or not
+    fn find_import_cycles(&self, root: &Path) -> Vec<DeadCodeItem> {
+        let mut files: Vec<&PathBuf> = self.nodes.keys().collect();
+        files.sort();
+        let index: HashMap<&PathBuf, usize> = files
+            .iter()
+            .enumerate()
+            .map(|(i, file)| (*file, i))
+            .collect();
+        let edges: Vec<Vec<usize>> = files
+            .iter()
+            .map(|file| {
+                let mut targets: Vec<usize> = self
+                    .import_sources(file)
+                    .filter_map(|source| self.resolve_import(file, source))
+                    .filter_map(|target| index.get(&target).copied())
+                    .collect();
+                targets.sort_unstable();
+                targets.dedup();
+                targets
+            })
+            .collect();
+
+        strongly_connected_components(&edges)
+            .into_iter()
+            .filter(|component| component.len() > 1)
+            .map(|mut component| {
+                component.sort_unstable();
+                let relative: Vec<String> = component
+                    .iter()
+                    .map(|&i| {
+                        let root = owning_root(files[i], root, &self.additional_roots);
+                        relative_to(files[i], root)
+                    })
+                    .collect();
+                let name = relative.join(", ");
+                let first = files[component[0]];
+
+                DeadCodeItem {
+                    id: finding_id(&relative[0], DeadCodeKind::CircularImport, &name),
+                    file_path: first.clone(),
+                    relative_path: relative[0].clone(),
+                    span: CodeSpan {
+                        start: 1,
+                        end: 1,
+                        col_start: 0,
+                        col_end: 0,
+                        byte_start: 0,
+                        byte_end: 0,
+                    },
+                    code_snippet: String::new(),
+                    kind: DeadCodeKind::CircularImport,
+                    severity: DeadCodeKind::CircularImport.severity(),
+                    reason: format!("{} files import each other in a cycle", relative.len()),
+                    name,
+                    // Not dead code, so neither likely nor unlikely to be removable
+                    confidence: 0.5,
+                    context: None,
+                }
+            })
+            .collect()
+    }
 
-        let snippet: Vec<&str> = lines[start..end].to_vec();
+    /// Sources of a file's imports and re-exports
+    fn import_sources<'a>(&'a self, file_path: &Path) -> impl Iterator<Item = &'a str> {
+        let node = self.nodes.get(file_path);
+        let imports = node
+            .into_iter()
+            .flat_map(|node| node.imports.iter().map(|import| import.source.as_str()));
+        let reexports = node
+            .into_iter()
+            .flat_map(|node| node.exports.iter().filter_map(|e| e.source.as_deref()));
+        imports.chain(reexports)
+    }
 
-        // Limit snippet size
-        if snippet.len() > 10 {
-            Ok(format!(
-                "{}\n... ({} more lines)",
-                snippet[..5].join("\n"),
-                snippet.len() - 5
-            ))
-        } else {
-            Ok(snippet.join("\n"))
+    /// Files transitively imported or re-exported from the entry files
+    fn reachable_files(&self) -> HashSet<PathBuf> {
+        let mut entries = Vec::new();
+        for entry in &self.entry_files {
+            if self.nodes.contains_key(entry) {
+                entries.push(entry.clone());
+            } else {
+                tracing::warn!("Entry {:?} is not among the scanned files", entry);
+            }
         }
+        self.reachable_from(entries)
     }
 
-    /// Calculate confidence score for an unused export
-    fn calculate_export_confidence(&self, file_path: &Path, export_name: &str) -> f64 {
-        let mut confidence: f64 = 0.9;
+    /// Files reachable from the program's roots: entry points, public API,
+    /// tests, usage-only files and never-imported files without exports,
+    /// which zombie detection also treats as live
+    fn live_files(&self, root: &Path, usage: &UsageIndex) -> HashSet<PathBuf> {
+        let roots = self
+            .nodes
+            .iter()
+            .filter(|(file_path, node)| {
+                let root = owning_root(file_path, root, &self.additional_roots);
+                self.is_likely_entry_point(file_path, root)
+                    || self.is_public_api(file_path, root)
+                    || self.is_test_file(file_path)
+                    || self.usage_only_files.contains(*file_path)
+                    || is_declaration_file(file_path)
+                    || (node.exports.is_empty() && !usage.imported_files.contains(*file_path))
+            })
+            .map(|(file_path, _)| file_path.clone())
+            .collect();
+        self.reachable_from(roots)
+    }
 
-        // Lower confidence for potential dynamic imports
-        if self.might_be_dynamic_import(export_name) {
-            confidence -= 0.2;
-        }
+    /// Files transitively imported or re-exported from `roots`
+    fn reachable_from(&self, roots: Vec<PathBuf>) -> HashSet<PathBuf> {
+        let mut reachable = HashSet::new();
+        let mut pending = roots;
 
-        // Lower confidence for test files
-        if self.is_test_file(file_path) {
-            confidence -= 0.3;
+        while let Some(file_path) = pending.pop() {
+            if !reachable.insert(file_path.clone()) {
+                continue;
+            }
+            for source in self.import_sources(&file_path) {
+                if let Some(target) = self.resolve_import(&file_path, source) {
+                    if !reachable.contains(&target) {
+                        pending.push(target);
+                    }
+                }
+            }
         }
 
-        // Lower confidence for files that look like entry points
-        let filename = file_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-        if filename == "index" || filename == "main" || filename == "app" {
-            confidence -= 0.2;
+        reachable
+    }
+
+    /// Module whose exports `name` imported from `target` is a namespace of:
+    /// `target` itself for `import * as`, or the source of `export * as name`
+    fn namespace_origin(&self, target: &Path, name: &str) -> Option<PathBuf> {
+        if name == "*" {
+            return Some(target.to_path_buf());
         }
 
-        confidence.max(0.1)
+        let export = self
+            .nodes
+            .get(target)?
+            .exports
+            .iter()
+            .find(|e| e.is_reexport && e.kind == SymbolKind::Namespace && e.name == name)?;
+        self.resolve_import(target, export.source.as_deref()?)
     }
 
-    /// Check if a name might be dynamically imported
-    fn might_be_dynamic_import(&self, name: &str) -> bool {
-        // Common patterns for dynamic imports
-        let patterns = [
-            "handler",
-            "middleware",
-            "plugin",
-            "route",
-            "controller",
-            "model",
-        ];
-        let lower = name.to_lowercase();
-        patterns.iter().any(|p| lower.contains(p))
+    /// Modules and names that `name`, imported from `file_path`, re-exports:
+    /// a matching `export { local as name } from`, else every `export *`
+    fn reexport_origins(&self, file_path: &Path, name: &str) -> Vec<(PathBuf, String)> {
+        let Some(node) = self.nodes.get(file_path) else {
+            return Vec::new();
+        };
+        let resolve = |source: Option<&str>| self.resolve_import(file_path, source?);
+
+        let named: Vec<(PathBuf, String)> = node
+            .exports
+            .iter()
+            .filter(|e| e.is_reexport && e.kind != SymbolKind::Namespace && e.name == name)
+            .filter_map(|e| {
+                let local = e.local_name.clone().unwrap_or_else(|| name.to_string());
+                Some((resolve(e.source.as_deref())?, local))
+            })
+            .collect();
+        // Own exports and named re-exports shadow `export *`
+        if !named.is_empty() || node.exports.iter().any(|e| e.name == name) {
+            return named;
+        }
+        node.exports
+            .iter()
+            .filter(|e| e.is_reexport && e.name == "*")
+            .filter_map(|e| Some((resolve(e.source.as_deref())?, name.to_string())))
+            .collect()
     }
 
-    /// Check if a file is a test file
-    fn is_test_file(&self, path: &Path) -> bool {
-        let path_str = path.to_string_lossy().to_lowercase();
-        path_str.contains(".test.") || path_str.contains(".spec.") || path_str.contains("__tests__")
+    /// Get code snippet from file
+    fn get_code_snippet(&self, file_path: &Path, span: &CodeSpan) -> Result<String> {
+        if !self.snippets {
+            return Ok(String::new());
+        }
+
+        let content = read_source(file_path, None)?;
+        Ok(code_snippet(
+            content.as_str(),
+            span,
+            self.snippet_context,
+            self.snippet_max_lines,
+        ))
     }
 
-    /// Check if a file is part of the public API
-    fn is_public_api(&self, path: &Path, root: &Path) -> bool {
-        let relative = pathdiff::diff_paths(path, root).unwrap_or_else(|| path.to_path_buf());
-        let relative_str = relative.to_string_lossy();
+    /// Calculate confidence score for an unused export
+    fn calculate_export_confidence(&self, file_path: &Path, export_name: &str) -> f64 {
+        let adjustment: f64 = self
+            .export_confidence_factors(file_path, export_name)
+            .iter()
+            .map(|(_, delta)| delta)
+            .sum();
 
-        // Common public API patterns
-        relative_str == "index.ts"
-            || relative_str == "index.js"
-            || relative_str.starts_with("src/index")
-            || relative_str.starts_with("lib/index")
+        (EXPORT_BASE_CONFIDENCE + adjustment).clamp(0.1, 1.0)
     }
 
-    /// Check if a file is likely an entry point
-    fn is_likely_entry_point(&self, path: &Path, root: &Path) -> bool {
-        let relative = pathdiff::diff_paths(path, root).unwrap_or_else(|| path.to_path_buf());
-        let filename = path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
-        let relative_str = relative.to_string_lossy();
-
-        // Entry point patterns
-        filename == "index"
-            || filename == "main"
-            || filename == "app"
-            || relative_str.ends_with("index.ts")
-            || relative_str.ends_with("index.js")
-            || relative_str.contains("pages/") // Next.js pages
-            || relative_str.contains("routes/") // Route files
+    /// Whether `name` is an `as const` object that its file indexes with a
+    /// computed key, so which of its members are used can't be known
+    fn is_dynamic_lookup(&self, file_path: &Path, name: &str) -> bool {
+        self.nodes.get(file_path).is_some_and(|node| {
+            node.const_objects.iter().any(|object| object == name)
+                && node
+                    .computed_member_refs
+                    .iter()
+                    .any(|object| object == name)
+        })
     }
-}
 
-impl Default for ReferenceGraph {
-    fn default() -> Self {
-        Self::new()
+    /// Individual adjustments applied to the base confidence of an unused export
+    pub fn export_confidence_factors(
+        &self,
+        file_path: &Path,
+        export_name: &str,
+    ) -> Vec<(&'static str, f64)> {
+        let mut factors = Vec::new();
+
+        // Lower confidence for potential dynamic imports
+        if self.might_be_dynamic_import(export_name) {
+            factors.push(("name suggests dynamic loading", -0.2));
+        }
+
+        // Hooks and HOCs are often reached through JSX or framework wiring
+        if self.framework_naming && is_hook_or_hoc_name(export_name) {
+            factors.push(("named like a React hook or HOC", -0.15));
+        }
+
+        // Lower confidence for test files
+        if self.is_test_file(file_path) {
+            factors.push(("declared in a test file", -0.3));
+        }
+
+        // Declaration files are consumed by the type system in ways we can't see
+        if is_declaration_file(file_path) {
+            factors.push(("declared in a .d.ts file", -0.6));
+        }
+
+        let export = self
+            .nodes
+            .get(file_path)
+            .and_then(|node| node.exports.iter().find(|e| e.name == export_name));
+
+        // Lookup tables indexed by computed keys are easy to miss
+        if self.is_dynamic_lookup(file_path, export_name) {
+            factors.push(("`as const` object indexed by computed key", -0.3));
+        }
+
+        // Unfinished code is often not wired up yet
+        if let (Some(node), Some(export)) = (self.nodes.get(file_path), export) {
+            if has_todo_near(node, &export.span) {
+                factors.push(("TODO/FIXME/HACK comment nearby", -TODO_CONFIDENCE_PENALTY));
+            }
+        }
+
+        // Default exports are often loaded by frameworks rather than imported
+        if export.is_some_and(|e| e.is_default) {
+            factors.push(("default export", -0.3));
+        }
+
+        // JSDoc tags state the author's intent
+        if let Some(doc) = export.and_then(|e| e.doc_comment.as_deref()) {
+            if has_jsdoc_tag(doc, "@public") || has_jsdoc_tag(doc, "@api") {
+                factors.push(("documented as public API", -0.6));
+            }
+            if has_jsdoc_tag(doc, "@deprecated") {
+                factors.push(("marked @deprecated", 0.05));
+            }
+            if has_jsdoc_tag(doc, "@internal") {
+                factors.push(("marked @internal", 0.05));
+            }
+        }
+
+        // Lower confidence for files that look like entry points
+        let filename = file_path.file_stem().and_then(|s| s.to_str()).unwrap_or("");
+        if filename == "index" || filename == "main" || filename == "app" {
+            factors.push(("file looks like an entry point", -0.2));
+        }
+
+        factors.extend(self.project_size_factor());
+
+        factors
+    }
+
+    /// Adjustment for the size of the analyzed project; "no references" is
+    /// weaker evidence among thousands of files, with more dynamic loading
+    fn project_size_factor(&self) -> Option<(&'static str, f64)> {
+        let files = self.nodes.len();
+        if files >= 10 * LARGE_PROJECT_FILES {
+            Some(("very large project (10000+ files)", -0.1))
+        } else if files >= LARGE_PROJECT_FILES {
+            Some(("large project (1000+ files)", -0.05))
+        } else {
+            None
+        }
+    }
+
+    /// Files whose imports resolve to `file_path`
+    pub fn importers_of(&self, file_path: &Path) -> Vec<PathBuf> {
+        let mut importers: Vec<PathBuf> = self
+            .nodes
+            .iter()
+            .filter(|(path, node)| {
+                path.as_path() != file_path
+                    && node.imports.iter().any(|import| {
+                        self.resolve_import(path, &import.source).as_deref() == Some(file_path)
+                    })
+            })
+            .map(|(path, _)| path.clone())
+            .collect();
+        importers.sort();
+        importers
+    }
+
+    /// Files other than `exclude` that import a symbol called `name` from anywhere
+    pub fn files_importing_name(&self, name: &str, exclude: &Path) -> Vec<PathBuf> {
+        let mut files: Vec<PathBuf> =
+            self.nodes
+                .iter()
+                .filter(|(path, node)| {
+                    path.as_path() != exclude
+                        && node.imports.iter().any(|import| {
+                            import.name == name || import.alias.as_deref() == Some(name)
+                        })
+                })
+                .map(|(path, _)| path.clone())
+                .collect();
+        files.sort();
+        files
+    }
+
+    /// The analyzed file an entry path names; directories resolve through
+    /// their `package.json`, then `index.*`
+    pub fn resolve_entry(&self, path: &Path) -> Option<PathBuf> {
+        self.resolve_path(&normalize_path(path), true)
+    }
+
+    /// Every export of the entry files, each located at the module that
+    /// declares it by following re-exports
+    pub fn public_api(&self, root: &Path, entries: &[PathBuf]) -> Vec<ApiSymbol> {
+        let relative = |path: &Path| relative_to(path, root);
+
+        let mut api = Vec::new();
+        for entry in entries {
+            for name in self.exported_names(entry, &mut HashSet::new()) {
+                let Some((origin, export)) = self.export_origin(entry, &name, &mut HashSet::new())
+                else {
+                    continue;
+                };
+                api.push(ApiSymbol {
+                    is_default: name == "default",
+                    name,
+                    kind: export.kind,
+                    entry: relative(entry),
+                    relative_path: relative(origin),
+                    line: export.span.start,
+                });
+            }
+        }
+        api
+    }
+
+    /// Names a file exports, including those forwarded by `export *`
+    fn exported_names(&self, file_path: &Path, seen: &mut HashSet<PathBuf>) -> BTreeSet<String> {
+        let mut names = BTreeSet::new();
+        let Some(node) = self.nodes.get(file_path) else {
+            return names;
+        };
+        if !seen.insert(file_path.to_path_buf()) {
+            return names;
+        }
+
+        for export in &node.exports {
+            if !(export.is_reexport && export.name == "*") {
+                names.insert(export.name.clone());
+                continue;
+            }
+            let Some(target) = export
+                .source
+                .as_deref()
+                .and_then(|source| self.resolve_import(file_path, source))
+            else {
+                continue;
+            };
+            // `export *` never forwards a default export
+            names.extend(
+                self.exported_names(&target, seen)
+                    .into_iter()
+                    .filter(|name| name != "default"),
+            );
+        }
+        names
+    }
+
+    /// The file and declaration behind `name` as exported by `file_path`;
+    /// re-exports of modules outside the graph stop at the re-export
+    fn export_origin<'a>(
+        &'a self,
+        file_path: &Path,
+        name: &str,
+        seen: &mut HashSet<(PathBuf, String)>,
+    ) -> Option<(&'a Path, &'a ExportedSymbol)> {
+        let (path, node) = self.nodes.get_key_value(file_path)?;
+        if !seen.insert((path.clone(), name.to_string())) {
+            return None;
+        }
+
+        if let Some(export) = node
+            .exports
+            .iter()
+            .find(|e| !e.is_reexport && e.name == name)
+        {
+            return Some((path, export));
+        }
+        for (origin, local) in self.reexport_origins(file_path, name) {
+            if let Some(found) = self.export_origin(&origin, &local, seen) {
+                return Some(found);
+            }
+        }
+        node.exports
+            .iter()
+            .find(|e| e.name == name)
+            .map(|export| (path.as_path(), export))
+    }
+
+    /// Check if a name might be dynamically imported
+    fn might_be_dynamic_import(&self, name: &str) -> bool {
+        // Common patterns for dynamic imports
+        let patterns = [
+            "handler",
+            "middleware",
+            "plugin",
+            "route",
+            "controller",
+            "model",
+        ];
+        let lower = name.to_lowercase();
+        patterns.iter().any(|p| lower.contains(p))
+    }
+
+    /// The patterns that apply to a file, and the directory they are relative to
+    fn patterns_for<'a>(&'a self, path: &Path, root: &'a Path) -> (&'a FilePatterns, &'a Path) {
+        self.directory_patterns
+            .iter()
+            .find(|(dir, _)| path.starts_with(dir))
+            .map_or((&self.patterns, root), |(dir, patterns)| {
+                (patterns, dir.as_path())
+            })
+    }
+
+    /// Check if a file is a test file
+    fn is_test_file(&self, path: &Path) -> bool {
+        self.patterns_for(path, path).0.test_files.is_match(path)
+    }
+
+    /// Check if an export name in a file is allowlisted
+    fn is_always_used(&self, path: &Path, name: &str) -> bool {
+        self.patterns_for(path, path)
+            .0
+            .always_used_exports
+            .is_match(name)
+    }
+
+    /// Check if a file is part of the public API
+    fn is_public_api(&self, path: &Path, root: &Path) -> bool {
+        let (patterns, base) = self.patterns_for(path, root);
+        let relative = pathdiff::diff_paths(path, base).unwrap_or_else(|| path.to_path_buf());
+        patterns.public_api.is_match(relative)
+    }
+
+    /// Check if a file is likely an entry point
+    fn is_likely_entry_point(&self, path: &Path, root: &Path) -> bool {
+        let (patterns, base) = self.patterns_for(path, root);
+        let relative = pathdiff::diff_paths(path, base).unwrap_or_else(|| path.to_path_buf());
+        patterns.entry_points.is_match(relative)
+    }
+}
+
+/// Name findings about `export` go by: anonymous default exports would all be
+/// `default`, so they take the binding they export or the file's name
+fn finding_name(file_path: &Path, export: &ExportedSymbol) -> String {
+    if !export.is_default || export.name != "default" {
+        return export.name.clone();
+    }
+    let origin = export.local_name.clone().unwrap_or_else(|| {
+        file_path
+            .file_name()
+            .map_or_else(String::new, |name| name.to_string_lossy().to_string())
+    });
+    format!("default({})", origin)
+}
+
+/// `path` relative to `root` as a string, or `path` itself outside `root`
+pub(crate) fn relative_to(path: &Path, root: &Path) -> String {
+    pathdiff::diff_paths(path, root)
+        .unwrap_or_else(|| path.to_path_buf())
+        .to_string_lossy()
+        .to_string()
+}
+
+/// Whether a name looks like a React hook (`useX`) or higher-order component (`withX`)
+fn is_hook_or_hoc_name(name: &str) -> bool {
+    ["use", "with"].iter().any(|prefix| {
+        name.strip_prefix(prefix)
+            .and_then(|rest| rest.chars().next())
+            .is_some_and(|c| c.is_ascii_uppercase())
+    })
+}
+
+/// Whether an import names a non-code file that a bundler loads
+fn is_asset_import(source: &str) -> bool {
+    const ASSET_EXTENSIONS: &[&str] = &[
+        "css", "scss", "sass", "less", "styl", "svg", "png", "jpg", "jpeg", "gif", "webp", "avif",
+        "ico", "woff", "woff2", "ttf", "otf", "json", "wasm", "html", "md", "txt",
+    ];
+    Path::new(source)
+        .extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| ASSET_EXTENSIONS.contains(&ext))
+}
+
+/// Whether a doc comment contains `tag` as a whole word
+fn has_jsdoc_tag(doc: &str, tag: &str) -> bool {
+    doc.split(|c: char| c.is_whitespace() || c == '*')
+        .any(|word| word == tag)
+}
+
+/// The `module` or `main` entry of the `package.json` in `dir`, if any
+fn package_entry(dir: &Path) -> Option<String> {
+    let manifest = read_manifest(dir)?;
+    ["module", "main"]
+        .iter()
+        .find_map(|field| manifest.get(field)?.as_str())
+        .map(str::to_string)
+}
+
+/// Every file the `package.json` in `dir` exposes: each `exports` target,
+/// then `module` and `main`
+pub fn package_entries(dir: &Path) -> Vec<String> {
+    let Some(manifest) = read_manifest(dir) else {
+        return Vec::new();
+    };
+
+    let mut entries = Vec::new();
+    if let Some(exports) = manifest.get("exports") {
+        collect_export_targets(exports, &mut entries);
+    }
+    for field in ["module", "main"] {
+        if let Some(entry) = manifest.get(field).and_then(|entry| entry.as_str()) {
+            entries.push(entry.to_string());
+        }
+    }
+
+    let mut seen = HashSet::new();
+    entries.retain(|entry| seen.insert(entry.clone()));
+    entries
+}
+
+/// Targets of an `exports` field, through condition and subpath maps;
+/// wildcard subpaths and JSON files are skipped
+fn collect_export_targets(value: &serde_json::Value, entries: &mut Vec<String>) {
+    match value {
+        serde_json::Value::String(target)
+            if !target.contains('*') && !target.ends_with(".json") =>
+        {
+            entries.push(target.clone());
+        }
+        serde_json::Value::Array(targets) => {
+            for target in targets {
+                collect_export_targets(target, entries);
+            }
+        }
+        serde_json::Value::Object(map) => {
+            for target in map.values() {
+                collect_export_targets(target, entries);
+            }
+        }
+        _ => {}
+    }
+}
+
+fn read_manifest(dir: &Path) -> Option<serde_json::Value> {
+    let manifest = fs::read_to_string(dir.join("package.json")).ok()?;
+    serde_json::from_str(&manifest).ok()
+}
+
+/// Tarjan's algorithm over an adjacency list, without recursion so deep
+/// import chains can't overflow the stack
+fn strongly_connected_components(edges: &[Vec<usize>]) -> Vec<Vec<usize>> {
+    let mut next_index = 0;
+    let mut indices: Vec<Option<usize>> = vec![None; edges.len()];
+    let mut low_links = vec![0; edges.len()];
+    let mut on_stack = vec![false; edges.len()];
+    let mut stack = Vec::new();
+    let mut components = Vec::new();
+
+    for start in 0..edges.len() {
+        if indices[start].is_some() {
+            continue;
+        }
+        // Each frame is a node and the position of its next edge to follow
+        let mut frames = vec![(start, 0)];
+        while let Some(&mut (node, ref mut edge)) = frames.last_mut() {
+            if *edge == 0 {
+                indices[node] = Some(next_index);
+                low_links[node] = next_index;
+                next_index += 1;
+                stack.push(node);
+                on_stack[node] = true;
+            }
+
+            if let Some(&target) = edges[node].get(*edge) {
+                *edge += 1;
+                match indices[target] {
+                    None => frames.push((target, 0)),
+                    Some(target_index) if on_stack[target] => {
+                        low_links[node] = low_links[node].min(target_index);
+                    }
+                    Some(_) => {}
+                }
+                continue;
+            }
+
+            frames.pop();
+            if let Some(&(parent, _)) = frames.last() {
+                low_links[parent] = low_links[parent].min(low_links[node]);
+            }
+            if Some(low_links[node]) == indices[node] {
+                let mut component = Vec::new();
+                while let Some(member) = stack.pop() {
+                    on_stack[member] = false;
+                    component.push(member);
+                    if member == node {
+                        break;
+                    }
+                }
+                components.push(component);
+            }
+        }
+    }
+
+    components
+}
+
+/// Lexically resolve inner `.` and `..` so `../x` matches the walked path of `x`;
+/// a leading `.` is kept, as the walker keeps it for roots like `./src`
+pub(super) fn normalize_path(path: &Path) -> PathBuf {
+    let mut normalized = PathBuf::new();
+    for component in path.components() {
+        match component {
+            Component::CurDir if normalized.as_os_str().is_empty() => normalized.push(component),
+            Component::CurDir => {}
+            Component::ParentDir
+                if matches!(
+                    normalized.components().next_back(),
+                    Some(Component::Normal(_))
+                ) =>
+            {
+                normalized.pop();
+            }
+            _ => normalized.push(component),
+        }
+    }
+    normalized
+}
+
+/// The deepest root containing `path`, out of `root` and `additional_roots`
+pub(crate) fn owning_root<'a>(
+    path: &Path,
+    root: &'a Path,
+    additional_roots: &'a [PathBuf],
+) -> &'a Path {
+    additional_roots
+        .iter()
+        .map(PathBuf::as_path)
+        .chain(std::iter::once(root))
+        .filter(|candidate| path.starts_with(candidate))
+        .max_by_key(|candidate| candidate.components().count())
+        .unwrap_or(root)
+}
+
+/// Cross-file usage facts, resolved once per detection run
+struct UsageIndex {
+    /// Files that some other file imports from
+    imported_files: HashSet<PathBuf>,
+    /// Names imported from each file by some other file
+    used_exports: HashMap<PathBuf, HashSet<String>>,
+    /// `Enum.Member` accesses on enums imported from each file
+    used_enum_members: HashMap<PathBuf, HashSet<(String, String)>>,
+    /// Other files importing something from each file
+    importers: HashMap<PathBuf, BTreeSet<PathBuf>>,
+    /// Other files importing each name from each file
+    export_importers: HashMap<PathBuf, HashMap<String, BTreeSet<PathBuf>>>,
+    /// Property names accessed as `object.property` in any file
+    accessed_properties: HashSet<String>,
+    /// Files reachable from the explicit entries, when any were given;
+    /// imports from other files are then ignored
+    reachable: Option<HashSet<PathBuf>>,
+}
+
+impl UsageIndex {
+    fn build(graph: &ReferenceGraph) -> Self {
+        let reachable = (!graph.entry_files.is_empty()).then(|| graph.reachable_files());
+        let is_live = |file_path: &PathBuf| {
+            reachable
+                .as_ref()
+                .map_or(true, |reachable| reachable.contains(file_path))
+        };
+
+        let resolved: Vec<(PathBuf, &str, &PathBuf)> = graph
+            .nodes
+            .par_iter()
+            .filter(|(file_path, _)| is_live(file_path))
+            .flat_map_iter(|(file_path, node)| {
+                let mut resolved = Vec::new();
+                for import in &node.imports {
+                    let Some(target) = graph.resolve_import(file_path, &import.source) else {
+                        continue;
+                    };
+
+                    // `ns.foo` through a namespace import uses `foo` in the origin;
+                    // so does `cfg.foo` or `const { foo } = cfg` on a default
+                    // import, as modules often default-export their named exports
+                    let origin = if import.name == "default" {
+                        Some(target.clone())
+                    } else {
+                        graph.namespace_origin(&target, &import.name)
+                    };
+                    if let Some(origin) = origin {
+                        let local = import.alias.as_ref().unwrap_or(&import.name);
+                        for (object, property) in &node.member_refs {
+                            if object == local {
+                                resolved.push((origin.clone(), property.as_str(), file_path));
+                            }
+                        }
+                    }
+
+                    resolved.push((target, import.name.as_str(), file_path));
+                }
+                resolved
+            })
+            .collect();
+
+        // A name imported from a barrel is used in the module the barrel
+        // re-exports it from, and so on down the chain
+        let mut forwarded: Vec<(PathBuf, String, &PathBuf)> = Vec::new();
+        let mut seen: HashSet<(PathBuf, String, &PathBuf)> = HashSet::new();
+        let mut pending: Vec<(PathBuf, String, &PathBuf)> = resolved
+            .iter()
+            .map(|(target, name, importer)| (target.clone(), name.to_string(), *importer))
+            .collect();
+        while let Some((target, name, importer)) = pending.pop() {
+            for (origin, origin_name) in graph.reexport_origins(&target, &name) {
+                let entry = (origin, origin_name, importer);
+                if seen.insert(entry.clone()) {
+                    forwarded.push(entry.clone());
+                    pending.push(entry);
+                }
+            }
+        }
+
+        let member_accesses: Vec<(PathBuf, (String, String))> = graph
+            .nodes
+            .par_iter()
+            .filter(|(file_path, _)| is_live(file_path))
+            .flat_map_iter(|(file_path, node)| {
+                let mut accesses = Vec::new();
+                for import in &node.imports {
+                    let local = import.alias.as_ref().unwrap_or(&import.name);
+                    let properties: Vec<&String> = node
+                        .member_refs
+                        .iter()
+                        .filter(|(object, _)| object == local)
+                        .map(|(_, property)| property)
+                        .collect();
+                    if properties.is_empty() {
+                        continue;
+                    }
+                    let Some(target) = graph.resolve_import(file_path, &import.source) else {
+                        continue;
+                    };
+                    for property in properties {
+                        accesses.push((target.clone(), (import.name.clone(), property.clone())));
+                    }
+                }
+                accesses
+            })
+            .collect();
+
+        let mut index = Self {
+            imported_files: HashSet::new(),
+            used_exports: HashMap::new(),
+            used_enum_members: HashMap::new(),
+            importers: HashMap::new(),
+            export_importers: HashMap::new(),
+            accessed_properties: graph
+                .nodes
+                .values()
+                .flat_map(|node| {
+                    node.member_refs
+                        .iter()
+                        .map(|(_, property)| property.clone())
+                })
+                .collect(),
+            reachable: None,
+        };
+        for (target, access) in member_accesses {
+            index
+                .used_enum_members
+                .entry(target)
+                .or_default()
+                .insert(access);
+        }
+        let forwarded = forwarded
+            .iter()
+            .map(|(origin, name, importer)| (origin.clone(), name.as_str(), *importer));
+        for (target, name, importer) in resolved.into_iter().chain(forwarded) {
+            if target != *importer {
+                index
+                    .used_exports
+                    .entry(target.clone())
+                    .or_default()
+                    .insert(name.to_string());
+                index
+                    .importers
+                    .entry(target.clone())
+                    .or_default()
+                    .insert(importer.clone());
+                index
+                    .export_importers
+                    .entry(target.clone())
+                    .or_default()
+                    .entry(name.to_string())
+                    .or_default()
+                    .insert(importer.clone());
+            }
+            index.imported_files.insert(target);
+        }
+        index.reachable = reachable;
+        index
+    }
+
+    /// Files that import from `file_path`, relative to `root`
+    fn importers_of(&self, file_path: &Path, root: &Path) -> Vec<String> {
+        self.importers
+            .get(file_path)
+            .into_iter()
+            .flatten()
+            .map(|importer| relative_to(importer, root))
+            .collect()
+    }
+
+    /// Other files importing `export_name` from `export_file`, if any do
+    fn importers_of_export(
+        &self,
+        export_file: &Path,
+        export_name: &str,
+    ) -> Option<&BTreeSet<PathBuf>> {
+        self.export_importers.get(export_file)?.get(export_name)
+    }
+
+    /// `file ← its importer ← ...`, relative to `root`, following the first
+    /// importer back to a file nothing imports (or around a cycle)
+    fn import_chain(&self, file_path: &Path, root: &Path) -> Vec<String> {
+        let mut chain = Vec::new();
+        let mut seen = HashSet::new();
+        let mut current = Some(file_path);
+        while let Some(file_path) = current.filter(|file_path| seen.insert(*file_path)) {
+            chain.push(relative_to(file_path, root));
+            current = self
+                .importers
+                .get(file_path)
+                .and_then(|importers| importers.iter().next())
+                .map(PathBuf::as_path);
+        }
+        chain
+    }
+
+    /// Check if another file accesses `enum_name.member` on an enum it imported
+    fn is_enum_member_used(&self, enum_file: &Path, enum_name: &str, member: &str) -> bool {
+        self.used_enum_members
+            .get(enum_file)
+            .is_some_and(|accesses| {
+                accesses
+                    .iter()
+                    .any(|(name, property)| name == enum_name && property == member)
+            })
+    }
+
+    /// Check if an export is imported by any file other than its own
+    fn is_export_used(&self, export_file: &Path, export_name: &str) -> bool {
+        self.used_exports
+            .get(export_file)
+            .is_some_and(|names| names.contains(export_name))
+    }
+}
+
+impl Default for ReferenceGraph {
+    fn default() -> Self {
+        Self::new()
     }
 }
 
@@ -436,16 +1804,1148 @@ mod tests {
                     end: 1,
                     col_start: 0,
                     col_end: 0,
+                    byte_start: 0,
+                    byte_end: 0,
                 },
                 is_default: false,
                 is_reexport: false,
+                source: None,
+                local_name: None,
+                doc_comment: None,
+                is_async: false,
             }],
-            imports: vec![],
-            internal_refs: vec![],
+            ..Default::default()
         };
 
         graph.add_node(node);
         assert_eq!(graph.nodes.len(), 1);
         assert!(graph.export_index.contains_key("foo"));
     }
+
+    #[test]
+    fn test_declaration_file_exports_low_confidence() {
+        let dir = tempfile::tempdir().unwrap();
+        let dts = dir.path().join("globals.d.ts");
+        let source = "export declare const VERSION: string;\n";
+        fs::write(&dts, source).unwrap();
+
+        let mut graph = ReferenceGraph::new();
+        graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&dts, source).unwrap());
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+
+        // Never reported as a zombie file, and exports are low confidence
+        assert!(!dead_code
+            .iter()
+            .any(|item| item.kind == DeadCodeKind::ZombieFile));
+        let export = dead_code
+            .iter()
+            .find(|item| item.name == "VERSION")
+            .unwrap();
+        assert!(export.confidence < 0.5);
+    }
+
+    #[test]
+    fn test_type_import_resolves_to_declaration_file() {
+        let mut graph = ReferenceGraph::new();
+        let dts = PathBuf::from("/project/types.d.ts");
+        graph.add_node(ReferenceNode {
+            file_path: dts.clone(),
+            ..Default::default()
+        });
+
+        let resolved = graph.resolve_import(Path::new("/project/app.ts"), "./types");
+        assert_eq!(resolved, Some(dts));
+    }
+
+    #[test]
+    fn test_resolve_with_scanned_extensions() {
+        let extensions: Vec<String> = ["ts", "mts", "cts"].map(String::from).to_vec();
+        let mut graph = ReferenceGraph::new().with_resolve_extensions(&extensions);
+        for path in [
+            "/project/util.mts",
+            "/project/legacy.cts",
+            "/project/lib/index.mts",
+        ] {
+            graph.add_node(ReferenceNode {
+                file_path: PathBuf::from(path),
+                ..Default::default()
+            });
+        }
+
+        let from = Path::new("/project/app.ts");
+        assert_eq!(
+            graph.resolve_import(from, "./util"),
+            Some(PathBuf::from("/project/util.mts"))
+        );
+        assert_eq!(
+            graph.resolve_import(from, "./legacy"),
+            Some(PathBuf::from("/project/legacy.cts"))
+        );
+        assert_eq!(
+            graph.resolve_import(from, "./lib"),
+            Some(PathBuf::from("/project/lib/index.mts"))
+        );
+    }
+
+    #[test]
+    fn test_resolve_js_extension_to_ts_source() {
+        let mut graph = ReferenceGraph::new();
+        for path in ["/project/foo.ts", "/project/bar.mts"] {
+            graph.add_node(ReferenceNode {
+                file_path: PathBuf::from(path),
+                ..Default::default()
+            });
+        }
+
+        let from = Path::new("/project/app.ts");
+        assert_eq!(
+            graph.resolve_import(from, "./foo.js"),
+            Some(PathBuf::from("/project/foo.ts"))
+        );
+        assert_eq!(
+            graph.resolve_import(from, "./bar.mjs"),
+            Some(PathBuf::from("/project/bar.mts"))
+        );
+        assert_eq!(graph.resolve_import(from, "./missing.js"), None);
+    }
+
+    #[test]
+    fn test_write_only_export_is_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("counter.ts");
+        let source = "export let counter = 0;\nexport function increment() {\n  counter++;\n}\nlet total = 0;\ntotal += 1;\nlet seen = 0;\nseen = 1;\nconsole.log(seen);\n";
+        fs::write(&file, source).unwrap();
+
+        let mut graph = ReferenceGraph::new();
+        graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+
+        let write_only: Vec<_> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedVariable)
+            .map(|item| (item.name.as_str(), item.confidence))
+            .collect();
+        assert_eq!(write_only.len(), 2);
+        assert!(write_only.contains(&("counter", 0.6)));
+        assert!(write_only.contains(&("total", 0.6)));
+
+        // Not double-reported as an unused export
+        assert!(!dead_code
+            .iter()
+            .any(|item| item.kind == DeadCodeKind::UnusedExport && item.name == "counter"));
+    }
+
+    #[test]
+    fn test_parallel_detection_matches_serial() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        for i in 0..500 {
+            let file = dir.path().join(format!("mod{}.ts", i));
+            let mut source = String::new();
+            if i > 0 {
+                source.push_str(&format!(
+                    "import {{ used{prev}, stale{prev} }} from './mod{prev}';\nconsole.log(used{prev});\n",
+                    prev = i - 1
+                ));
+            }
+            source.push_str(&format!(
+                "export const used{i} = {i};\nexport const stale{i} = {i};\nexport let sink{i} = 0;\nsink{i} = 1;\n"
+            ));
+            fs::write(&file, &source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, &source).unwrap());
+        }
+
+        let summarize = |items: Vec<DeadCodeItem>| -> Vec<(String, u32, f64)> {
+            items
+                .into_iter()
+                .map(|item| (item.id, item.span.start, item.confidence))
+                .collect()
+        };
+
+        let parallel = summarize(graph.find_dead_code(dir.path(), 0.0).unwrap());
+        let serial = summarize(
+            rayon::ThreadPoolBuilder::new()
+                .num_threads(1)
+                .build()
+                .unwrap()
+                .install(|| graph.find_dead_code(dir.path(), 0.0))
+                .unwrap(),
+        );
+
+        assert!(parallel.len() > 1000);
+        assert_eq!(parallel, serial);
+    }
+
+    /// The per-export scan the usage index replaced
+    fn is_export_used_by_scan(graph: &ReferenceGraph, export_file: &Path, name: &str) -> bool {
+        graph.nodes.iter().any(|(file_path, node)| {
+            file_path != export_file
+                && node.imports.iter().any(|import| {
+                    import.name == name
+                        && graph.resolve_import(file_path, &import.source).as_deref()
+                            == Some(export_file)
+                })
+        })
+    }
+
+    fn chain_graph(dir: &Path, files: usize) -> ReferenceGraph {
+        let mut graph = ReferenceGraph::new();
+        for i in 0..files {
+            let file = dir.join(format!("mod{}.ts", i));
+            let mut source = format!("export const a{i} = {i};\nexport const b{i} = {i};\n");
+            if i > 0 {
+                source.push_str(&format!("import {{ a{} }} from './mod{}';\n", i - 1, i - 1));
+            }
+            // Self-imports never count as usage
+            source.push_str(&format!("import {{ b{i} }} from './mod{i}';\n"));
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, &source).unwrap());
+        }
+        graph
+    }
+
+    #[test]
+    fn test_usage_index_matches_scan() {
+        let dir = tempfile::tempdir().unwrap();
+        let graph = chain_graph(dir.path(), 20);
+        let usage = UsageIndex::build(&graph);
+
+        let mut used = 0;
+        for (file_path, node) in &graph.nodes {
+            for export in &node.exports {
+                let expected = is_export_used_by_scan(&graph, file_path, &export.name);
+                assert_eq!(
+                    usage.is_export_used(file_path, &export.name),
+                    expected,
+                    "{} in {:?}",
+                    export.name,
+                    file_path
+                );
+                used += expected as usize;
+            }
+        }
+        assert_eq!(used, 19);
+    }
+
+    #[test]
+    fn test_usage_index_scales_to_large_graphs() {
+        let dir = tempfile::tempdir().unwrap();
+        let graph = chain_graph(dir.path(), 5000);
+
+        let start = std::time::Instant::now();
+        let usage = UsageIndex::build(&graph);
+        let used = graph
+            .nodes
+            .iter()
+            .flat_map(|(path, node)| node.exports.iter().map(move |e| (path, e)))
+            .filter(|(path, export)| usage.is_export_used(path, &export.name))
+            .count();
+
+        assert_eq!(used, 4999);
+        // The per-export scan is quadratic in file count; the index is linear
+        assert!(start.elapsed() < std::time::Duration::from_secs(5));
+    }
+
+    #[test]
+    fn test_unused_default_export_is_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        for (name, source) in [
+            ("Used.tsx", "export default function Used() {}\n"),
+            ("Unused.tsx", "export default function Unused() {}\n"),
+            ("consumer.ts", "import Renamed from './Used';\nRenamed();\n"),
+        ] {
+            let file = dir.path().join(name);
+            fs::write(&file, source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        }
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let unused_exports: Vec<_> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedExport)
+            .map(|item| (item.name.as_str(), item.confidence))
+            .collect();
+
+        assert_eq!(unused_exports.len(), 1);
+        assert_eq!(unused_exports[0].0, "Unused");
+        assert!((unused_exports[0].1 - 0.6).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_partial_references_list_importers() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        for (name, source) in [
+            (
+                "util.ts",
+                "export const used = 1;\nexport const unused = 2;\n",
+            ),
+            (
+                "b.ts",
+                "import { used } from './util';\nconsole.log(used);\n",
+            ),
+            (
+                "a.ts",
+                "import { used } from './util';\nconsole.log(used);\n",
+            ),
+        ] {
+            let file = dir.path().join(name);
+            fs::write(&file, source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        }
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let unused = dead_code
+            .iter()
+            .find(|item| item.kind == DeadCodeKind::UnusedExport && item.name == "unused")
+            .unwrap();
+        assert_eq!(
+            unused.context.as_ref().unwrap().partial_references,
+            vec!["a.ts", "b.ts"]
+        );
+    }
+
+    #[test]
+    fn test_const_object_indexed_by_computed_key() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("labels.ts");
+        let source = "export const LABELS = { ok: 'OK', error: 'Error' } as const;\n\
+                      export const COLORS = { ok: 'green' } as const;\n\
+                      export function label(key: keyof typeof LABELS) {\n\
+                      \x20 return LABELS[key] + COLORS['ok'];\n\
+                      }\n";
+        fs::write(&file, source).unwrap();
+
+        let node = crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap();
+        assert_eq!(node.const_objects, vec!["LABELS", "COLORS"]);
+        assert_eq!(node.computed_member_refs, vec!["LABELS"]);
+        assert!(node
+            .member_refs
+            .contains(&("COLORS".to_string(), "ok".to_string())));
+
+        let mut graph = ReferenceGraph::new();
+        graph.add_node(node);
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let find = |name: &str| {
+            dead_code
+                .iter()
+                .find(|item| item.kind == DeadCodeKind::RedundantExport && item.name == name)
+                .unwrap()
+        };
+
+        let labels = find("LABELS");
+        assert!(labels.context.as_ref().unwrap().possibly_dynamic);
+        assert!((labels.confidence - (EXPORT_BASE_CONFIDENCE - 0.3)).abs() < 1e-9);
+
+        let colors = find("COLORS");
+        assert!(!colors.context.as_ref().unwrap().possibly_dynamic);
+        assert!((colors.confidence - EXPORT_BASE_CONFIDENCE).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_write_only_class_fields() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("cache.ts");
+        let source = "export class Cache {\n\
+                      \x20 private hits = 0;\n\
+                      \x20 #misses: number;\n\
+                      \x20 constructor(private store: Map<string, string>) {\n\
+                      \x20   this.label = 'cache';\n\
+                      \x20   this.#misses = 0;\n\
+                      \x20 }\n\
+                      \x20 get(key: string) {\n\
+                      \x20   this.hits += 1;\n\
+                      \x20   this.#misses = this.#misses + 1;\n\
+                      \x20   return this.store.get(key);\n\
+                      \x20 }\n\
+                      }\n";
+        fs::write(&file, source).unwrap();
+        let consumer = dir.path().join("main.ts");
+        let consumer_source = "import { Cache } from './cache';\nnew Cache(new Map()).get('a');\n";
+        fs::write(&consumer, consumer_source).unwrap();
+
+        let mut graph = ReferenceGraph::new();
+        graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        graph.add_node(
+            crate::scanner::AstAnalyzer::analyze_source(&consumer, consumer_source).unwrap(),
+        );
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let fields: Vec<_> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedVariable)
+            .map(|item| (item.name.as_str(), item.span.start, item.confidence))
+            .collect();
+        assert_eq!(
+            fields,
+            vec![("Cache.hits", 2, 0.6), ("Cache.label", 5, 0.6)]
+        );
+    }
+
+    #[test]
+    fn test_escaping_this_keeps_fields() {
+        let source = "class Point {\n\
+                      \x20 constructor() { this.x = 1; }\n\
+                      \x20 toJSON() { return { ...this }; }\n\
+                      }\n";
+        let node =
+            crate::scanner::AstAnalyzer::analyze_source(Path::new("point.ts"), source).unwrap();
+        assert!(node.write_only_fields.is_empty());
+    }
+
+    #[test]
+    fn test_import_cycle_is_reported() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            ("a.ts", "import { b } from './b';\nexport const a = b;\n"),
+            ("b.ts", "import { c } from './c';\nexport const b = c;\n"),
+            ("c.ts", "import { a } from './a';\nexport const c = a;\n"),
+            ("main.ts", "import { a } from './a';\nconsole.log(a);\n"),
+        ];
+        let build = |detect: bool| {
+            let mut graph = ReferenceGraph::new().with_cycle_detection(detect);
+            for (name, source) in files {
+                let file = dir.path().join(name);
+                fs::write(&file, source).unwrap();
+                graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+            }
+            graph
+        };
+        let cycles = |graph: &ReferenceGraph| -> Vec<DeadCodeItem> {
+            graph
+                .find_dead_code(dir.path(), 0.9)
+                .unwrap()
+                .into_iter()
+                .filter(|item| item.kind == DeadCodeKind::CircularImport)
+                .collect()
+        };
+
+        assert!(cycles(&build(false)).is_empty());
+
+        let found = cycles(&build(true));
+        assert_eq!(found.len(), 1);
+        assert_eq!(found[0].name, "a.ts, b.ts, c.ts");
+        assert_eq!(found[0].relative_path, "a.ts");
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        // 0 -> 1 -> 2 -> 0, 2 -> 3, 3 -> 3, 4 alone
+        let edges = vec![vec![1], vec![2], vec![0, 3], vec![3], vec![]];
+        let mut components: Vec<Vec<usize>> = strongly_connected_components(&edges)
+            .into_iter()
+            .map(|mut component| {
+                component.sort_unstable();
+                component
+            })
+            .collect();
+        components.sort();
+        assert_eq!(components, vec![vec![0, 1, 2], vec![3], vec![4]]);
+    }
+
+    #[test]
+    fn test_always_used_exports_are_kept() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("plugin.ts");
+        let source = "export function createPlugin() {}\nexport function useHook() {}\nexport function helper() {}\n";
+        fs::write(&file, source).unwrap();
+
+        let mut graph = ReferenceGraph::new()
+            .with_always_used_exports(&["createPlugin".to_string(), "use*".to_string()]);
+        graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+
+        let unused: Vec<_> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedExport)
+            .map(|item| item.name.as_str())
+            .collect();
+        assert_eq!(unused, vec!["helper"]);
+    }
+
+    #[test]
+    fn test_namespace_reexport_member_usage() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        for (name, source) in [
+            (
+                "math.ts",
+                "export function add() {}\nexport function sub() {}\n",
+            ),
+            ("index.ts", "export * as math from './math';\n"),
+            ("app.ts", "import { math } from './index';\nmath.add();\n"),
+        ] {
+            let file = dir.path().join(name);
+            fs::write(&file, source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        }
+
+        let math = &graph.nodes[&dir.path().join("index.ts")].exports[0];
+        assert_eq!(math.name, "math");
+        assert_eq!(math.kind, SymbolKind::Namespace);
+        assert_eq!(math.source.as_deref(), Some("./math"));
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let unused: Vec<_> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedExport)
+            .map(|item| item.name.as_str())
+            .collect();
+        assert_eq!(unused, vec!["sub"]);
+    }
+
+    #[test]
+    fn test_destructured_default_import_members_are_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        for (name, source) in [
+            (
+                "config.ts",
+                "export const apiUrl = '/api';\nexport const timeout = 5;\nexport const retries = 3;\nexport default { apiUrl, timeout, retries };\n",
+            ),
+            (
+                "app.ts",
+                "import cfg from './config';\nconst { apiUrl, timeout: ms } = cfg;\nfetch(apiUrl, ms);\n",
+            ),
+        ] {
+            let file = dir.path().join(name);
+            fs::write(&file, source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        }
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        // `retries` is still read by the default export object
+        let unused: Vec<_> = dead_code
+            .iter()
+            .filter(|item| {
+                matches!(
+                    item.kind,
+                    DeadCodeKind::UnusedExport | DeadCodeKind::RedundantExport
+                )
+            })
+            .map(|item| item.name.as_str())
+            .collect();
+        assert_eq!(unused, vec!["retries"]);
+    }
+
+    #[test]
+    fn test_duplicate_findings_are_merged() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("utils.ts");
+        fs::write(&file, "export { helper, helper };\n").unwrap();
+
+        let export = ExportedSymbol {
+            name: "helper".to_string(),
+            kind: SymbolKind::Variable,
+            span: CodeSpan {
+                start: 1,
+                end: 1,
+                col_start: 0,
+                col_end: 26,
+                byte_start: 0,
+                byte_end: 0,
+            },
+            is_default: false,
+            is_reexport: false,
+            source: None,
+            local_name: None,
+            doc_comment: None,
+            is_async: false,
+        };
+        let mut graph = ReferenceGraph::new();
+        graph.add_node(ReferenceNode {
+            file_path: file.clone(),
+            exports: vec![export.clone(), export],
+            line_count: 1,
+            ..Default::default()
+        });
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let helpers: Vec<_> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedExport)
+            .collect();
+
+        assert_eq!(helpers.len(), 1);
+        assert_eq!(helpers[0].name, "helper");
+    }
+
+    #[test]
+    fn test_unused_enum_member_is_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        let color = dir.path().join("color.ts");
+        let app = dir.path().join("app.ts");
+        fs::write(
+            &color,
+            "export enum Color {\n  Red,\n  Green,\n  Teal,\n}\nexport const primary = Color.Red;\n",
+        )
+        .unwrap();
+        fs::write(
+            &app,
+            "import { Color, primary } from './color';\nconsole.log(primary, Color.Green);\n",
+        )
+        .unwrap();
+
+        let mut graph = ReferenceGraph::new();
+        for path in [&color, &app] {
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_file(path).unwrap());
+        }
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let members: Vec<_> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedEnum)
+            .collect();
+
+        assert_eq!(members.len(), 1);
+        assert_eq!(members[0].name, "Color.Teal");
+        assert_eq!(members[0].confidence, 0.6);
+        assert_eq!(members[0].span.start, 4);
+    }
+
+    #[test]
+    fn test_type_only_import_is_suggested() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("app.ts");
+        let source = "import { Config, load } from './config';\n\
+                      import { Logger } from './logger';\n\
+                      export const config: Config = load();\n\
+                      export function log(logger: Logger) { return new Logger(); }\n";
+        fs::write(&file, source).unwrap();
+
+        let mut graph = ReferenceGraph::new();
+        graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let type_only: Vec<&str> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::TypeOnlyImport)
+            .map(|item| item.name.as_str())
+            .collect();
+
+        // `Config` only annotates; `load` and `Logger` are used as values
+        assert_eq!(type_only, vec!["Config"]);
+        assert!(!dead_code
+            .iter()
+            .any(|item| item.kind == DeadCodeKind::UnusedImport));
+    }
+
+    #[test]
+    fn test_normalize_path() {
+        assert_eq!(
+            normalize_path(Path::new("/repo/api/../web/./shared")),
+            PathBuf::from("/repo/web/shared")
+        );
+        assert_eq!(
+            normalize_path(Path::new("./src/lib/../utils")),
+            PathBuf::from("./src/utils")
+        );
+        assert_eq!(
+            normalize_path(Path::new("../shared/x")),
+            PathBuf::from("../shared/x")
+        );
+    }
+
+    #[test]
+    fn test_resolve_directory_through_package_json() {
+        let dir = tempfile::tempdir().unwrap();
+        let package = dir.path().join("packages/ui");
+        fs::create_dir_all(package.join("lib")).unwrap();
+        fs::write(package.join("package.json"), r#"{"main": "lib/entry.ts"}"#).unwrap();
+
+        let mut graph = ReferenceGraph::new();
+        for path in [package.join("lib/entry.ts"), package.join("index.ts")] {
+            graph.add_node(ReferenceNode {
+                file_path: path,
+                ..Default::default()
+            });
+        }
+
+        let from = dir.path().join("app.ts");
+        assert_eq!(
+            graph.resolve_import(&from, "./packages/ui"),
+            Some(package.join("lib/entry.ts"))
+        );
+
+        // Without a usable entry the directory falls back to its index
+        fs::write(package.join("package.json"), r#"{"main": "missing.js"}"#).unwrap();
+        assert_eq!(
+            graph.resolve_import(&from, "./packages/ui"),
+            Some(package.join("index.ts"))
+        );
+    }
+
+    #[test]
+    fn test_jsdoc_tags_adjust_confidence() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("utils.ts");
+        let source = "/** Formats a date. @public */\n\
+                      export const format = 1;\n\
+                      /**\n * @deprecated use format\n */\n\
+                      export const legacyFormat = 2;\n\
+                      // plain comment\n\
+                      export const plain = 3;\n";
+        fs::write(&file, source).unwrap();
+
+        let mut graph = ReferenceGraph::new();
+        graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let confidence = |name: &str| {
+            dead_code
+                .iter()
+                .find(|item| item.kind == DeadCodeKind::UnusedExport && item.name == name)
+                .map(|item| item.confidence)
+                .unwrap()
+        };
+
+        assert!(confidence("format") < 0.5);
+        assert!(confidence("legacyFormat") > confidence("plain"));
+        assert_eq!(confidence("plain"), EXPORT_BASE_CONFIDENCE);
+
+        let public = dead_code.iter().find(|item| item.name == "format").unwrap();
+        let doc = public.context.as_ref().unwrap().doc_comment.as_deref();
+        assert_eq!(doc, Some("/** Formats a date. @public */"));
+    }
+
+    #[test]
+    fn test_hook_and_hoc_names_lower_confidence() {
+        let dir = tempfile::tempdir().unwrap();
+        let file = dir.path().join("hooks.ts");
+        let source = "export function useFoo() {}\n\
+                      export function withAuth() {}\n\
+                      export function user() {}\n\
+                      export function format() {}\n";
+        fs::write(&file, source).unwrap();
+        let node = crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap();
+
+        let confidence = |graph: &ReferenceGraph, name: &str| {
+            graph
+                .find_dead_code(dir.path(), 0.0)
+                .unwrap()
+                .into_iter()
+                .find(|item| item.kind == DeadCodeKind::UnusedExport && item.name == name)
+                .map(|item| item.confidence)
+                .unwrap()
+        };
+
+        let mut graph = ReferenceGraph::new();
+        graph.add_node(node.clone());
+        assert!(confidence(&graph, "useFoo") < confidence(&graph, "format"));
+        assert!(confidence(&graph, "withAuth") < confidence(&graph, "format"));
+        assert_eq!(confidence(&graph, "user"), confidence(&graph, "format"));
+
+        let mut strict = ReferenceGraph::new().with_framework_naming(false);
+        strict.add_node(node);
+        assert_eq!(confidence(&strict, "useFoo"), confidence(&strict, "format"));
+    }
+
+    #[test]
+    fn test_unreachable_island_is_zombie() {
+        let dir = tempfile::tempdir().unwrap();
+        let files = [
+            ("main.ts", "import { a } from './a';\nconsole.log(a);\n"),
+            ("a.ts", "export const a = 1;\n"),
+            // b and c only import each other
+            ("b.ts", "import { c } from './c';\nexport const b = c;\n"),
+            (
+                "c.ts",
+                "import { b } from './b';\nexport const c = 1;\nconsole.log(b);\n",
+            ),
+        ];
+        for (name, source) in files {
+            fs::write(dir.path().join(name), source).unwrap();
+        }
+
+        let build = |entries: &[PathBuf]| {
+            let mut graph = ReferenceGraph::new().with_entry_files(entries);
+            for (name, _) in files {
+                let path = dir.path().join(name);
+                graph.add_node(crate::scanner::AstAnalyzer::analyze_file(&path).unwrap());
+            }
+            graph
+        };
+        let zombies = |graph: &ReferenceGraph| {
+            let mut names: Vec<_> = graph
+                .find_dead_code(dir.path(), 0.0)
+                .unwrap()
+                .into_iter()
+                .filter(|item| item.kind == DeadCodeKind::ZombieFile)
+                .map(|item| item.name)
+                .collect();
+            names.sort();
+            names
+        };
+
+        assert!(zombies(&build(&[])).is_empty());
+        assert_eq!(
+            zombies(&build(&[dir.path().join("main.ts")])),
+            vec!["b.ts", "c.ts"]
+        );
+    }
+
+    #[test]
+    fn test_severity_by_kind() {
+        use crate::types::Severity;
+
+        for (kind, severity) in [
+            (DeadCodeKind::ZombieFile, Severity::High),
+            (DeadCodeKind::UnusedExport, Severity::High),
+            (DeadCodeKind::UnreachableFunction, Severity::High),
+            (DeadCodeKind::UnusedClass, Severity::High),
+            (DeadCodeKind::UnusedVariable, Severity::Medium),
+            (DeadCodeKind::UnusedType, Severity::Medium),
+            (DeadCodeKind::UnusedEnum, Severity::Medium),
+            (DeadCodeKind::DeadBranch, Severity::Medium),
+            (DeadCodeKind::UnusedImport, Severity::Low),
+            (DeadCodeKind::TypeOnlyImport, Severity::Low),
+            (DeadCodeKind::CircularImport, Severity::Low),
+        ] {
+            assert_eq!(kind.severity(), severity, "{}", kind);
+        }
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        let file = dir.path().join("lib.ts");
+        let source = "import { x } from './x';\nexport const unused = 1;\n";
+        fs::write(&file, source).unwrap();
+        graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        assert!(!dead_code.is_empty());
+        for item in &dead_code {
+            assert_eq!(item.severity, item.kind.severity());
+        }
+    }
+
+    #[test]
+    fn test_function_imported_only_by_dead_module_is_unreachable() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        for (name, source) in [
+            ("main.ts", "import { used } from './util';\nused();\n"),
+            (
+                "util.ts",
+                "export function used() {}\nexport function legacyOnly() {}\n",
+            ),
+            // Nothing imports legacy.ts, and report.ts only through it
+            (
+                "legacy.ts",
+                "import { report } from './report';\nexport const run = () => report();\n",
+            ),
+            (
+                "report.ts",
+                "import { legacyOnly } from './util';\nexport function report() { legacyOnly(); }\n",
+            ),
+        ] {
+            let file = dir.path().join(name);
+            fs::write(&file, source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        }
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let mut unreachable: Vec<_> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnreachableFunction)
+            .collect();
+        unreachable.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let names: Vec<&str> = unreachable.iter().map(|item| item.name.as_str()).collect();
+        assert_eq!(names, vec!["legacyOnly", "report"]);
+        assert!(unreachable[0]
+            .reason
+            .ends_with("legacyOnly ← report.ts ← legacy.ts"));
+        assert!(dead_code
+            .iter()
+            .any(|item| item.kind == DeadCodeKind::ZombieFile && item.name == "legacy.ts"));
+    }
+
+    #[test]
+    fn test_reexported_symbol_without_consumer_is_flagged() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        for (name, source) in [
+            (
+                "consts.ts",
+                "export const USED = 1;\nexport const RENAMED = 2;\nexport const DEAD = 3;\n",
+            ),
+            ("more.ts", "export const STARRED = 4;\nexport const UNSEEN = 5;\n"),
+            (
+                "barrel.ts",
+                "export { USED, RENAMED as ALIAS, DEAD } from './consts';\nexport * from './more';\n",
+            ),
+            (
+                "consumer.ts",
+                "import { USED, ALIAS, STARRED } from './barrel';\nconsole.log(USED, ALIAS, STARRED);\n",
+            ),
+        ] {
+            let file = dir.path().join(name);
+            fs::write(&file, source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        }
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let mut unused: Vec<(&str, &str)> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedExport)
+            .map(|item| (item.relative_path.as_str(), item.name.as_str()))
+            .collect();
+        unused.sort();
+
+        assert_eq!(unused, vec![("consts.ts", "DEAD"), ("more.ts", "UNSEEN")]);
+        // Origins count as imported once a consumer reaches them
+        assert!(!dead_code
+            .iter()
+            .any(|item| item.kind == DeadCodeKind::ZombieFile));
+    }
+
+    #[test]
+    fn test_todo_nearby_lowers_confidence() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        let file = dir.path().join("wip.ts");
+        let source = "\
+// TODO: wire this into the settings page
+export function draft() {}
+
+
+
+export function finished() {}
+
+
+
+let counter = 0;
+counter = 1; // FIXME
+";
+        fs::write(&file, source).unwrap();
+        graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let find = |name: &str| dead_code.iter().find(|item| item.name == name).unwrap();
+        let has_todo = |item: &DeadCodeItem| item.context.as_ref().is_some_and(|c| c.has_todo);
+
+        let draft = find("draft");
+        assert!(has_todo(draft));
+        assert!((draft.confidence - (find("finished").confidence - 0.2)).abs() < 1e-9);
+        assert!(!has_todo(find("finished")));
+        assert!(graph
+            .export_confidence_factors(&file, "draft")
+            .iter()
+            .any(|(factor, _)| factor.contains("TODO")));
+        // Other kinds carry the flag too
+        let counter = find("counter");
+        assert_eq!(counter.kind, DeadCodeKind::UnusedVariable);
+        assert!(has_todo(counter));
+    }
+
+    #[test]
+    fn test_snippet_includes_context_lines() {
+        let dir = tempfile::tempdir().unwrap();
+        let source = "import { a } from './a';\n\
+                      console.log(a);\n\
+                      // before\n\
+                      export function unused() {\n\
+                      \x20 return 1;\n\
+                      }\n\
+                      // after\n\
+                      // far\n";
+        let file = dir.path().join("lib.ts");
+        fs::write(&file, source).unwrap();
+
+        let find = |graph: ReferenceGraph| {
+            let mut graph = graph;
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+            let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+            dead_code
+                .into_iter()
+                .find(|item| item.name == "unused")
+                .unwrap()
+                .code_snippet
+        };
+
+        let snippet = find(ReferenceGraph::new().with_snippet_context(1));
+        assert_eq!(
+            snippet,
+            "// before\nexport function unused() {\n  return 1;\n}\n// after"
+        );
+
+        let snippet = find(
+            ReferenceGraph::new()
+                .with_snippet_context(1)
+                .with_snippet_max_lines(2),
+        );
+        assert_eq!(
+            snippet,
+            "// before\nexport function unused() {\n... (3 more lines)"
+        );
+    }
+
+    #[test]
+    fn test_public_api_lists_exports_reachable_from_entry() {
+        let dir = tempfile::tempdir().unwrap();
+        fs::write(
+            dir.path().join("package.json"),
+            r#"{"exports": {".": {"import": "./src/index.ts", "require": "./dist/index.cjs"}, "./package.json": "./package.json"}, "main": "./dist/index.cjs"}"#,
+        )
+        .unwrap();
+        fs::create_dir(dir.path().join("src")).unwrap();
+        let mut graph = ReferenceGraph::new();
+        for (name, source) in [
+            (
+                "src/index.ts",
+                "export { a as alpha } from './a';\nexport * from './b';\nexport const VERSION = '1';\n",
+            ),
+            ("src/a.ts", "export function a() {}\nexport function hidden() {}\n"),
+            ("src/b.ts", "export class B {}\nexport default 1;\n"),
+            ("src/internal.ts", "export const internal = 1;\n"),
+        ] {
+            let file = dir.path().join(name);
+            fs::write(&file, source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        }
+
+        assert_eq!(
+            package_entries(dir.path()),
+            vec!["./src/index.ts", "./dist/index.cjs"]
+        );
+        let entry = graph
+            .resolve_entry(&dir.path().join("./src/index.ts"))
+            .unwrap();
+        assert!(graph
+            .resolve_entry(&dir.path().join("./dist/index.cjs"))
+            .is_none());
+
+        let api = graph.public_api(dir.path(), &[entry]);
+        let listed: Vec<(&str, SymbolKind, &str, u32)> = api
+            .iter()
+            .map(|s| (s.name.as_str(), s.kind, s.relative_path.as_str(), s.line))
+            .collect();
+        assert_eq!(
+            listed,
+            vec![
+                ("B", SymbolKind::Class, "src/b.ts", 1),
+                ("VERSION", SymbolKind::Const, "src/index.ts", 3),
+                ("alpha", SymbolKind::Function, "src/a.ts", 1),
+            ]
+        );
+        assert!(api.iter().all(|s| s.entry == "src/index.ts"));
+    }
+
+    #[test]
+    fn test_export_only_used_in_own_file_is_redundant() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        for (name, source) in [
+            (
+                "math.ts",
+                "export const x = 2;\nexport const y = 3;\nexport function double() {\n  return x * 2;\n}\n",
+            ),
+            ("app.ts", "import { double } from './math';\ndouble();\n"),
+        ] {
+            let file = dir.path().join(name);
+            fs::write(&file, source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        }
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let x = dead_code.iter().find(|item| item.name == "x").unwrap();
+        assert_eq!(x.kind, DeadCodeKind::RedundantExport);
+        assert_eq!(x.severity, Severity::Low);
+        assert!(x.reason.contains("only used internally"));
+        assert!(!x.kind.is_removable());
+
+        let y = dead_code.iter().find(|item| item.name == "y").unwrap();
+        assert_eq!(y.kind, DeadCodeKind::UnusedExport);
+        assert!(!dead_code.iter().any(|item| item.name == "double"));
+    }
+
+    #[test]
+    fn test_large_project_lowers_confidence() {
+        let dir = tempfile::tempdir().unwrap();
+        let unused_in = |files: usize| {
+            let graph = chain_graph(dir.path(), files);
+            let file = dir.path().join("mod0.ts");
+            let factors = graph.export_confidence_factors(&file, "b0");
+            (graph.calculate_export_confidence(&file, "b0"), factors)
+        };
+
+        let (small, small_factors) = unused_in(3);
+        let (large, large_factors) = unused_in(LARGE_PROJECT_FILES);
+        assert_eq!(small, EXPORT_BASE_CONFIDENCE);
+        assert!(small_factors.is_empty());
+        assert!((large - (EXPORT_BASE_CONFIDENCE - 0.05)).abs() < 1e-9);
+        assert_eq!(large_factors, vec![("large project (1000+ files)", -0.05)]);
+    }
+
+    #[test]
+    fn test_async_export_passed_as_callback_is_used() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        for (name, source) in [
+            (
+                "jobs.ts",
+                "export async function load() {}\n\
+                 export const save = async () => {};\n\
+                 export async function unused() {}\n\
+                 export function sync() {}\n",
+            ),
+            (
+                "app.ts",
+                "import { load, save, sync } from './jobs';\n\
+                 register([load], { save });\n\
+                 void sync();\n",
+            ),
+        ] {
+            let file = dir.path().join(name);
+            fs::write(&file, source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        }
+
+        let jobs = &graph.nodes[&dir.path().join("jobs.ts")];
+        let is_async: Vec<_> = jobs.exports.iter().map(|e| e.is_async).collect();
+        assert_eq!(is_async, vec![true, true, true, false]);
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let unused: Vec<_> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedExport)
+            .collect();
+        assert_eq!(unused.len(), 1);
+        assert_eq!(unused[0].name, "unused");
+        assert_eq!(
+            unused[0].reason,
+            "Async export 'unused' has 0 references in the codebase"
+        );
+    }
+
+    #[test]
+    fn test_anonymous_default_exports_get_distinct_names() {
+        let dir = tempfile::tempdir().unwrap();
+        let mut graph = ReferenceGraph::new();
+        for (name, source) in [
+            ("config.ts", "export default () => ({ debug: false });\n"),
+            ("routes.ts", "export default function () { return []; }\n"),
+            (
+                "theme.ts",
+                "const theme = { dark: true };\nexport default theme;\n",
+            ),
+        ] {
+            let file = dir.path().join(name);
+            fs::write(&file, source).unwrap();
+            graph.add_node(crate::scanner::AstAnalyzer::analyze_source(&file, source).unwrap());
+        }
+
+        let dead_code = graph.find_dead_code(dir.path(), 0.0).unwrap();
+        let mut names: Vec<&str> = dead_code
+            .iter()
+            .filter(|item| item.kind == DeadCodeKind::UnusedExport)
+            .map(|item| item.name.as_str())
+            .collect();
+        names.sort();
+        assert_eq!(
+            names,
+            vec!["default(config.ts)", "default(routes.ts)", "default(theme)"]
+        );
+    }
 }
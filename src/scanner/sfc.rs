@@ -0,0 +1,144 @@
+//! Single-file components - the `<script>` blocks of `.vue` and `.svelte` files
+//!
+//! Rather than cutting the blocks out, everything around them is blanked
+//! to spaces (newlines kept), so byte offsets, lines and columns reported
+//! by the parser are already file coordinates.
+
+use std::collections::BTreeSet;
+use std::ops::Range;
+use std::path::Path;
+
+/// Extensions whose code lives in `<script>` blocks
+pub const SFC_EXTENSIONS: [&str; 2] = ["vue", "svelte"];
+
+/// One `<script>` block of a component
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ScriptBlock {
+    /// Byte range of the block's content, between the tags
+    pub content: Range<usize>,
+    /// `lang="ts"` (or `typescript`)
+    pub typescript: bool,
+    /// Svelte's module script (`context="module"` or `module`); `export let`
+    /// in the other script declares props, not module exports
+    pub module_context: bool,
+}
+
+pub fn is_sfc(path: &Path) -> bool {
+    path.extension()
+        .and_then(|ext| ext.to_str())
+        .is_some_and(|ext| SFC_EXTENSIONS.contains(&ext))
+}
+
+/// Find every `<script>` block, skipping unterminated ones
+pub fn script_blocks(source: &str) -> Vec<ScriptBlock> {
+    let mut blocks = Vec::new();
+    let mut rest = 0;
+    while let Some(found) = source[rest..].find("<script") {
+        let tag_start = rest + found + "<script".len();
+        // `<scripts>` or `<script-x>` are other elements
+        if !source[tag_start..].starts_with(|c: char| c == '>' || c.is_whitespace()) {
+            rest = tag_start;
+            continue;
+        }
+        let Some(tag_len) = source[tag_start..].find('>') else {
+            break;
+        };
+        let attributes = &source[tag_start..tag_start + tag_len];
+        let start = tag_start + tag_len + 1;
+        let Some(len) = source[start..].find("</script>") else {
+            break;
+        };
+        blocks.push(ScriptBlock {
+            content: start..start + len,
+            typescript: attribute(attributes, "lang")
+                .is_some_and(|lang| lang == "ts" || lang == "typescript"),
+            module_context: attribute(attributes, "context") == Some("module")
+                || attributes.split_whitespace().any(|word| word == "module"),
+        });
+        rest = start + len + "</script>".len();
+    }
+    blocks
+}
+
+/// The value of `name="value"`, `name='value'` or `name=value`
+fn attribute<'a>(attributes: &'a str, name: &str) -> Option<&'a str> {
+    attributes.split_whitespace().find_map(|attribute| {
+        let value = attribute.strip_prefix(name)?.strip_prefix('=')?;
+        Some(value.trim_matches(|c| c == '"' || c == '\''))
+    })
+}
+
+/// `source` with every byte outside the blocks blanked, newlines kept
+pub fn mask_to_scripts(source: &str, blocks: &[ScriptBlock]) -> String {
+    let mut bytes = source.as_bytes().to_vec();
+    let mut keep = vec![false; bytes.len()];
+    for block in blocks {
+        keep[block.content.clone()].fill(true);
+    }
+    for (byte, keep) in bytes.iter_mut().zip(keep) {
+        if !keep && *byte != b'\n' {
+            *byte = b' ';
+        }
+    }
+    // Blocks start and end at ASCII tags, so no character is split
+    String::from_utf8(bytes).unwrap_or_default()
+}
+
+/// Identifier-like words in the markup around the blocks, where templates
+/// use imports and script bindings; `my-button` tags also yield `MyButton`
+pub fn markup_identifiers(source: &str, blocks: &[ScriptBlock]) -> BTreeSet<String> {
+    let mut markup = String::with_capacity(source.len());
+    let mut rest = 0;
+    for block in blocks {
+        markup.push_str(&source[rest..block.content.start]);
+        markup.push(' ');
+        rest = block.content.end;
+    }
+    markup.push_str(&source[rest..]);
+
+    let mut identifiers = BTreeSet::new();
+    let is_word = |c: char| c.is_alphanumeric() || c == '_' || c == '$' || c == '-';
+    for word in markup
+        .split(|c: char| !is_word(c))
+        .filter(|word| !word.is_empty())
+    {
+        let parts: Vec<&str> = word.split('-').filter(|part| !part.is_empty()).collect();
+        if parts.len() > 1 {
+            identifiers.insert(parts.iter().map(|part| capitalize(part)).collect());
+        }
+        identifiers.extend(parts.into_iter().map(str::to_string));
+    }
+    identifiers
+}
+
+fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    chars
+        .next()
+        .map(|first| first.to_uppercase().chain(chars).collect())
+        .unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_mask_keeps_script_offsets() {
+        let source = "<template>\n  <my-button />\n</template>\n<script lang=\"ts\">\nexport const a = 1;\n</script>\n";
+        let blocks = script_blocks(source);
+        assert_eq!(blocks.len(), 1);
+        assert!(blocks[0].typescript);
+
+        let masked = mask_to_scripts(source, &blocks);
+        assert_eq!(masked.len(), source.len());
+        assert_eq!(masked.lines().count(), source.lines().count());
+        let offset = source.find("export").unwrap();
+        assert_eq!(&masked[offset..offset + 6], "export");
+        assert!(!masked.contains("template"));
+
+        let identifiers = markup_identifiers(source, &blocks);
+        assert!(identifiers.contains("MyButton"));
+        assert!(!identifiers.contains("a"));
+    }
+}
@@ -0,0 +1,128 @@
+//! Source Reader - Load file contents ahead of parsing
+//!
+//! Reading is IO-bound and parsing CPU-bound, so the scanner reads every
+//! file in its own bounded stage first. Large files can be memory-mapped
+//! instead of copied into a buffer. Files that are not valid UTF-8 are
+//! decoded lossily rather than skipped.
+
+use anyhow::{Context, Result};
+use memmap2::Mmap;
+use std::fs::File;
+use std::io::Read;
+use std::path::Path;
+use std::str::Utf8Error;
+
+/// Contents of a source file, as valid UTF-8
+pub enum SourceText {
+    Owned(String),
+    Mapped(Mmap),
+}
+
+impl SourceText {
+    pub fn as_str(&self) -> &str {
+        match self {
+            SourceText::Owned(text) => text,
+            // SAFETY: `read_source` checked the mapping is valid UTF-8
+            SourceText::Mapped(map) => unsafe { std::str::from_utf8_unchecked(map) },
+        }
+    }
+}
+
+/// Read a file, memory-mapping it when it is at least `mmap_threshold` bytes
+pub fn read_source(path: &Path, mmap_threshold: Option<u64>) -> Result<SourceText> {
+    let file = File::open(path).with_context(|| format!("Failed to read file: {:?}", path))?;
+    let len = file.metadata()?.len();
+
+    // Empty files cannot be mapped
+    if mmap_threshold.is_some_and(|threshold| len > 0 && len >= threshold) {
+        // SAFETY: the map is read-only; a file truncated by another process
+        // while mapped is outside what a scan can guard against
+        let map = unsafe { Mmap::map(&file) }
+            .with_context(|| format!("Failed to map file: {:?}", path))?;
+        return Ok(match std::str::from_utf8(&map) {
+            Ok(_) => SourceText::Mapped(map),
+            Err(e) => SourceText::Owned(decode_lossy(path, &map, e)),
+        });
+    }
+
+    let mut bytes = Vec::with_capacity(len as usize);
+    (&file)
+        .read_to_end(&mut bytes)
+        .with_context(|| format!("Failed to read file: {:?}", path))?;
+    let text = match String::from_utf8(bytes) {
+        Ok(text) => text,
+        Err(e) => decode_lossy(path, e.as_bytes(), e.utf8_error()),
+    };
+    Ok(SourceText::Owned(text))
+}
+
+/// Replace invalid UTF-8 sequences so the rest of the file is still analyzed
+fn decode_lossy(path: &Path, bytes: &[u8], e: Utf8Error) -> String {
+    tracing::warn!(
+        "{:?} is not valid UTF-8 (invalid byte at offset {}); decoding lossily",
+        path,
+        e.valid_up_to()
+    );
+    String::from_utf8_lossy(bytes).into_owned()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::scanner::AstAnalyzer;
+    use std::fs;
+
+    #[test]
+    fn test_mapped_and_buffered_reads_match() {
+        let dir = tempfile::tempdir().unwrap();
+        let files: Vec<_> = (0..200)
+            .map(|i| {
+                let path = dir.path().join(format!("module{}.ts", i));
+                let mut source = format!("import {{ dep }} from './module{}';\n", i + 1);
+                for j in 0..i % 20 {
+                    source.push_str(&format!("export const value{} = dep + {};\n", j, j));
+                }
+                fs::write(&path, source).unwrap();
+                path
+            })
+            .collect();
+
+        for path in &files {
+            let buffered = read_source(path, None).unwrap();
+            let mapped = read_source(path, Some(1)).unwrap();
+            assert!(matches!(buffered, SourceText::Owned(_)));
+            assert!(matches!(mapped, SourceText::Mapped(_)));
+
+            let buffered = AstAnalyzer::analyze_source(path, buffered.as_str()).unwrap();
+            let mapped = AstAnalyzer::analyze_source(path, mapped.as_str()).unwrap();
+            assert_eq!(
+                serde_json::to_value(&buffered).unwrap(),
+                serde_json::to_value(&mapped).unwrap()
+            );
+        }
+    }
+
+    #[test]
+    fn test_non_utf8_file_is_decoded_lossily() {
+        let dir = tempfile::tempdir().unwrap();
+        let path = dir.path().join("latin1.ts");
+        fs::write(
+            &path,
+            b"export const label = 'caf\xe9';\nexport const other = 1;\n",
+        )
+        .unwrap();
+
+        for threshold in [None, Some(1)] {
+            let source = read_source(&path, threshold).unwrap();
+            assert!(matches!(source, SourceText::Owned(_)));
+            assert_eq!(
+                source.as_str(),
+                "export const label = 'caf\u{fffd}';\nexport const other = 1;\n"
+            );
+
+            let node = AstAnalyzer::analyze_source(&path, source.as_str()).unwrap();
+            let names: Vec<_> = node.exports.iter().map(|e| e.name.as_str()).collect();
+            assert_eq!(names, vec!["label", "other"]);
+        }
+    }
+}
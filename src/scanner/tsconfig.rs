@@ -0,0 +1,245 @@
+//! TypeScript project config - `paths` / `baseUrl` import mappings
+//!
+//! Reads the root `tsconfig.json` and every project it lists under
+//! `references`, so imports through any project's aliases resolve.
+
+use super::reference_graph::normalize_path;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+
+/// Project config file read at the root and in referenced project directories
+pub const TSCONFIG_FILE: &str = "tsconfig.json";
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct TsConfig {
+    #[serde(default)]
+    compiler_options: CompilerOptions,
+    #[serde(default)]
+    references: Vec<ProjectReference>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CompilerOptions {
+    base_url: Option<String>,
+    #[serde(default)]
+    paths: BTreeMap<String, Vec<String>>,
+}
+
+#[derive(Debug, Deserialize)]
+struct ProjectReference {
+    path: String,
+}
+
+/// One `paths` entry: `prefix*suffix` mapped to targets with a `*` slot
+#[derive(Debug, Clone)]
+struct PathMapping {
+    prefix: String,
+    suffix: Option<String>,
+    targets: Vec<PathBuf>,
+}
+
+/// Non-relative import mappings from every loaded project
+#[derive(Debug, Clone, Default)]
+pub struct PathMappings {
+    /// Most specific (longest prefix) first
+    mappings: Vec<PathMapping>,
+    /// `baseUrl` directories, where bare imports also resolve
+    base_dirs: Vec<PathBuf>,
+}
+
+impl PathMappings {
+    /// Load `root/tsconfig.json` and, transitively, its project references
+    ///
+    /// Missing or invalid configs are skipped with a warning; resolution
+    /// then falls back to relative imports only.
+    pub fn load(root: &Path) -> Self {
+        let mut mappings = Self::default();
+        let mut visited = HashSet::new();
+        mappings.load_project(&root.join(TSCONFIG_FILE), &mut visited);
+        mappings
+            .mappings
+            .sort_by_key(|mapping| std::cmp::Reverse(mapping.prefix.len()));
+        mappings
+    }
+
+    /// Merge another root's mappings into these
+    pub fn extend(&mut self, other: PathMappings) {
+        self.mappings.extend(other.mappings);
+        self.mappings
+            .sort_by_key(|mapping| std::cmp::Reverse(mapping.prefix.len()));
+        self.base_dirs.extend(other.base_dirs);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.mappings.is_empty() && self.base_dirs.is_empty()
+    }
+
+    /// Paths a bare import may refer to, most specific mapping first
+    pub fn candidates(&self, source: &str) -> Vec<PathBuf> {
+        let mut candidates = Vec::new();
+        for mapping in &self.mappings {
+            let matched = match &mapping.suffix {
+                Some(suffix) => source
+                    .strip_prefix(mapping.prefix.as_str())
+                    .and_then(|rest| rest.strip_suffix(suffix.as_str())),
+                None => (source == mapping.prefix).then_some(""),
+            };
+            if let Some(wildcard) = matched {
+                candidates.extend(mapping.targets.iter().map(|target| {
+                    PathBuf::from(target.to_string_lossy().replacen('*', wildcard, 1))
+                }));
+            }
+        }
+        candidates.extend(self.base_dirs.iter().map(|dir| dir.join(source)));
+        candidates
+    }
+
+    fn load_project(&mut self, path: &Path, visited: &mut HashSet<PathBuf>) {
+        // A reference names either a config file or a directory holding one
+        let path = if path.is_dir() {
+            path.join(TSCONFIG_FILE)
+        } else {
+            path.to_path_buf()
+        };
+        if !visited.insert(path.clone()) || !path.is_file() {
+            return;
+        }
+        let config: TsConfig = match fs::read_to_string(&path)
+            .map_err(anyhow::Error::from)
+            .and_then(|text| Ok(serde_json::from_str(&strip_jsonc(&text))?))
+        {
+            Ok(config) => config,
+            Err(e) => {
+                tracing::warn!("Skipping {:?}: {}", path, e);
+                return;
+            }
+        };
+        let Some(dir) = path.parent() else {
+            return;
+        };
+
+        // `paths` are relative to `baseUrl` when set, else to the config itself
+        let base_url = config
+            .compiler_options
+            .base_url
+            .as_ref()
+            .map(|base_url| normalize_path(&dir.join(base_url)));
+        let paths_base = base_url.clone().unwrap_or_else(|| dir.to_path_buf());
+        for (pattern, targets) in &config.compiler_options.paths {
+            let (prefix, suffix) = match pattern.split_once('*') {
+                Some((prefix, suffix)) => (prefix.to_string(), Some(suffix.to_string())),
+                None => (pattern.clone(), None),
+            };
+            self.mappings.push(PathMapping {
+                prefix,
+                suffix,
+                targets: targets
+                    .iter()
+                    .map(|target| paths_base.join(target))
+                    .collect(),
+            });
+        }
+        self.base_dirs.extend(base_url);
+
+        for reference in &config.references {
+            self.load_project(&dir.join(&reference.path), visited);
+        }
+    }
+}
+
+/// Turn tsconfig's JSON-with-comments into plain JSON: drop `//` and
+/// `/* */` comments and trailing commas, leaving strings untouched
+fn strip_jsonc(text: &str) -> String {
+    let mut out = String::with_capacity(text.len());
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '"' => {
+                out.push(c);
+                while let Some(c) = chars.next() {
+                    out.push(c);
+                    match c {
+                        '\\' => out.extend(chars.next()),
+                        '"' => break,
+                        _ => {}
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'/') => {
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        out.push(c);
+                        break;
+                    }
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            ',' => {
+                // Keep the comma unless only whitespace separates it from a closer
+                let next = chars.clone().find(|c| !c.is_whitespace());
+                if !matches!(next, Some('}') | Some(']')) {
+                    out.push(c);
+                }
+            }
+            _ => out.push(c),
+        }
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_strip_jsonc() {
+        let text = r#"{
+            // line comment
+            "a": "http://x/*y*/", /* block */
+            "b": [1, 2,],
+        }"#;
+        let value: serde_json::Value = serde_json::from_str(&strip_jsonc(text)).unwrap();
+
+        assert_eq!(value["a"], "http://x/*y*/");
+        assert_eq!(value["b"], serde_json::json!([1, 2]));
+    }
+
+    #[test]
+    fn test_follows_project_references() {
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path();
+        fs::create_dir_all(root.join("packages/shared")).unwrap();
+        fs::write(
+            root.join(TSCONFIG_FILE),
+            r#"{ "references": [{ "path": "./packages/shared" }] }"#,
+        )
+        .unwrap();
+        fs::write(
+            root.join("packages/shared/tsconfig.json"),
+            r#"{
+                // the shared project maps its alias onto src
+                "compilerOptions": { "baseUrl": ".", "paths": { "@shared/*": ["src/*"] } },
+            }"#,
+        )
+        .unwrap();
+
+        let mappings = PathMappings::load(root);
+        let candidates = mappings.candidates("@shared/util");
+
+        assert_eq!(candidates[0], root.join("packages/shared/src/util"));
+        assert!(candidates.contains(&root.join("packages/shared/@shared/util")));
+    }
+}
@@ -3,7 +3,7 @@
 //! Provides a beautiful terminal interface using ratatui
 //! for exploring dead code scan results.
 
-use crate::types::{DeadCodeItem, DeadCodeKind, ScanOutput};
+use crate::types::{DeadCodeItem, DeadCodeKind, ScanOutput, Severity};
 use anyhow::Result;
 use crossterm::{
     event::{self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEventKind},
@@ -52,6 +52,20 @@ struct App<'a> {
     list_state: ListState,
     selected_index: usize,
     scroll_offset: u16,
+    /// Opened on first copy and kept, since some platforms drop the
+    /// clipboard contents along with it
+    clipboard: Option<arboard::Clipboard>,
+    /// Shown in the footer until the next key press
+    status: Option<String>,
+}
+
+/// What `y` and `Y` copy from the selected finding
+#[derive(Debug, Clone, Copy)]
+enum CopyPayload {
+    /// `relative_path:line`
+    Location,
+    /// The whole finding as pretty JSON
+    Json,
 }
 
 impl<'a> App<'a> {
@@ -64,6 +78,8 @@ impl<'a> App<'a> {
             list_state,
             selected_index: 0,
             scroll_offset: 0,
+            clipboard: None,
+            status: None,
         }
     }
 
@@ -118,6 +134,40 @@ impl<'a> App<'a> {
     fn selected_item(&self) -> Option<&DeadCodeItem> {
         self.scan_output.dead_code.get(self.selected_index)
     }
+
+    fn copy(&mut self, payload: CopyPayload) {
+        let Some(item) = self.selected_item() else {
+            return;
+        };
+        let text = match copy_text(item, payload) {
+            Ok(text) => text,
+            Err(e) => {
+                self.status = Some(format!("Copy failed: {}", e));
+                return;
+            }
+        };
+        let copied = match &mut self.clipboard {
+            Some(clipboard) => clipboard.set_text(text.as_str()),
+            None => arboard::Clipboard::new().and_then(|mut clipboard| {
+                let copied = clipboard.set_text(text.as_str());
+                self.clipboard = Some(clipboard);
+                copied
+            }),
+        };
+        self.status = Some(match (copied, payload) {
+            (Ok(()), CopyPayload::Location) => format!("Copied {}", text),
+            (Ok(()), CopyPayload::Json) => "Copied finding as JSON".to_string(),
+            (Err(e), _) => format!("Clipboard unavailable: {}", e),
+        });
+    }
+}
+
+/// Text `y` / `Y` put on the clipboard for a finding
+fn copy_text(item: &DeadCodeItem, payload: CopyPayload) -> Result<String> {
+    Ok(match payload {
+        CopyPayload::Location => item.location(),
+        CopyPayload::Json => serde_json::to_string_pretty(item)?,
+    })
 }
 
 fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App) -> Result<()> {
@@ -126,12 +176,15 @@ fn run_app(terminal: &mut Terminal<CrosstermBackend<io::Stdout>>, app: &mut App)
 
         if let Event::Key(key) = event::read()? {
             if key.kind == KeyEventKind::Press {
+                app.status = None;
                 match key.code {
                     KeyCode::Char('q') | KeyCode::Esc => return Ok(()),
                     KeyCode::Down | KeyCode::Char('j') => app.next(),
                     KeyCode::Up | KeyCode::Char('k') => app.previous(),
                     KeyCode::Char('J') | KeyCode::PageDown => app.scroll_down(),
                     KeyCode::Char('K') | KeyCode::PageUp => app.scroll_up(),
+                    KeyCode::Char('y') => app.copy(CopyPayload::Location),
+                    KeyCode::Char('Y') => app.copy(CopyPayload::Json),
                     _ => {}
                 }
             }
@@ -165,7 +218,7 @@ fn ui(f: &mut Frame, app: &mut App) {
     render_details(f, main_chunks[1], app);
 
     // Footer
-    render_footer(f, chunks[2]);
+    render_footer(f, chunks[2], app.status.as_deref());
 }
 
 fn render_header(f: &mut Frame, area: Rect, app: &App) {
@@ -211,6 +264,10 @@ fn render_list(f: &mut Frame, area: Rect, app: &mut App) {
         .enumerate()
         .map(|(i, item)| {
             let icon = kind_to_icon(&item.kind);
+            let has_todo = item
+                .context
+                .as_ref()
+                .is_some_and(|context| context.has_todo);
             let confidence_color = if item.confidence >= 0.8 {
                 Color::Red
             } else if item.confidence >= 0.5 {
@@ -222,12 +279,21 @@ fn render_list(f: &mut Frame, area: Rect, app: &mut App) {
             let content = Line::from(vec![
                 Span::raw(format!("{:>3}. ", i + 1)),
                 Span::raw(format!("{} ", icon)),
-                Span::styled(&item.name, Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    &item.name,
+                    Style::default()
+                        .fg(severity_color(item.severity))
+                        .add_modifier(Modifier::BOLD),
+                ),
                 Span::raw(" "),
                 Span::styled(
                     format!("{:.0}%", item.confidence * 100.0),
                     Style::default().fg(confidence_color),
                 ),
+                Span::styled(
+                    if has_todo { " TODO" } else { "" },
+                    Style::default().fg(Color::Yellow),
+                ),
             ]);
 
             ListItem::new(content)
@@ -257,6 +323,13 @@ fn render_details(f: &mut Frame, area: Rect, app: &App) {
                 Span::styled("Kind: ", Style::default().add_modifier(Modifier::BOLD)),
                 Span::raw(format!("{}", item.kind)),
             ]),
+            Line::from(vec![
+                Span::styled("Severity: ", Style::default().add_modifier(Modifier::BOLD)),
+                Span::styled(
+                    format!("{}", item.severity),
+                    Style::default().fg(severity_color(item.severity)),
+                ),
+            ]),
             Line::from(vec![
                 Span::styled("File: ", Style::default().add_modifier(Modifier::BOLD)),
                 Span::styled(&item.relative_path, Style::default().fg(Color::Cyan)),
@@ -286,12 +359,41 @@ fn render_details(f: &mut Frame, area: Rect, app: &App) {
             )]),
             Line::from(item.reason.as_str()),
             Line::from(""),
-            Line::from(vec![Span::styled(
-                "Code:",
-                Style::default().add_modifier(Modifier::BOLD),
-            )]),
         ];
 
+        if item
+            .context
+            .as_ref()
+            .is_some_and(|context| context.has_todo)
+        {
+            lines.push(Line::from(Span::styled(
+                "TODO/FIXME/HACK comment nearby; likely work in progress",
+                Style::default().fg(Color::Yellow),
+            )));
+            lines.push(Line::from(""));
+        }
+
+        // Files importing other symbols of this module, or a zombie's exports
+        let references = references_section(item);
+        if let Some((heading, entries)) = references.split_first() {
+            lines.push(Line::from(Span::styled(
+                heading.clone(),
+                Style::default().add_modifier(Modifier::BOLD),
+            )));
+            for entry in entries {
+                lines.push(Line::from(Span::styled(
+                    entry.clone(),
+                    Style::default().fg(Color::Cyan),
+                )));
+            }
+            lines.push(Line::from(""));
+        }
+
+        lines.push(Line::from(vec![Span::styled(
+            "Code:",
+            Style::default().add_modifier(Modifier::BOLD),
+        )]));
+
         // Add code snippet
         for line in item.code_snippet.lines() {
             lines.push(Line::from(Span::styled(
@@ -313,7 +415,15 @@ fn render_details(f: &mut Frame, area: Rect, app: &App) {
     f.render_widget(details, area);
 }
 
-fn render_footer(f: &mut Frame, area: Rect) {
+fn render_footer(f: &mut Frame, area: Rect, status: Option<&str>) {
+    if let Some(status) = status {
+        let footer = Paragraph::new(status.to_string())
+            .block(Block::default().borders(Borders::ALL).title("Help"))
+            .style(Style::default().fg(Color::Yellow));
+        f.render_widget(footer, area);
+        return;
+    }
+
     let help = Line::from(vec![
         Span::styled("↑/k", Style::default().fg(Color::Yellow)),
         Span::raw(" Up  "),
@@ -323,6 +433,8 @@ fn render_footer(f: &mut Frame, area: Rect) {
         Span::raw(" Scroll Up  "),
         Span::styled("J/PageDown", Style::default().fg(Color::Yellow)),
         Span::raw(" Scroll Down  "),
+        Span::styled("y/Y", Style::default().fg(Color::Yellow)),
+        Span::raw(" Copy location/JSON  "),
         Span::styled("q/Esc", Style::default().fg(Color::Yellow)),
         Span::raw(" Quit"),
     ]);
@@ -334,6 +446,15 @@ fn render_footer(f: &mut Frame, area: Rect) {
     f.render_widget(footer, area);
 }
 
+/// Names are colored by severity, percentages by confidence
+fn severity_color(severity: Severity) -> Color {
+    match severity {
+        Severity::High => Color::Red,
+        Severity::Medium => Color::Yellow,
+        Severity::Low => Color::Gray,
+    }
+}
+
 fn kind_to_icon(kind: &DeadCodeKind) -> &'static str {
     use DeadCodeKind::*;
     match kind {
@@ -346,5 +467,97 @@ fn kind_to_icon(kind: &DeadCodeKind) -> &'static str {
         UnusedClass => "🏛️",
         UnusedEnum => "🔢",
         DeadBranch => "🌿",
+        TypeOnlyImport => "🏷️",
+        CircularImport => "🔁",
+        RedundantExport => "🏠",
+    }
+}
+
+/// Heading and entries for the references part of the details pane; empty
+/// when the finding carries nothing to list
+fn references_section(item: &DeadCodeItem) -> Vec<String> {
+    let Some(context) = &item.context else {
+        return Vec::new();
+    };
+    let (heading, entries) = if item.kind == DeadCodeKind::ZombieFile {
+        ("Exports:", &context.exports)
+    } else {
+        ("Imported by (other symbols):", &context.partial_references)
+    };
+    if entries.is_empty() {
+        return Vec::new();
+    }
+
+    std::iter::once(heading.to_string())
+        .chain(entries.iter().map(|entry| format!("  {}", entry)))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::types::DeadCodeContext;
+
+    fn item(kind: DeadCodeKind, context: Option<DeadCodeContext>) -> DeadCodeItem {
+        DeadCodeItem {
+            context,
+            ..DeadCodeItem::fixture("src/util.ts", "helper", kind)
+        }
+    }
+
+    fn context(partial_references: &[&str], exports: &[&str]) -> DeadCodeContext {
+        DeadCodeContext {
+            possibly_dynamic: false,
+            in_test_file: false,
+            public_api: false,
+            partial_references: partial_references.iter().map(|s| s.to_string()).collect(),
+            doc_comment: None,
+            exports: exports.iter().map(|s| s.to_string()).collect(),
+            has_todo: false,
+        }
+    }
+
+    #[test]
+    fn test_references_section() {
+        let export = item(
+            DeadCodeKind::UnusedExport,
+            Some(context(&["src/a.ts", "src/b.ts"], &[])),
+        );
+        assert_eq!(
+            references_section(&export),
+            vec!["Imported by (other symbols):", "  src/a.ts", "  src/b.ts"]
+        );
+
+        let zombie = item(
+            DeadCodeKind::ZombieFile,
+            Some(context(&[], &["helper", "default"])),
+        );
+        assert_eq!(
+            references_section(&zombie),
+            vec!["Exports:", "  helper", "  default"]
+        );
+
+        assert!(references_section(&item(DeadCodeKind::UnusedExport, None)).is_empty());
+        assert!(
+            references_section(&item(DeadCodeKind::UnusedExport, Some(context(&[], &[]))))
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_copy_text() {
+        let mut finding = item(DeadCodeKind::UnusedExport, None);
+        finding.span.start = 12;
+
+        assert_eq!(
+            copy_text(&finding, CopyPayload::Location).unwrap(),
+            "src/util.ts:12:1"
+        );
+
+        let json = copy_text(&finding, CopyPayload::Json).unwrap();
+        assert!(json.contains("\n  \"name\": \"helper\""));
+        let parsed: DeadCodeItem = serde_json::from_str(&json).unwrap();
+        assert_eq!(parsed.relative_path, "src/util.ts");
+        assert_eq!(parsed.span.start, 12);
     }
 }
@@ -5,10 +5,12 @@
 
 use schemars::JsonSchema;
 use serde::{Deserialize, Serialize};
-use std::path::PathBuf;
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
+use std::time::Duration;
 
 /// The kind of dead code detected
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize, JsonSchema)]
 #[serde(rename_all = "snake_case")]
 pub enum DeadCodeKind {
     /// Exported symbol with no external references
@@ -25,10 +27,16 @@ pub enum DeadCodeKind {
     UnusedType,
     /// Class that is never instantiated or extended
     UnusedClass,
-    /// Enum that is never used
+    /// Enum, or enum member, that is never used
     UnusedEnum,
     /// Dead branch in conditional logic
     DeadBranch,
+    /// Value import only used in type positions; could be `import type`
+    TypeOnlyImport,
+    /// Files that import each other in a cycle (informational)
+    CircularImport,
+    /// Export only used inside its own file; could be a local
+    RedundantExport,
 }
 
 impl std::fmt::Display for DeadCodeKind {
@@ -43,12 +51,86 @@ impl std::fmt::Display for DeadCodeKind {
             DeadCodeKind::UnusedClass => write!(f, "unused_class"),
             DeadCodeKind::UnusedEnum => write!(f, "unused_enum"),
             DeadCodeKind::DeadBranch => write!(f, "dead_branch"),
+            DeadCodeKind::TypeOnlyImport => write!(f, "type_only_import"),
+            DeadCodeKind::CircularImport => write!(f, "circular_import"),
+            DeadCodeKind::RedundantExport => write!(f, "redundant_export"),
+        }
+    }
+}
+
+impl DeadCodeKind {
+    /// How much a finding of this kind matters, regardless of confidence:
+    /// a whole dead file or public symbol outweighs a stray import
+    pub fn severity(self) -> Severity {
+        match self {
+            DeadCodeKind::ZombieFile
+            | DeadCodeKind::UnusedExport
+            | DeadCodeKind::UnreachableFunction
+            | DeadCodeKind::UnusedClass => Severity::High,
+            DeadCodeKind::UnusedVariable
+            | DeadCodeKind::UnusedType
+            | DeadCodeKind::UnusedEnum
+            | DeadCodeKind::DeadBranch => Severity::Medium,
+            DeadCodeKind::UnusedImport
+            | DeadCodeKind::TypeOnlyImport
+            | DeadCodeKind::CircularImport
+            | DeadCodeKind::RedundantExport => Severity::Low,
+        }
+    }
+
+    /// Whether fixing a finding of this kind deletes code; type-only and
+    /// circular imports and redundant exports are still in use
+    pub fn is_removable(self) -> bool {
+        !matches!(
+            self,
+            DeadCodeKind::TypeOnlyImport
+                | DeadCodeKind::CircularImport
+                | DeadCodeKind::RedundantExport
+        )
+    }
+
+    /// Whether `clrd fix` acts on findings of this kind: it deletes removable
+    /// ones and rewrites type-only imports to `import type`
+    pub fn is_fixable(self) -> bool {
+        self.is_removable() || self == DeadCodeKind::TypeOnlyImport
+    }
+}
+
+/// Triage weight of a finding, ordered `Low < Medium < High`
+#[derive(
+    Debug,
+    Clone,
+    Copy,
+    Default,
+    PartialEq,
+    Eq,
+    PartialOrd,
+    Ord,
+    Hash,
+    Serialize,
+    Deserialize,
+    JsonSchema,
+)]
+#[serde(rename_all = "snake_case")]
+pub enum Severity {
+    Low,
+    #[default]
+    Medium,
+    High,
+}
+
+impl std::fmt::Display for Severity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Severity::Low => write!(f, "low"),
+            Severity::Medium => write!(f, "medium"),
+            Severity::High => write!(f, "high"),
         }
     }
 }
 
 /// Span information for code location
-#[derive(Debug, Clone, Copy, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
 pub struct CodeSpan {
     /// Starting line (1-indexed)
     pub start: u32,
@@ -58,21 +140,34 @@ pub struct CodeSpan {
     pub col_start: u32,
     /// Ending column (0-indexed)
     pub col_end: u32,
+    /// Starting byte offset in the source
+    ///
+    /// Offsets count from after a UTF-8 byte order mark, as editors show the
+    /// text; in a file that starts with one, the on-disk offset is 3 bytes more.
+    pub byte_start: u32,
+    /// Ending byte offset in the source (exclusive), also after any BOM
+    pub byte_end: u32,
 }
 
 /// A detected piece of dead code
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct DeadCodeItem {
+    /// Stable fingerprint of the finding, independent of its line numbers
+    pub id: String,
     /// Absolute path to the file
     pub file_path: PathBuf,
     /// Relative path from project root
     pub relative_path: String,
     /// Location in the file
     pub span: CodeSpan,
-    /// The actual code snippet (for LLM context)
+    /// The actual code snippet (for LLM context); omitted when empty
+    #[serde(default, skip_serializing_if = "String::is_empty")]
     pub code_snippet: String,
     /// Type of dead code
     pub kind: DeadCodeKind,
+    /// How much the finding matters, from its kind; independent of confidence
+    #[serde(default)]
+    pub severity: Severity,
     /// Name of the symbol (function name, variable name, etc.)
     pub name: String,
     /// Human-readable reason for flagging
@@ -85,8 +180,81 @@ pub struct DeadCodeItem {
     pub context: Option<DeadCodeContext>,
 }
 
+impl DeadCodeItem {
+    /// `relative_path:line:col`, with the 1-indexed column editors show
+    pub fn location(&self) -> String {
+        format!(
+            "{}:{}:{}",
+            self.relative_path,
+            self.span.start,
+            self.span.col_start + 1
+        )
+    }
+}
+
+#[cfg(test)]
+impl DeadCodeItem {
+    /// A finding for tests: `name` on line 1 of `relative_path`, with the
+    /// relative path as its file path and a confidence of 0.9
+    pub(crate) fn fixture(relative_path: &str, name: &str, kind: DeadCodeKind) -> Self {
+        Self {
+            id: finding_id(relative_path, kind, name),
+            file_path: PathBuf::from(relative_path),
+            relative_path: relative_path.to_string(),
+            span: CodeSpan {
+                start: 1,
+                end: 1,
+                col_start: 0,
+                col_end: 0,
+                byte_start: 0,
+                byte_end: 0,
+            },
+            code_snippet: String::new(),
+            kind,
+            severity: kind.severity(),
+            name: name.to_string(),
+            reason: String::new(),
+            confidence: 0.9,
+            context: None,
+        }
+    }
+
+    /// Move the finding to one line
+    pub(crate) fn at_line(mut self, line: u32) -> Self {
+        self.span.start = line;
+        self.span.end = line;
+        self
+    }
+
+    /// Resolve the file path against a project root
+    pub(crate) fn in_root(mut self, root: &Path) -> Self {
+        self.file_path = root.join(&self.relative_path);
+        self
+    }
+}
+
+/// Compute the stable id of a finding from its file, kind and symbol name
+///
+/// Uses 64-bit FNV-1a so the value is identical across runs, platforms and
+/// compiler versions (unlike `std`'s `DefaultHasher`).
+pub fn finding_id(relative_path: &str, kind: DeadCodeKind, name: &str) -> String {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    let kind = kind.to_string();
+    let mut hash = OFFSET_BASIS;
+    for part in [relative_path, kind.as_str(), name] {
+        // Separator keeps ("ab", "c") and ("a", "bc") distinct
+        for byte in part.bytes().chain(std::iter::once(0)) {
+            hash ^= u64::from(byte);
+            hash = hash.wrapping_mul(PRIME);
+        }
+    }
+    format!("{:016x}", hash)
+}
+
 /// Additional context to help LLM make decisions
-#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
 pub struct DeadCodeContext {
     /// Is this potentially a dynamic import/require?
     pub possibly_dynamic: bool,
@@ -99,6 +267,35 @@ pub struct DeadCodeContext {
     /// JSDoc or comment hints suggesting intentional code
     #[serde(skip_serializing_if = "Option::is_none")]
     pub doc_comment: Option<String>,
+    /// Names a zombie file exports
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub exports: Vec<String>,
+    /// A `TODO`, `FIXME` or `HACK` comment is nearby; likely work in progress
+    #[serde(default)]
+    pub has_todo: bool,
+}
+
+impl DeadCodeContext {
+    /// Combine the hints of two reports of the same finding
+    pub fn merge(&mut self, other: DeadCodeContext) {
+        self.possibly_dynamic |= other.possibly_dynamic;
+        self.in_test_file |= other.in_test_file;
+        self.public_api |= other.public_api;
+        self.has_todo |= other.has_todo;
+        for reference in other.partial_references {
+            if !self.partial_references.contains(&reference) {
+                self.partial_references.push(reference);
+            }
+        }
+        if self.doc_comment.is_none() {
+            self.doc_comment = other.doc_comment;
+        }
+        for export in other.exports {
+            if !self.exports.contains(&export) {
+                self.exports.push(export);
+            }
+        }
+    }
 }
 
 /// Result of a scan operation
@@ -110,8 +307,15 @@ pub struct ScanOutput {
     pub root: String,
     /// Timestamp of scan
     pub timestamp: String,
-    /// All detected dead code items
+    /// Detected dead code items (capped by `--max-results` and `--max-per-file`)
     pub dead_code: Vec<DeadCodeItem>,
+    /// Number of findings before `--max-results` and `--max-per-file` were
+    /// applied; with `--staged`, in the whole project
+    #[serde(default)]
+    pub total_findings: u32,
+    /// Whether `dead_code` omits findings because of `--max-results` or `--max-per-file`
+    #[serde(default)]
+    pub truncated: bool,
     /// Total files scanned
     pub total_files_scanned: u32,
     /// Total lines of code analyzed
@@ -120,6 +324,121 @@ pub struct ScanOutput {
     pub scan_duration_ms: u64,
     /// Summary statistics
     pub summary: ScanSummary,
+    /// Files that failed to parse cleanly
+    #[serde(default)]
+    pub parse_errors: Vec<ParseErrorInfo>,
+    /// Ignore patterns that are invalid or matched no files
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub ignore_warnings: Vec<String>,
+    /// Relative imports that resolve to no scanned file
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub unresolved_imports: Vec<UnresolvedImport>,
+    /// Files not analyzed because they exceed the size limit
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub skipped_files: Vec<SkippedFile>,
+    /// Per-phase profiling data (only with `--stats`)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stats: Option<ScanStats>,
+    /// Hash of each analyzed file's contents when it was read
+    #[serde(skip)]
+    pub content_hashes: HashMap<PathBuf, u64>,
+}
+
+/// JSON Schema of the scan output, for LLM tool definitions
+pub fn output_schema() -> String {
+    let schema = schemars::schema_for!(ScanOutput);
+    serde_json::to_string_pretty(&schema).expect("schema serializes to JSON")
+}
+
+/// A parse error reported for a scanned file
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct ParseErrorInfo {
+    /// Path of the file (relative to the root in scan output)
+    pub path: String,
+    /// Line of the error (1-indexed, 0 if unknown)
+    pub line: u32,
+    /// Parser message
+    pub message: String,
+}
+
+/// A relative import that resolves to no scanned file
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct UnresolvedImport {
+    /// Path of the importing file, relative to the root
+    pub path: String,
+    /// Line of the import
+    pub line: u32,
+    /// Import source as written
+    pub source: String,
+}
+
+/// A file left out of the scan for exceeding `max_file_bytes`
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize, JsonSchema)]
+pub struct SkippedFile {
+    /// Path of the file, relative to the root
+    pub path: String,
+    /// Size of the file in bytes
+    pub bytes: u64,
+}
+
+/// Profiling data collected during a scan
+#[derive(Debug, Clone, Default, Serialize, Deserialize, JsonSchema)]
+pub struct ScanStats {
+    /// Time spent walking the file system
+    #[serde(with = "duration_ms")]
+    #[schemars(with = "f64")]
+    pub walk: Duration,
+    /// Time spent reading files
+    #[serde(default, with = "duration_ms")]
+    #[schemars(with = "f64")]
+    pub read: Duration,
+    /// Time spent parsing files
+    #[serde(with = "duration_ms")]
+    #[schemars(with = "f64")]
+    pub parse: Duration,
+    /// Time spent inserting parsed files into the reference graph
+    #[serde(with = "duration_ms")]
+    #[schemars(with = "f64")]
+    pub graph: Duration,
+    /// Time spent detecting dead code
+    #[serde(with = "duration_ms")]
+    #[schemars(with = "f64")]
+    pub detect: Duration,
+    /// Files parsed successfully
+    pub files_parsed: u32,
+    /// Files reused unchanged from the incremental cache
+    #[serde(default)]
+    pub files_cached: u32,
+    /// Files that could not be read or analyzed
+    pub files_failed: u32,
+    /// Total parser errors across all files
+    pub parse_errors: u32,
+    /// Slowest files to parse, slowest first
+    pub slowest_files: Vec<FileTiming>,
+}
+
+/// Parse duration of a single file
+#[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
+pub struct FileTiming {
+    pub path: String,
+    #[serde(with = "duration_ms")]
+    #[schemars(with = "f64")]
+    pub duration: Duration,
+}
+
+/// Serialize a `Duration` as fractional milliseconds
+mod duration_ms {
+    use serde::{Deserialize, Deserializer, Serializer};
+    use std::time::Duration;
+
+    pub fn serialize<S: Serializer>(duration: &Duration, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_f64(duration.as_secs_f64() * 1000.0)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Duration, D::Error> {
+        let ms = f64::deserialize(deserializer)?;
+        Ok(Duration::from_secs_f64(ms.max(0.0) / 1000.0))
+    }
 }
 
 /// Summary statistics from a scan
@@ -134,6 +453,69 @@ pub struct ScanSummary {
     pub total_issues: u32,
     pub high_confidence_issues: u32,
     pub low_confidence_issues: u32,
+    /// Number of files with parse errors
+    #[serde(default)]
+    pub parse_errors: u32,
+    /// Number of files skipped for exceeding the size limit
+    #[serde(default)]
+    pub skipped_files: u32,
+    /// Number of files with at least one finding
+    #[serde(default)]
+    pub files_with_issues: u32,
+    /// Fraction of scanned lines covered by findings (0.0 - 1.0)
+    #[serde(default)]
+    pub dead_code_ratio: f64,
+    /// Cleanliness score from 0 (messy) to 100 (clean), see `compute_health`
+    #[serde(default)]
+    pub health_score: u32,
+    /// Letter grade for `health_score` (A - F)
+    #[serde(default)]
+    pub health_grade: String,
+    /// What fixing every high-confidence finding would remove
+    #[serde(default)]
+    pub estimated_removal: RemovalEstimate,
+    /// Findings per confidence bucket: 0.0-0.2, 0.2-0.4, 0.4-0.6, 0.6-0.8, 0.8-1.0
+    #[serde(default)]
+    pub confidence_histogram: [u32; 5],
+    /// Findings left out of `dead_code` by `--max-per-file`, per file, by its
+    /// path relative to the first scan root
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub suppressed_per_file: BTreeMap<String, u32>,
+}
+
+/// Lines a cleanup would remove and the files it touches
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize, JsonSchema)]
+pub struct RemovalEstimate {
+    /// Lines covered by the findings, overlapping spans counted once
+    pub lines: u64,
+    /// Files with at least one finding
+    pub files: u32,
+}
+
+impl RemovalEstimate {
+    /// Sum the lines the findings span, per file; a zombie file covers all
+    /// `file_lines` of it
+    pub fn of<'a>(
+        items: impl IntoIterator<Item = &'a DeadCodeItem>,
+        file_lines: impl Fn(&Path) -> u32,
+    ) -> Self {
+        let mut lines: HashMap<&Path, HashSet<u32>> = HashMap::new();
+        for item in items {
+            let (start, end) = if item.kind == DeadCodeKind::ZombieFile {
+                (1, file_lines(&item.file_path))
+            } else {
+                (item.span.start, item.span.end)
+            };
+            lines
+                .entry(item.file_path.as_path())
+                .or_default()
+                .extend(start..=end);
+        }
+        Self {
+            lines: lines.values().map(|file| file.len() as u64).sum(),
+            files: lines.len() as u32,
+        }
+    }
 }
 
 impl ScanSummary {
@@ -148,9 +530,35 @@ impl ScanSummary {
             total_issues: 0,
             high_confidence_issues: 0,
             low_confidence_issues: 0,
+            parse_errors: 0,
+            skipped_files: 0,
+            files_with_issues: 0,
+            dead_code_ratio: 0.0,
+            health_score: 100,
+            health_grade: health_grade(100).to_string(),
+            estimated_removal: RemovalEstimate::default(),
+            confidence_histogram: [0; 5],
+            suppressed_per_file: BTreeMap::new(),
         }
     }
 
+    /// Score the counted findings against the size of the codebase
+    ///
+    /// High-confidence issues weigh 3, others 1. With `density` the weighted
+    /// issues per 1000 lines, the score is `100 / (1 + density / 10)`, rounded:
+    /// 10 weighted issues per 1000 lines halve the score.
+    pub fn compute_health(&mut self, total_lines: u64) {
+        let weighted = 3.0 * self.high_confidence_issues as f64 + self.low_confidence_issues as f64;
+        let density = if total_lines == 0 {
+            0.0
+        } else {
+            weighted * 1000.0 / total_lines as f64
+        };
+
+        self.health_score = (100.0 / (1.0 + density / 10.0)).round() as u32;
+        self.health_grade = health_grade(self.health_score).to_string();
+    }
+
     pub fn add(&mut self, item: &DeadCodeItem) {
         self.total_issues += 1;
 
@@ -159,6 +567,9 @@ impl ScanSummary {
         } else {
             self.low_confidence_issues += 1;
         }
+        // Each bucket includes its lower bound; 1.0 falls in the last one
+        let bucket = (item.confidence * 5.0).floor().clamp(0.0, 4.0) as usize;
+        self.confidence_histogram[bucket] += 1;
 
         match item.kind {
             DeadCodeKind::UnusedExport => self.unused_exports += 1,
@@ -169,11 +580,25 @@ impl ScanSummary {
             DeadCodeKind::UnusedType | DeadCodeKind::UnusedClass | DeadCodeKind::UnusedEnum => {
                 self.unused_types += 1
             }
-            DeadCodeKind::DeadBranch => {}
+            DeadCodeKind::DeadBranch
+            | DeadCodeKind::TypeOnlyImport
+            | DeadCodeKind::CircularImport
+            | DeadCodeKind::RedundantExport => {}
         }
     }
 }
 
+/// Letter grade for a health score: A from 90, B from 80, C from 70, D from 60, else F
+pub fn health_grade(score: u32) -> &'static str {
+    match score {
+        90.. => "A",
+        80..=89 => "B",
+        70..=79 => "C",
+        60..=69 => "D",
+        _ => "F",
+    }
+}
+
 impl Default for ScanSummary {
     fn default() -> Self {
         Self::new()
@@ -191,10 +616,119 @@ pub struct ClrConfig {
     pub include_tests: bool,
     /// Minimum confidence threshold for reporting
     pub confidence_threshold: f64,
+    /// Files never reported as zombies (glob patterns, relative to the root)
+    #[serde(default = "default_entry_point_globs")]
+    pub entry_point_globs: Vec<String>,
+    /// Files treated as tests (glob patterns)
+    #[serde(default = "default_test_file_globs")]
+    pub test_file_globs: Vec<String>,
+    /// Files whose exports form the public API (glob patterns, relative to the root)
+    #[serde(default = "default_public_api_globs")]
+    pub public_api_globs: Vec<String>,
+    /// Export names never reported as unused (exact names or glob patterns)
+    #[serde(default)]
+    pub always_used_exports: Vec<String>,
+    /// Count imports from test files as usage without reporting findings in them
+    #[serde(default)]
+    pub consider_tests_usage: bool,
+    /// Also report import cycles as `circular_import` findings
+    #[serde(default)]
+    pub detect_cycles: bool,
+    /// Finding kinds never reported
+    #[serde(default)]
+    pub excluded_kinds: Vec<DeadCodeKind>,
+    /// Drop findings whose context marks them as possibly used dynamically
+    #[serde(default)]
+    pub exclude_possibly_dynamic: bool,
+    /// Exit with a non-zero code when a finding reaches this confidence
+    #[serde(default = "default_fail_on")]
+    pub fail_on: f64,
+    /// Disable colored output
+    #[serde(default)]
+    pub no_color: bool,
+    /// Files whose first lines contain one of these are generated and never reported
+    #[serde(default = "default_generated_markers")]
+    pub generated_markers: Vec<String>,
+    /// Lines shown before and after each finding's code snippet
+    #[serde(default)]
+    pub snippet_context: usize,
+    /// Longest code snippet in lines before it is cut (0 for no limit)
+    #[serde(default = "default_snippet_max_lines")]
+    pub snippet_max_lines: usize,
+    /// Files larger than this many bytes are skipped (0 for no limit)
+    #[serde(default = "default_max_file_bytes")]
+    pub max_file_bytes: u64,
     /// Output format preferences
     pub output: OutputConfig,
 }
 
+/// Default confidence at which a scan exits non-zero: the high-confidence cutoff
+pub fn default_fail_on() -> f64 {
+    0.8
+}
+
+/// Default cap on code snippet length, in lines
+pub fn default_snippet_max_lines() -> usize {
+    10
+}
+
+/// Default size above which files are skipped: 2 MiB, far beyond hand-written source
+pub fn default_max_file_bytes() -> u64 {
+    2 * 1024 * 1024
+}
+
+/// Default markers identifying generated files
+pub fn default_generated_markers() -> Vec<String> {
+    vec!["@generated".into()]
+}
+
+/// Default scanned file extensions, in import resolution order
+pub fn default_extensions() -> Vec<String> {
+    vec![
+        "ts".into(),
+        "tsx".into(),
+        "js".into(),
+        "jsx".into(),
+        "mjs".into(),
+        "cjs".into(),
+        "vue".into(),
+        "svelte".into(),
+    ]
+}
+
+/// Default entry-point patterns: index/main/app files and router directories
+pub fn default_entry_point_globs() -> Vec<String> {
+    vec![
+        "**/index.*".into(),
+        "**/main.*".into(),
+        "**/app.*".into(),
+        "**/pages/**".into(),
+        "**/routes/**".into(),
+    ]
+}
+
+/// Default test-file patterns
+pub fn default_test_file_globs() -> Vec<String> {
+    vec![
+        "**/*.test.*".into(),
+        "**/*.spec.*".into(),
+        "**/*_test.*".into(),
+        "**/*_spec.*".into(),
+        "**/__tests__/**".into(),
+        "**/__mocks__/**".into(),
+    ]
+}
+
+/// Default public API patterns: the package's root index module
+pub fn default_public_api_globs() -> Vec<String> {
+    vec![
+        "index.ts".into(),
+        "index.js".into(),
+        "src/index*".into(),
+        "lib/index*".into(),
+    ]
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct OutputConfig {
     /// Generate agent.md
@@ -203,19 +737,31 @@ pub struct OutputConfig {
     pub claude_md: bool,
     /// Generate .cursorrules
     pub cursorrules: bool,
+    /// Generate .github/copilot-instructions.md
+    pub copilot_instructions: bool,
+    /// Generate .windsurfrules
+    pub windsurfrules: bool,
+    /// Generate GEMINI.md
+    pub gemini_md: bool,
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            agent_md: true,
+            claude_md: true,
+            cursorrules: true,
+            copilot_instructions: true,
+            windsurfrules: true,
+            gemini_md: true,
+        }
+    }
 }
 
 impl Default for ClrConfig {
     fn default() -> Self {
         Self {
-            extensions: vec![
-                "ts".into(),
-                "tsx".into(),
-                "js".into(),
-                "jsx".into(),
-                "mjs".into(),
-                "cjs".into(),
-            ],
+            extensions: default_extensions(),
             ignore_patterns: vec![
                 "**/node_modules/**".into(),
                 "**/dist/**".into(),
@@ -227,34 +773,119 @@ impl Default for ClrConfig {
             ],
             include_tests: false,
             confidence_threshold: 0.5,
-            output: OutputConfig {
-                agent_md: true,
-                claude_md: true,
-                cursorrules: true,
-            },
+            entry_point_globs: default_entry_point_globs(),
+            test_file_globs: default_test_file_globs(),
+            public_api_globs: default_public_api_globs(),
+            always_used_exports: Vec::new(),
+            consider_tests_usage: false,
+            detect_cycles: false,
+            excluded_kinds: Vec::new(),
+            exclude_possibly_dynamic: false,
+            fail_on: default_fail_on(),
+            no_color: false,
+            generated_markers: default_generated_markers(),
+            snippet_context: 0,
+            snippet_max_lines: default_snippet_max_lines(),
+            max_file_bytes: default_max_file_bytes(),
+            output: OutputConfig::default(),
+        }
+    }
+}
+
+impl ClrConfig {
+    /// CI preset: test imports count as usage, only near-certain findings
+    /// fail the build, and output is plain text
+    pub fn ci() -> Self {
+        Self {
+            consider_tests_usage: true,
+            fail_on: 0.9,
+            no_color: true,
+            ..Self::default()
+        }
+    }
+
+    /// Strict preset: report every kind, down to low-confidence findings
+    pub fn strict() -> Self {
+        Self {
+            confidence_threshold: 0.1,
+            detect_cycles: true,
+            ..Self::default()
+        }
+    }
+
+    /// Safe preset: only high-confidence findings, without zombie files or
+    /// symbols that may be used dynamically
+    pub fn safe() -> Self {
+        Self {
+            confidence_threshold: 0.8,
+            excluded_kinds: vec![DeadCodeKind::ZombieFile],
+            exclude_possibly_dynamic: true,
+            ..Self::default()
         }
     }
 }
 
 /// Reference graph node
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ReferenceNode {
     pub file_path: PathBuf,
     pub exports: Vec<ExportedSymbol>,
     pub imports: Vec<ImportedSymbol>,
+    /// Top-level declarations that are not exported
+    pub locals: Vec<LocalSymbol>,
+    /// Identifiers read anywhere in the file, in values or types
     pub internal_refs: Vec<String>,
+    /// Identifiers that only ever appear in type positions
+    pub type_refs: Vec<String>,
+    /// Identifiers assigned or updated anywhere in the file
+    pub write_refs: Vec<String>,
+    /// `object.property` accesses on plain identifiers
+    pub member_refs: Vec<(String, String)>,
+    /// Plain identifiers indexed with a computed key, as in `object[key]`
+    #[serde(default)]
+    pub computed_member_refs: Vec<String>,
+    /// Variables initialized with an `as const` object literal
+    #[serde(default)]
+    pub const_objects: Vec<String>,
+    /// Enums declared in the file, with their members
+    pub enums: Vec<EnumSymbol>,
+    /// Instance fields assigned through `this` but never read in their class
+    #[serde(default)]
+    pub write_only_fields: Vec<ClassField>,
+    /// Lines of `TODO`, `FIXME` and `HACK` comments
+    #[serde(default)]
+    pub todo_lines: Vec<u32>,
+    /// Imported names only referenced where a local declaration shadows them
+    #[serde(default)]
+    pub shadowed_refs: Vec<String>,
+    /// Errors the parser recovered from
+    pub parse_errors: Vec<ParseErrorInfo>,
+    /// Number of lines in the source file
+    pub line_count: u32,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportedSymbol {
     pub name: String,
     pub kind: SymbolKind,
     pub span: CodeSpan,
     pub is_default: bool,
     pub is_reexport: bool,
+    /// Module a re-export comes from
+    pub source: Option<String>,
+    /// Name in that module, for re-exported specifiers (`a` in `export { a as b } from`),
+    /// or the local binding of `export default a`
+    #[serde(default)]
+    pub local_name: Option<String>,
+    /// JSDoc comment directly before the export
+    #[serde(default)]
+    pub doc_comment: Option<String>,
+    /// Whether it is an `async` function, or a variable bound to one
+    #[serde(default)]
+    pub is_async: bool,
 }
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ImportedSymbol {
     pub name: String,
     pub alias: Option<String>,
@@ -263,7 +894,30 @@ pub struct ImportedSymbol {
     pub span: CodeSpan,
 }
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LocalSymbol {
+    pub name: String,
+    pub kind: SymbolKind,
+    pub span: CodeSpan,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnumSymbol {
+    pub name: String,
+    /// Member names with the span of each member
+    pub members: Vec<(String, CodeSpan)>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ClassField {
+    pub class: String,
+    /// Field name, with a leading `#` for private fields
+    pub name: String,
+    /// The field declaration, or its first assignment
+    pub span: CodeSpan,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum SymbolKind {
     Function,
     Class,
@@ -276,6 +930,37 @@ pub enum SymbolKind {
     Namespace,
 }
 
+impl std::fmt::Display for SymbolKind {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            SymbolKind::Function => write!(f, "function"),
+            SymbolKind::Class => write!(f, "class"),
+            SymbolKind::Variable => write!(f, "variable"),
+            SymbolKind::Type => write!(f, "type"),
+            SymbolKind::Interface => write!(f, "interface"),
+            SymbolKind::Enum => write!(f, "enum"),
+            SymbolKind::Const => write!(f, "const"),
+            SymbolKind::Let => write!(f, "let"),
+            SymbolKind::Namespace => write!(f, "namespace"),
+        }
+    }
+}
+
+/// An export reachable from a package entry point
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ApiSymbol {
+    /// Name consumers import it by
+    pub name: String,
+    pub kind: SymbolKind,
+    /// Entry point exposing it, relative to the root
+    pub entry: String,
+    /// File declaring it, relative to the root
+    pub relative_path: String,
+    /// Line of the declaration
+    pub line: u32,
+    pub is_default: bool,
+}
+
 /// LLM judgment request format
 #[derive(Debug, Clone, Serialize, Deserialize, JsonSchema)]
 pub struct LlmJudgmentRequest {